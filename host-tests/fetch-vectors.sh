@@ -0,0 +1,11 @@
+#!/bin/sh
+# Download the upstream test vectors used by tests/sighash.rs into vectors/.
+set -eu
+
+cd "$(dirname "$0")"
+mkdir -p vectors
+
+curl -fsSL -o vectors/sighash.json \
+    https://raw.githubusercontent.com/bitcoin/bitcoin/v27.0/src/test/data/sighash.json
+curl -fsSL -o vectors/bip341_wallet_vectors.json \
+    https://raw.githubusercontent.com/bitcoin/bips/master/bip-0341/wallet-test-vectors.json
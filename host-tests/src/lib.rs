@@ -0,0 +1,78 @@
+//! 在主机上编译固件中不依赖 Flipper SDK 的模块，供 tests/ 下的测试向量使用
+
+#[path = "../../src/address.rs"]
+pub mod address;
+#[path = "../../src/aes.rs"]
+pub mod aes;
+#[path = "../../src/base58.rs"]
+pub mod base58;
+#[path = "../../src/bech32.rs"]
+pub mod bech32;
+#[path = "../../src/bip32.rs"]
+pub mod bip32;
+#[path = "../../src/bip39.rs"]
+pub mod bip39;
+#[path = "../../src/coin_selection.rs"]
+pub mod coin_selection;
+#[path = "../../src/fixed_string.rs"]
+pub mod fixed_string;
+#[path = "../../src/flipper_wallet_core.rs"]
+pub mod flipper_wallet_core;
+#[path = "../../src/hex.rs"]
+pub mod hex;
+#[path = "../../src/keccak256.rs"]
+pub mod keccak256;
+#[path = "../../src/network.rs"]
+pub mod network;
+#[path = "../../src/pbkdf2.rs"]
+pub mod pbkdf2;
+#[path = "../../src/psbt.rs"]
+pub mod psbt;
+#[path = "../../src/qrcodegen.rs"]
+pub mod qrcodegen;
+#[path = "../../src/ripemd160.rs"]
+pub mod ripemd160;
+#[path = "../../src/script.rs"]
+pub mod script;
+#[path = "../../src/secp256k1.rs"]
+pub mod secp256k1;
+#[path = "../../src/sha256.rs"]
+pub mod sha256;
+#[path = "../../src/sha512.rs"]
+pub mod sha512;
+#[path = "../../src/sighash.rs"]
+pub mod sighash;
+#[path = "../../src/spend.rs"]
+pub mod spend;
+#[path = "../../src/storage.rs"]
+pub mod storage;
+#[path = "../../src/transaction.rs"]
+pub mod transaction;
+#[path = "../../src/trng.rs"]
+pub mod trng;
+#[path = "../../src/utxo.rs"]
+pub mod utxo;
+#[path = "../../src/wif.rs"]
+pub mod wif;
+#[path = "../../src/word_list.rs"]
+pub mod word_list;
+
+/// 代替固件中的硬件随机数（xorshift，仅供测试，不可用于生成真实密钥）
+///
+/// # Safety
+///
+/// buf 必须指向至少 len 个可写字节。
+#[no_mangle]
+pub unsafe extern "C" fn furi_hal_random_fill_buf(buf: *mut u8, len: u32) {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static STATE: AtomicU64 = AtomicU64::new(0x9E37_79B9_7F4A_7C15);
+
+    for i in 0..len as usize {
+        let mut x = STATE.load(Ordering::Relaxed);
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        STATE.store(x, Ordering::Relaxed);
+        *buf.add(i) = x as u8;
+    }
+}
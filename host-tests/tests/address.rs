@@ -0,0 +1,580 @@
+mod common;
+
+use common::{hex, unhex};
+use flipper_zero_wallet::address::{
+    generate_address_for_network, parse_address, AddressKind, AddressType, Cryptocurrency,
+};
+use flipper_zero_wallet::base58;
+use flipper_zero_wallet::bech32::{self, Variant};
+use flipper_zero_wallet::flipper_wallet_core::Wallet;
+use flipper_zero_wallet::network::Network;
+use flipper_zero_wallet::secp256k1::PublicKey;
+use flipper_zero_wallet::wif;
+
+/// BIP84/BIP86/BIP49 参考向量使用的助记词
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+fn wallet() -> Wallet {
+    Wallet::from_mnemonic(MNEMONIC, "").unwrap()
+}
+
+fn receive_address(wallet: &Wallet, address_type: AddressType, index: u32) -> String {
+    let address = wallet
+        .get_address(Cryptocurrency::Bitcoin, address_type, 0, index)
+        .unwrap();
+    address.as_str().to_string()
+}
+
+fn change_address(wallet: &Wallet, address_type: AddressType, index: u32) -> String {
+    let address = wallet
+        .get_change_address(Cryptocurrency::Bitcoin, address_type, 0, index)
+        .unwrap();
+    address.as_str().to_string()
+}
+
+/// 见证版本与程序对应的输出脚本（OP_n <program>）
+fn witness_script(version: u8, program: &[u8]) -> String {
+    let op = if version == 0 { 0 } else { 0x50 + version };
+    format!("{:02x}{:02x}{}", op, program.len(), hex(program))
+}
+
+/// 按 BIP173/BIP350 测试向量的做法，依次尝试 bc 与 tb 两个 HRP
+fn decode_address(address: &str) -> Result<(u8, Vec<u8>), &'static str> {
+    bech32::decode_segwit_address("bc", address)
+        .or_else(|_| bech32::decode_segwit_address("tb", address))
+}
+
+/// BIP84 参考向量：m/84'/0'/0'
+#[test]
+fn bip84_addresses() {
+    let wallet = wallet();
+    assert_eq!(
+        receive_address(&wallet, AddressType::P2wpkh, 0),
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+    );
+    assert_eq!(
+        receive_address(&wallet, AddressType::P2wpkh, 1),
+        "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g"
+    );
+    assert_eq!(
+        change_address(&wallet, AddressType::P2wpkh, 0),
+        "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el"
+    );
+
+    // 直接由公钥生成；莱特币使用同一见证程序与 ltc 前缀
+    let public_key = PublicKey::from_slice(&unhex(
+        "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+    ))
+    .unwrap();
+    let address = generate_address_for_network(
+        &public_key,
+        Cryptocurrency::Bitcoin,
+        AddressType::P2wpkh,
+        Network::Mainnet,
+    )
+    .unwrap();
+    assert_eq!(address, b"bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+
+    let litecoin = generate_address_for_network(
+        &public_key,
+        Cryptocurrency::Litecoin,
+        AddressType::P2wpkh,
+        Network::Mainnet,
+    )
+    .unwrap();
+    let litecoin = std::str::from_utf8(&litecoin).unwrap();
+    assert!(litecoin.starts_with("ltc1q"));
+    let parsed = parse_address(litecoin).unwrap();
+    assert_eq!(parsed.cryptocurrency, Cryptocurrency::Litecoin);
+    assert_eq!(parsed.kind, AddressKind::P2wpkh);
+    assert_eq!(
+        hex(&parsed.payload),
+        "c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2"
+    );
+}
+
+/// BIP173 中有效的 bech32 字符串：校验和为 bech32，重新编码得到小写形式
+#[test]
+fn bip173_valid_strings() {
+    let longest = format!("11{}c8247j", "q".repeat(82));
+    let vectors = [
+        "A12UEL5L",
+        "a12uel5l",
+        "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+        "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+        &longest,
+        "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+        "?1ezyfcl",
+    ];
+    for s in vectors {
+        let (hrp, data, variant) = bech32::decode(s).unwrap_or_else(|e| panic!("{}: {}", s, e));
+        assert_eq!(variant, Variant::Bech32, "{}", s);
+        let encoded = bech32::encode(std::str::from_utf8(&hrp).unwrap(), &data, variant).unwrap();
+        assert_eq!(encoded, s.to_ascii_lowercase().as_bytes());
+    }
+}
+
+/// BIP173 中无效的 bech32 字符串
+#[test]
+fn bip173_invalid_strings() {
+    let vectors = [
+        // HRP 字符超出范围
+        "\u{20}1nwldj5",
+        "\u{7f}1axkwrx",
+        "\u{80}1eym55h",
+        // 超过 90 个字符
+        "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+        // 没有分隔符
+        "pzry9x0s0muk",
+        // HRP 为空
+        "1pzry9x0s0muk",
+        "10a06t8",
+        "1qzzfhee",
+        // 数据部分有非法字符
+        "x1b4n0q5v",
+        // 校验和太短
+        "li1dgmt3",
+        // 校验和中有非法字符
+        "de1lg7wt\u{ff}",
+        // 校验和按大写 HRP 计算
+        "A1G7SGD8",
+    ];
+    for s in vectors {
+        assert!(bech32::decode(s).is_err(), "{:?}", s);
+    }
+}
+
+/// BIP173 中有效的见证版本 0 地址及其输出脚本
+#[test]
+fn bip173_valid_addresses() {
+    let vectors = [
+        (
+            "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+        ),
+        (
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+        ),
+        (
+            "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+            "0020000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+        ),
+    ];
+    for (address, script) in vectors {
+        let (version, program) = decode_address(address).unwrap();
+        assert_eq!(witness_script(version, &program), script, "{}", address);
+        let hrp = address[..2].to_ascii_lowercase();
+        assert_eq!(
+            bech32::encode_segwit_address(&hrp, version, &program).unwrap(),
+            address.to_ascii_lowercase().as_bytes()
+        );
+    }
+}
+
+/// 无效的见证版本 0 地址
+#[test]
+fn bip173_invalid_addresses() {
+    let vectors = [
+        // 见证版本 0 的程序长度只能是 20 或 32 字节
+        "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+        // 大小写混合
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7",
+        // 8 位转 5 位的填充不为零
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3pjxtptv",
+        // 数据部分为空
+        "bc1gmk9yu",
+    ];
+    for address in vectors {
+        assert!(decode_address(address).is_err(), "{}", address);
+        assert!(parse_address(address).is_err(), "{}", address);
+    }
+}
+
+/// BIP86 参考向量：m/86'/0'/0'，输出密钥为内部公钥按 BIP341 调整（无脚本路径）的结果
+#[test]
+fn bip86_addresses() {
+    let wallet = wallet();
+    assert_eq!(
+        receive_address(&wallet, AddressType::P2tr, 0),
+        "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+    );
+    assert_eq!(
+        receive_address(&wallet, AddressType::P2tr, 1),
+        "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh"
+    );
+    assert_eq!(
+        change_address(&wallet, AddressType::P2tr, 0),
+        "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7"
+    );
+
+    let internal_key = PublicKey::from_slice(&unhex(
+        "03cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+    ))
+    .unwrap();
+    let (output_key, _) = internal_key.x_only_public_key().0.tap_tweak(None).unwrap();
+    assert_eq!(
+        hex(output_key.as_bytes()),
+        "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c"
+    );
+    let parsed =
+        parse_address("bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr").unwrap();
+    assert_eq!(parsed.kind, AddressKind::P2tr);
+    assert_eq!(parsed.payload, output_key.as_bytes());
+}
+
+/// BIP49 参考向量：主网 m/49'/0'/0'，以及规范中的测试网 m/49'/1'/0'/0/0
+#[test]
+fn bip49_addresses() {
+    let mut wallet = wallet();
+    assert_eq!(
+        receive_address(&wallet, AddressType::P2shP2wpkh, 0),
+        "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"
+    );
+
+    let public_key = PublicKey::from_slice(&unhex(
+        "039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c24",
+    ))
+    .unwrap();
+    let address = generate_address_for_network(
+        &public_key,
+        Cryptocurrency::Bitcoin,
+        AddressType::P2shP2wpkh,
+        Network::Mainnet,
+    )
+    .unwrap();
+    assert_eq!(address, b"37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf");
+    let parsed = parse_address("37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf").unwrap();
+    assert_eq!(parsed.network, Network::Mainnet);
+    assert_eq!(parsed.kind, AddressKind::P2sh);
+
+    wallet.set_network(Network::Testnet3).unwrap();
+    assert_eq!(
+        receive_address(&wallet, AddressType::P2shP2wpkh, 0),
+        "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2"
+    );
+
+    // 规范给出的私钥（WIF）与公钥
+    let key = wif::decode("cULrpoZGXiuC19Uhvykx7NugygA3k86b3hmdCeyvHYQZSxojGyXJ").unwrap();
+    assert!(key.compressed);
+    assert_eq!(key.network, Network::Testnet3);
+    let public_key = PublicKey::from_secret_key(&key.secret_key).unwrap();
+    assert_eq!(
+        hex(&public_key.serialize_compressed()),
+        "03a1af804ac108a8a51782198c2d034b28bf90c8803f5a53f76276fa69a4eae77f"
+    );
+    let address = generate_address_for_network(
+        &public_key,
+        Cryptocurrency::Bitcoin,
+        AddressType::P2shP2wpkh,
+        Network::Testnet3,
+    )
+    .unwrap();
+    assert_eq!(address, b"2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2");
+    let parsed = parse_address("2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2").unwrap();
+    assert_eq!(parsed.network, Network::Testnet3);
+    assert_eq!(parsed.kind, AddressKind::P2sh);
+}
+
+/// BIP350 中有效的 bech32m 字符串
+#[test]
+fn bip350_valid_strings() {
+    let longest = format!("11{}ludsr8", "l".repeat(82));
+    let vectors = [
+        "A1LQFN3A",
+        "a1lqfn3a",
+        "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6",
+        "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+        &longest,
+        "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+        "?1v759aa",
+    ];
+    for s in vectors {
+        let (hrp, data, variant) = bech32::decode(s).unwrap_or_else(|e| panic!("{}: {}", s, e));
+        assert_eq!(variant, Variant::Bech32m, "{}", s);
+        let encoded = bech32::encode(std::str::from_utf8(&hrp).unwrap(), &data, variant).unwrap();
+        assert_eq!(encoded, s.to_ascii_lowercase().as_bytes());
+    }
+}
+
+/// BIP350 中无效的 bech32m 字符串
+#[test]
+fn bip350_invalid_strings() {
+    let vectors = [
+        // HRP 字符超出范围
+        "\u{20}1xj0phk",
+        "\u{7f}1g6xzxy",
+        "\u{80}1vctc34",
+        // 超过 90 个字符
+        "an84characterslonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11d6pts4",
+        // 没有分隔符
+        "qyrz8wqd2c9m",
+        // HRP 为空
+        "1qyrz8wqd2c9m",
+        "16plkw9",
+        "1p2gdwpf",
+        // 数据部分有非法字符
+        "y1b0jsk6g",
+        "lt1igcx5c0",
+        // 校验和太短
+        "in1muywd",
+        // 校验和中有非法字符
+        "mm1crxm3i",
+        "au1s5cgom",
+        // 校验和按大写 HRP 计算
+        "M1VUXWEZ",
+    ];
+    for s in vectors {
+        assert!(bech32::decode(s).is_err(), "{:?}", s);
+    }
+}
+
+/// BIP350 中有效的地址：见证版本 1 到 16 使用 bech32m
+#[test]
+fn bip350_valid_addresses() {
+    let vectors = [
+        (
+            "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+        ),
+        (
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+        ),
+        (
+            "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+            "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+        ),
+        ("BC1SW50QGDZ25J", "6002751e"),
+        (
+            "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+            "5210751e76e8199196d454941c45d1b3a323",
+        ),
+        (
+            "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+            "0020000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+        ),
+        (
+            "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+            "5120000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+        ),
+        (
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ),
+    ];
+    for (address, script) in vectors {
+        let (version, program) = decode_address(address).unwrap();
+        assert_eq!(witness_script(version, &program), script, "{}", address);
+        let hrp = address[..2].to_ascii_lowercase();
+        assert_eq!(
+            bech32::encode_segwit_address(&hrp, version, &program).unwrap(),
+            address.to_ascii_lowercase().as_bytes()
+        );
+
+        let parsed = parse_address(address).unwrap();
+        let expected_kind = match (version, program.len()) {
+            (0, 20) => AddressKind::P2wpkh,
+            (0, 32) => AddressKind::P2wsh,
+            (1, 32) => AddressKind::P2tr,
+            (version, _) => AddressKind::WitnessUnknown(version),
+        };
+        assert_eq!(parsed.kind, expected_kind, "{}", address);
+        assert_eq!(parsed.payload, program);
+    }
+}
+
+/// BIP350 中无效的地址
+#[test]
+fn bip350_invalid_addresses() {
+    let vectors = [
+        // HRP 无效
+        "tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut",
+        // 见证版本 1 以上使用了 bech32 校验和
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+        "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf",
+        "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL",
+        // 见证版本 0 使用了 bech32m 校验和
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+        "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47",
+        // 校验和中有非法字符
+        "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+        // 见证版本无效
+        "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R",
+        // 程序长度为 1 字节与 41 字节
+        "bc1pw5dgrnzv",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav",
+        // 见证版本 0 的程序长度只能是 20 或 32 字节
+        "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+        // 大小写混合
+        "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq",
+        // 超过 4 位的零填充
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf",
+        // 8 位转 5 位的填充不为零
+        "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j",
+        // 数据部分为空
+        "bc1gmk9yu",
+    ];
+    for address in vectors {
+        assert!(decode_address(address).is_err(), "{}", address);
+        assert!(parse_address(address).is_err(), "{}", address);
+    }
+}
+
+/// 已知地址的解析结果：创世区块地址、BIP13 的 P2SH 示例与 Ripple 创世账户
+#[test]
+fn parse_known_addresses() {
+    let vectors = [
+        (
+            "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+            Cryptocurrency::Bitcoin,
+            Network::Mainnet,
+            AddressKind::P2pkh,
+            "62e907b15cbf27d5425399ebf6f0fb50ebb88f18",
+        ),
+        (
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+            Cryptocurrency::Bitcoin,
+            Network::Mainnet,
+            AddressKind::P2sh,
+            "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb",
+        ),
+        (
+            "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn",
+            Cryptocurrency::Bitcoin,
+            Network::Testnet3,
+            AddressKind::P2pkh,
+            "243f1394f44554f4ce3fd68649c19adc483ce924",
+        ),
+        (
+            "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh",
+            Cryptocurrency::Ripple,
+            Network::Mainnet,
+            AddressKind::P2pkh,
+            "b5f762798a53d543a014caf8b297cff8f2f937e8",
+        ),
+        (
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            Cryptocurrency::Ethereum,
+            Network::Mainnet,
+            AddressKind::Ethereum,
+            "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+        ),
+    ];
+    for (address, cryptocurrency, network, kind, payload) in vectors {
+        let parsed = parse_address(address).unwrap();
+        assert_eq!(parsed.cryptocurrency, cryptocurrency, "{}", address);
+        assert_eq!(parsed.network, network, "{}", address);
+        assert_eq!(parsed.kind, kind, "{}", address);
+        assert_eq!(hex(&parsed.payload), payload, "{}", address);
+    }
+
+    // 首尾空白被忽略
+    assert!(parse_address(" 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa\n").is_ok());
+}
+
+/// 每个币种、网络与地址类型生成的地址都能解析回同样的种类与载荷
+#[test]
+fn parse_generated_addresses() {
+    let public_key = PublicKey::from_slice(&unhex(
+        "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+    ))
+    .unwrap();
+    let hash160 = unhex("c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2");
+    let (output_key, _) = public_key.x_only_public_key().0.tap_tweak(None).unwrap();
+
+    let address_types = [
+        AddressType::P2pkh,
+        AddressType::P2shP2wpkh,
+        AddressType::P2wpkh,
+        AddressType::P2tr,
+    ];
+    for cryptocurrency in Cryptocurrency::ALL {
+        for network in [Network::Mainnet, Network::Testnet3] {
+            if !cryptocurrency.supports_network(network) {
+                continue;
+            }
+            for address_type in address_types {
+                if !cryptocurrency.supports_address_type(address_type) {
+                    continue;
+                }
+                let address = generate_address_for_network(
+                    &public_key,
+                    cryptocurrency,
+                    address_type,
+                    network,
+                )
+                .unwrap();
+                let address = std::str::from_utf8(&address).unwrap();
+                let parsed = parse_address(address).unwrap();
+
+                // 测试网的 P2PKH 版本字节在币种间重复，只有主网能确定币种
+                if network == Network::Mainnet || address_type != AddressType::P2pkh {
+                    assert_eq!(parsed.cryptocurrency, cryptocurrency, "{}", address);
+                }
+                assert_eq!(parsed.network, network, "{}", address);
+                match address_type {
+                    AddressType::P2pkh if cryptocurrency == Cryptocurrency::Ethereum => {
+                        assert_eq!(parsed.kind, AddressKind::Ethereum);
+                        assert_eq!(parsed.payload.len(), 20);
+                    }
+                    AddressType::P2pkh => {
+                        assert_eq!(parsed.kind, AddressKind::P2pkh, "{}", address);
+                        assert_eq!(parsed.payload, hash160, "{}", address);
+                    }
+                    AddressType::P2shP2wpkh => {
+                        assert_eq!(parsed.kind, AddressKind::P2sh, "{}", address);
+                        assert_eq!(parsed.payload.len(), 20);
+                    }
+                    AddressType::P2wpkh => {
+                        assert_eq!(parsed.kind, AddressKind::P2wpkh, "{}", address);
+                        assert_eq!(parsed.payload, hash160, "{}", address);
+                    }
+                    AddressType::P2tr => {
+                        assert_eq!(parsed.kind, AddressKind::P2tr, "{}", address);
+                        assert_eq!(parsed.payload, output_key.as_bytes(), "{}", address);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn parse_rejects_invalid_addresses() {
+    let genesis = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+
+    // 任意一个字符改变后校验和失败
+    for (i, c) in genesis.char_indices() {
+        let replacement = if c == '2' { "3" } else { "2" };
+        let mut corrupted = genesis.to_string();
+        corrupted.replace_range(i..i + 1, replacement);
+        assert!(parse_address(&corrupted).is_err(), "{}", corrupted);
+    }
+    let ripple = "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh";
+    assert!(parse_address(&ripple.replace('T', "U")).is_err());
+
+    // 校验和正确，但版本字节不属于任何币种与网络
+    let mut payload = unhex("0062e907b15cbf27d5425399ebf6f0fb50ebb88f18");
+    payload[0] = 0x01;
+    let unknown = base58::check_encode(&payload, &base58::BITCOIN);
+    assert_eq!(
+        parse_address(std::str::from_utf8(&unknown).unwrap()),
+        Err("Unknown address version")
+    );
+
+    // 载荷长度不是 21 字节
+    let short = base58::check_encode(&payload[..20], &base58::BITCOIN);
+    assert_eq!(
+        parse_address(std::str::from_utf8(&short).unwrap()),
+        Err("Invalid address payload length")
+    );
+
+    // 未知 HRP 的 bech32 字符串
+    let unknown_hrp = bech32::encode_segwit_address("xy", 0, &[0u8; 20]).unwrap();
+    assert!(parse_address(std::str::from_utf8(&unknown_hrp).unwrap()).is_err());
+
+    assert_eq!(parse_address("  "), Err("Empty address"));
+    assert!(parse_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").is_err());
+}
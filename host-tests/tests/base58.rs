@@ -0,0 +1,161 @@
+mod common;
+
+use common::{hex, unhex};
+use flipper_zero_wallet::base58::{
+    check_decode, check_decode_into, check_encode, check_encode_into, decode, decode_into, encode,
+    encode_into, BITCOIN, RIPPLE,
+};
+
+/// Bitcoin Core 的 base58_encode_decode.json
+const VECTORS: [(&str, &str); 13] = [
+    ("", ""),
+    ("61", "2g"),
+    ("626262", "a3gV"),
+    ("636363", "aPEr"),
+    (
+        "73696d706c792061206c6f6e6720737472696e67",
+        "2cFupjhnEsSn59qHXstmK2ffpLv2",
+    ),
+    (
+        "00eb15231dfceb60925886b67d065299925915aeb172c06647",
+        "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+    ),
+    ("516b6fcd0f", "ABnLTmg"),
+    ("bf4f89001e670274dd", "3SEo3LWLoPntC"),
+    ("572e4794", "3EFU7m"),
+    ("ecac89cad93923c02321", "EJDM8drfXA6uyA"),
+    ("10c8511e", "Rt5zm"),
+    ("00000000000000000000", "1111111111"),
+    (
+        "000111d38e5fc9071ffcd20b4a763cc9ae4f252bb4e48fd66a835e252ada93ff480d6dd43dc62a641155a5",
+        "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+    ),
+];
+
+#[test]
+fn bitcoin_core_vectors() {
+    for (data, encoded) in VECTORS {
+        let data = unhex(data);
+        assert_eq!(encode(&data, &BITCOIN), encoded.as_bytes());
+        assert_eq!(decode(encoded.as_bytes(), &BITCOIN).unwrap(), data);
+
+        // 固定缓冲区版本与分配版本结果一致
+        let mut out = [0u8; 128];
+        let len = encode_into(&data, &BITCOIN, &mut out).unwrap();
+        assert_eq!(&out[..len], encoded.as_bytes());
+        let len = decode_into(encoded.as_bytes(), &BITCOIN, &mut out).unwrap();
+        assert_eq!(&out[..len], &data[..]);
+    }
+}
+
+/// 每个前导零字节对应一个前导的零字符（比特币为 '1'，Ripple 为 'r'）
+#[test]
+fn leading_zeros() {
+    for zeros in 0..5 {
+        let mut data = vec![0u8; zeros];
+        data.extend_from_slice(&[0x00, 0x01, 0xff, 0x80]);
+        let encoded = encode(&data, &BITCOIN);
+        let expected_ones = zeros + 1;
+        assert_eq!(
+            encoded.iter().take_while(|&&c| c == b'1').count(),
+            expected_ones
+        );
+        assert_eq!(decode(&encoded, &BITCOIN).unwrap(), data);
+
+        let encoded = encode(&data, &RIPPLE);
+        assert_eq!(
+            encoded.iter().take_while(|&&c| c == b'r').count(),
+            expected_ones
+        );
+        assert_eq!(decode(&encoded, &RIPPLE).unwrap(), data);
+    }
+    assert_eq!(encode(&[0], &BITCOIN), b"1");
+    assert_eq!(decode(b"1", &BITCOIN).unwrap(), [0]);
+}
+
+#[test]
+fn rejects_invalid_characters() {
+    for input in ["0", "O", "I", "l", "1A 1", "é", "3SEo3LWLoPntC\n"] {
+        assert_eq!(
+            decode(input.as_bytes(), &BITCOIN),
+            Err("Invalid base58 character"),
+            "{:?}",
+            input
+        );
+    }
+    // Ripple 字母表同样不含 '0'、'O'、'I' 与 'l'
+    assert_eq!(decode(b"l", &RIPPLE), Err("Invalid base58 character"));
+}
+
+#[test]
+fn output_buffer_too_small() {
+    let data = unhex("00eb15231dfceb60925886b67d065299925915aeb172c06647");
+    let encoded = b"1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L";
+    let mut out = [0u8; 33];
+    assert_eq!(
+        encode_into(&data, &BITCOIN, &mut out),
+        Err("Base58 output buffer too small")
+    );
+    let mut out = [0u8; 24];
+    assert_eq!(
+        decode_into(encoded, &BITCOIN, &mut out),
+        Err("Base58 output buffer too small")
+    );
+    // 只有前导零时同样检查长度
+    assert_eq!(
+        decode_into(b"1111", &BITCOIN, &mut [0u8; 3]),
+        Err("Base58 output buffer too small")
+    );
+}
+
+#[test]
+fn base58check() {
+    // 创世区块地址：版本 0x00 与 20 字节公钥哈希
+    let payload = unhex("0062e907b15cbf27d5425399ebf6f0fb50ebb88f18");
+    let address = b"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+    assert_eq!(check_encode(&payload, &BITCOIN), address);
+    assert_eq!(check_decode(address, &BITCOIN).unwrap(), payload);
+
+    let mut out = [0u8; 64];
+    let len = check_encode_into(&payload, &BITCOIN, &mut out).unwrap();
+    assert_eq!(&out[..len], address);
+    let len = check_decode_into(address, &BITCOIN, &mut out).unwrap();
+    assert_eq!(&out[..len], &payload[..]);
+
+    // Ripple 创世账户
+    let account = b"rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh";
+    let payload = check_decode(account, &RIPPLE).unwrap();
+    assert_eq!(hex(&payload), "00b5f762798a53d543a014caf8b297cff8f2f937e8");
+    assert_eq!(check_encode(&payload, &RIPPLE), account);
+    // 同一字符串在比特币字母表下校验失败
+    assert!(check_decode(account, &BITCOIN).is_err());
+}
+
+#[test]
+fn base58check_rejects_corruption() {
+    let address = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+    for (i, c) in address.char_indices().skip(1) {
+        let replacement = if c == '2' { "3" } else { "2" };
+        let mut corrupted = address.to_string();
+        corrupted.replace_range(i..i + 1, replacement);
+        assert_eq!(
+            check_decode(corrupted.as_bytes(), &BITCOIN),
+            Err("Invalid Base58Check checksum"),
+            "{}",
+            corrupted
+        );
+    }
+    // 多一个前导零字节也会改变校验和
+    assert_eq!(
+        check_decode(format!("1{}", address).as_bytes(), &BITCOIN),
+        Err("Invalid Base58Check checksum")
+    );
+    assert_eq!(
+        check_decode(b"111", &BITCOIN),
+        Err("Base58Check data too short")
+    );
+    assert_eq!(
+        check_decode(b"", &BITCOIN),
+        Err("Base58Check data too short")
+    );
+}
@@ -0,0 +1,220 @@
+mod common;
+
+use common::unhex;
+use flipper_zero_wallet::bip32::{DerivationPath, ExtendedPrivKey, ExtendedPubKey};
+
+/// 从种子出发逐条检查路径、xpub 与 xprv，并验证编码能原样解析回来
+fn check_vector(seed: &str, chain: &[(&str, &str, &str)]) {
+    let master = ExtendedPrivKey::from_seed(&unhex(seed)).unwrap();
+    for &(path, xpub, xprv) in chain {
+        let key = master
+            .derive_path(&DerivationPath::parse(path).unwrap())
+            .unwrap();
+        assert_eq!(key.to_base58().as_str(), xprv, "{}", path);
+        assert_eq!(
+            key.to_extended_pub_key().unwrap().to_base58().as_str(),
+            xpub,
+            "{}",
+            path
+        );
+        assert_eq!(
+            ExtendedPrivKey::from_base58(xprv).unwrap().encode(),
+            key.encode(),
+            "{}",
+            path
+        );
+        assert_eq!(
+            ExtendedPubKey::from_base58(xpub).unwrap().encode(),
+            key.to_extended_pub_key().unwrap().encode(),
+            "{}",
+            path
+        );
+    }
+}
+
+#[test]
+fn test_vector_1() {
+    check_vector(
+        "000102030405060708090a0b0c0d0e0f",
+        &[
+            (
+                "m",
+                "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            ),
+            (
+                "m/0'",
+                "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+                "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+            ),
+            (
+                "m/0'/1",
+                "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+                "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+            ),
+            (
+                "m/0'/1/2'",
+                "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+                "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+            ),
+            (
+                "m/0'/1/2'/2",
+                "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+                "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+            ),
+            (
+                "m/0'/1/2'/2/1000000000",
+                "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+                "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn test_vector_2() {
+    check_vector(
+        "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        &[
+            (
+                "m",
+                "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB",
+                "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+            ),
+            (
+                "m/0",
+                "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH",
+                "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+            ),
+            (
+                "m/0/2147483647'",
+                "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a",
+                "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+            ),
+            (
+                "m/0/2147483647'/1",
+                "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon",
+                "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+            ),
+            (
+                "m/0/2147483647'/1/2147483646'",
+                "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL",
+                "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+            ),
+            (
+                "m/0/2147483647'/1/2147483646'/2",
+                "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt",
+                "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn test_vector_3() {
+    check_vector(
+        "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be",
+        &[
+            (
+                "m",
+                "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13",
+                "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+            ),
+            (
+                "m/0'",
+                "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y",
+                "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn test_vector_4() {
+    check_vector(
+        "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678",
+        &[
+            (
+                "m",
+                "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa",
+                "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+            ),
+            (
+                "m/0'",
+                "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m",
+                "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G",
+            ),
+            (
+                "m/0'/1'",
+                "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt",
+                "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1",
+            ),
+        ],
+    );
+}
+
+/// 测试向量 5：各类无效扩展密钥，按 BIP32 列出的情形由测试向量 1 的主密钥构造
+#[test]
+fn test_vector_5() {
+    let invalid = [
+        // pubkey version / prvkey mismatch
+        "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gYweD1YUMnzkxQw1bm6XhhCCXF5rvDu3SQRW2A1Z5yqnVwyY4cNT",
+        // prvkey version / pubkey mismatch
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChpzxM5bEu4ku6ynu4tP6GqJ5kziULDsCA7bVctSatEcmUDntDMZ",
+        // invalid pubkey prefix 04
+        "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ45ycVBsADt89FVXeDkYqbSeZmpjjnJETkyyiMwXokWPisrtUjm",
+        // invalid prvkey prefix 04
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChtGudJ7tny1s3mDVifGEu33q1sqF4rpn2yU5HHVd2bvpANAPAP7",
+        // invalid pubkey prefix 01
+        "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gYxFk5nqmbwrSjnkQvUtYydeKpRyanfmc6qmeyusqpnVEF2j8DGn",
+        // invalid prvkey prefix 01
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChnSg6bmoEgzBeJUNzvQF35FWGXz67kJ9g4FkYqRw3duegVvnguE",
+        // zero depth with non-zero parent fingerprint
+        "xprv9s2SVEMYPrA5zFr9cMZoqCQE6996p9PcDSAJdygf2wXW35yPEq4R8WjZcNDGuQFXjzJuMEWuHjMBXPKa4QGPyjiiAZJYQvsRPTuqBWKvEZh",
+        // zero depth with non-zero index
+        "xprv9s21ZrQH143K5xHBs26cwZK5DysagCJvyKkvGxYZfF4mZAqjPTNZDYRPyzMWuZqh2Ah4465C1KR38McHpLVffLbyzqfTkrY5tYLVhTL5ye4",
+        // zero depth with non-zero parent fingerprint
+        "xpub661ntjtSEDiPCjvciP6pCLLxeAybDc7Taf5uSN6GbH4UutJXnNNfgK43TdraRHfbfXCqrBY3w2hVKuWiMe73bminxG2maTP29aWaDpxYPw7",
+        // zero depth with non-zero index
+        "xpub661MyMwAqRbcJSMey3ddJhFon1i55f2nLYgX5LxBDabkRyAsvzgomLjsqFzpRTFkwhazZ36LecmLvsoS7aLKHNc4nYPgvP3geewEjpUTwEd",
+        // unknown extended key version
+        "pGoh3VSiBwoWmRoSExKdpxHJBCMF5iacGac3mc7Q7j3RD8AADSrpaVmfhA5z6V4aagkXui2W9FapryNxzQW8RvHDfJHBZWQMQj9JwRbJoC6zJxNu",
+        // unknown extended key version
+        "pGoh3VSiBwoWmRoSExKdpxHJBCMF5iacGac3mc7Q7j3RD8AADSrpaVmfhA5z6Uz5ZG3GSCE4Cf5vdzqN9DRV5WhsZS6meEhwZQwcPbLbHumsKTty",
+        // private key 0 not in 1..n-1
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChijLXZSun8bsGj49MuvWWsqL9fqS5fhiDUkRQvq8cj8L42RGwHP",
+        // private key n not in 1..n-1
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkg5hntwdZH6QYdrGVYWUCS2Xv6FCMHoYQZYQDohv67LnGTwiNd",
+        // invalid pubkey 020000000000000000000000000000000000000000000000000000000000000007
+        "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gYym6yCVZtiQKSpLUqpuy2xafsZZR8vydJmD1kZ1yXu2Lp8uNH4N",
+        // invalid checksum
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHj",
+    ];
+    for key in invalid {
+        assert!(ExtendedPrivKey::from_base58(key).is_err(), "{}", key);
+        assert!(ExtendedPubKey::from_base58(key).is_err(), "{}", key);
+    }
+}
+
+/// 公钥派生（CKDpub）与私钥派生得到相同的非硬化子公钥
+#[test]
+fn public_derivation_matches_private() {
+    let master = ExtendedPrivKey::from_seed(&unhex("000102030405060708090a0b0c0d0e0f")).unwrap();
+    let parent = master
+        .derive_path(&DerivationPath::parse("m/0'/1/2'").unwrap())
+        .unwrap();
+    let child = parent
+        .to_extended_pub_key()
+        .unwrap()
+        .derive_child(2)
+        .unwrap();
+    assert_eq!(
+        child.to_base58().as_str(),
+        "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV"
+    );
+    assert!(parent
+        .to_extended_pub_key()
+        .unwrap()
+        .derive_child(0x8000_0000)
+        .is_err());
+}
@@ -0,0 +1,165 @@
+use flipper_zero_wallet::coin_selection::{select, Candidate, Selection, Strategy, Target};
+
+/// 费率 1 聪/vB：每个候选的输入权重为 4（1 vB），有效金额为面值减 1
+fn candidates(effective: &[u64]) -> Vec<Candidate> {
+    effective
+        .iter()
+        .map(|&value| Candidate {
+            value: value + 1,
+            weight: 4,
+        })
+        .collect()
+}
+
+/// 固定部分 10 vB，找零输出 31 vB，以后花费找零 68 vB，找零粉尘门限 294 聪
+fn payment(amount: u64) -> Target {
+    Target {
+        amount,
+        fee_rate: 1,
+        base_weight: 40,
+        change_weight: 124,
+        change_spend_weight: 272,
+        change_dust: 294,
+    }
+}
+
+/// 选中的输入恰好支付金额与手续费
+fn assert_balanced(candidates: &[Candidate], target: &Target, selection: &Selection) {
+    let total: u64 = selection.indices.iter().map(|&i| candidates[i].value).sum();
+    assert_eq!(
+        total,
+        target.amount + selection.fee + selection.change.unwrap_or(0)
+    );
+}
+
+#[test]
+fn branch_and_bound_exact_match() {
+    let pool = candidates(&[100_000, 60_000, 35_000, 20_000, 7_000]);
+    // 需要 54_990 + 10 = 55_000，只有 35_000 + 20_000 落在 [55_000, 55_099] 内
+    let target = payment(54_990);
+    let selection = select(&pool, &target, Strategy::BranchAndBound).unwrap();
+    assert_eq!(
+        selection,
+        Selection {
+            indices: vec![2, 3],
+            fee: 12,
+            change: None,
+        }
+    );
+    assert_balanced(&pool, &target, &selection);
+
+    // 不超过找零成本的零头计入手续费
+    let target = payment(54_950);
+    let selection = select(&pool, &target, Strategy::BranchAndBound).unwrap();
+    assert_eq!(selection.indices, vec![2, 3]);
+    assert_eq!(selection.change, None);
+    assert_eq!(selection.fee, 52);
+    assert_balanced(&pool, &target, &selection);
+
+    // 同样的输入总是得到同样的结果
+    assert_eq!(
+        select(&pool, &target, Strategy::BranchAndBound).unwrap(),
+        selection
+    );
+}
+
+#[test]
+fn branch_and_bound_falls_back_to_largest_first() {
+    let pool = candidates(&[100_000, 60_000, 35_000, 20_000, 7_000]);
+    // 12_345 附近没有不需要找零的组合
+    let target = payment(12_335);
+    let bnb = select(&pool, &target, Strategy::BranchAndBound).unwrap();
+    let largest_first = select(&pool, &target, Strategy::LargestFirst).unwrap();
+    assert_eq!(bnb, largest_first);
+    assert_eq!(bnb.indices, vec![0]);
+    // 100_001 - 12_335 - 11 - 31
+    assert_eq!(bnb.change, Some(87_624));
+    assert_eq!(bnb.fee, 42);
+    assert_balanced(&pool, &target, &bnb);
+}
+
+#[test]
+fn largest_first_order() {
+    let pool = candidates(&[20_000, 60_000, 35_000, 60_000]);
+    // 金额相同时按原顺序
+    for (amount, indices) in [
+        (50_000, vec![1]),
+        (100_000, vec![1, 3]),
+        (130_000, vec![1, 2, 3]),
+    ] {
+        let target = payment(amount);
+        let selection = select(&pool, &target, Strategy::LargestFirst).unwrap();
+        assert_eq!(selection.indices, indices);
+        assert_balanced(&pool, &target, &selection);
+    }
+}
+
+#[test]
+fn dust_change_goes_to_fee() {
+    let pool = candidates(&[60_000]);
+
+    // 多出 200 聪：扣掉找零输出的 31 聪后低于粉尘门限，不创建找零
+    let target_no_change = payment(60_001 - 11 - 200);
+    let selection = select(&pool, &target_no_change, Strategy::LargestFirst).unwrap();
+    assert_eq!(selection.change, None);
+    assert_eq!(selection.fee, 211);
+    assert_balanced(&pool, &target_no_change, &selection);
+
+    // 恰好达到粉尘门限时创建找零
+    let target_change = payment(60_001 - 11 - 31 - 294);
+    let selection = select(&pool, &target_change, Strategy::LargestFirst).unwrap();
+    assert_eq!(selection.change, Some(294));
+    assert_eq!(selection.fee, 42);
+    assert_balanced(&pool, &target_change, &selection);
+
+    let target_below = payment(60_001 - 11 - 31 - 293);
+    let selection = select(&pool, &target_below, Strategy::LargestFirst).unwrap();
+    assert_eq!(selection.change, None);
+    assert_eq!(selection.fee, 11 + 31 + 293);
+}
+
+#[test]
+fn uneconomical_candidates_are_ignored() {
+    // 面值不超过花费它的手续费的候选不参与选择
+    let mut pool = candidates(&[50_000]);
+    pool.push(Candidate {
+        value: 1,
+        weight: 4,
+    });
+    let target = payment(49_000);
+    let selection = select(&pool, &target, Strategy::LargestFirst).unwrap();
+    assert_eq!(selection.indices, vec![0]);
+
+    let dust_only = [Candidate {
+        value: 1,
+        weight: 4,
+    }; 3];
+    assert_eq!(
+        select(&dust_only, &target, Strategy::BranchAndBound).unwrap_err(),
+        "Insufficient funds"
+    );
+}
+
+#[test]
+fn insufficient_funds_and_fee_rate() {
+    let pool = candidates(&[30_000, 20_000]);
+    // 有效金额 50_000 只够支付 49_990 + 10
+    assert!(select(&pool, &payment(49_990), Strategy::LargestFirst).is_ok());
+    assert_eq!(
+        select(&pool, &payment(49_991), Strategy::LargestFirst).unwrap_err(),
+        "Insufficient funds"
+    );
+    assert_eq!(
+        select(&pool, &payment(u64::MAX), Strategy::BranchAndBound).unwrap_err(),
+        "Amount too large"
+    );
+
+    let zero_rate = Target {
+        fee_rate: 0,
+        ..payment(1_000)
+    };
+    assert_eq!(
+        select(&pool, &zero_rate, Strategy::LargestFirst).unwrap_err(),
+        "Fee rate must be at least 1 sat/vB"
+    );
+}
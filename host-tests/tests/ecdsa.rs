@@ -0,0 +1,263 @@
+mod common;
+
+use common::{hex, unhex};
+use flipper_zero_wallet::address::generate_ethereum_address;
+use flipper_zero_wallet::secp256k1::{PublicKey, RecoverableSignature, SecretKey, Signature};
+use flipper_zero_wallet::sha256::Sha256;
+use flipper_zero_wallet::wif;
+
+fn secret_key(hex: &str) -> SecretKey {
+    SecretKey::from_bytes(&unhex(hex).try_into().unwrap()).unwrap()
+}
+
+/// 签名确定且为 low-S，DER 编码逐字节一致，并能由公钥验证
+fn check_signature(secret: &str, message: &str, der: &str) {
+    let key = secret_key(secret);
+    let hash = Sha256::digest(message.as_bytes());
+    let signature = key.sign_ecdsa(&hash);
+
+    assert_eq!(hex(&signature.serialize_der()), der, "{}", message);
+    assert!(signature.is_low_s());
+    assert!(Signature::from_der(&unhex(der)).unwrap() == signature);
+    let public_key = PublicKey::from_secret_key(&key).unwrap();
+    assert!(public_key.verify_ecdsa(&hash, &signature));
+}
+
+/// RFC 6979 确定性 nonce 在 secp256k1 上的常用测试向量（消息取 SHA-256）
+#[test]
+fn rfc6979_vectors() {
+    check_signature(
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "Satoshi Nakamoto",
+        "3045022100934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d802202442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+    );
+    check_signature(
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "All those moments will be lost in time, like tears in rain. Time to die...",
+        "30450221008600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b0220547fe64427496db33bf66019dacbf0039c04199abb0122918601db38a72cfc21",
+    );
+    check_signature(
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        "Satoshi Nakamoto",
+        "3045022100fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d002206b39cd0eb1bc8603e159ef5c20a5c8ad685a45b06ce9bebed3f153d10d93bed5",
+    );
+    check_signature(
+        "f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+        "Alan Turing",
+        "304402207063ae83e7f62bbb171798131b4a0564b956930092b33b07b395615d9ec7e15c022058dfcc1e00a35e1572f366ffe34ba0fc47db1e7189759b9fb233c5b05ab388ea",
+    );
+    check_signature(
+        "e91671c46231f833a6406ccbea0e3e392c76c167bac1cb013f6f1013980455c2",
+        "There is a computer disease that anybody who works with computers knows about. It's a very serious disease and it interferes completely with the work. The trouble with computers is that you 'play' with them!",
+        "3045022100b552edd27580141f3b2a5463048cb7cd3e047b97c9f98076c32dbdf85a68718b0220279fa72dd19bfae05577e06c7c0c1900c371fcd5893f7e1d56a37d30174671f6",
+    );
+}
+
+/// Bitcoin Core key_tests 中的确定性紧凑签名（头字节 27 + 恢复 id，压缩公钥再加 4）
+#[test]
+fn bitcoin_core_deterministic_signatures() {
+    let hash = Sha256::digest(&Sha256::digest(b"Very deterministic message"));
+    let cases = [
+        (
+            "5HxWvvfubhXpYYpS3tJkw6fq9jE9j18THftkZjHHfmFiWtmAbrj",
+            "1c5dbbddda71772d95ce91cd2d14b592cfbc1dd0aabd6a394b6c2d377bbe59d31d14ddda21494a4e221f0824f0b8b924c43fa43c0ad57dccdaa11f81a6bd4582f6",
+        ),
+        (
+            "Kwr371tjA9u2rFSMZjTNun2PXXP3WPZu2afRHTcta6KxEUdm1vEw",
+            "205dbbddda71772d95ce91cd2d14b592cfbc1dd0aabd6a394b6c2d377bbe59d31d14ddda21494a4e221f0824f0b8b924c43fa43c0ad57dccdaa11f81a6bd4582f6",
+        ),
+    ];
+    for (secret, expected) in cases {
+        let key = wif::decode(secret).unwrap();
+        let signature = key.secret_key.sign_ecdsa_recoverable(&hash);
+        let (compact, recovery_id) = signature.serialize_compact();
+        let header = 27 + recovery_id + if key.compressed { 4 } else { 0 };
+
+        let mut encoded = vec![header];
+        encoded.extend_from_slice(&compact);
+        assert_eq!(hex(&encoded), expected, "{}", secret);
+
+        let recovered = PublicKey::recover(&hash, &signature).unwrap();
+        let public_key = PublicKey::from_secret_key(&key.secret_key).unwrap();
+        assert_eq!(
+            recovered.serialize_compressed(),
+            public_key.serialize_compressed()
+        );
+    }
+}
+
+/// 恢复 id 与签名一起往返，错误的恢复 id 得到其他公钥
+#[test]
+fn recoverable_round_trip() {
+    let key = secret_key("0000000000000000000000000000000000000000000000000000000000000001");
+    let hash = Sha256::digest(b"Satoshi Nakamoto");
+    let signature = key.sign_ecdsa_recoverable(&hash);
+    let (compact, recovery_id) = signature.serialize_compact();
+
+    let parsed = RecoverableSignature::from_compact(&compact, recovery_id).unwrap();
+    assert!(parsed == signature);
+    let public_key = PublicKey::from_secret_key(&key).unwrap();
+    assert_eq!(
+        PublicKey::recover(&hash, &parsed)
+            .unwrap()
+            .serialize_compressed(),
+        public_key.serialize_compressed()
+    );
+
+    let wrong = RecoverableSignature::from_compact(&compact, recovery_id ^ 1).unwrap();
+    let other = PublicKey::recover(&hash, &wrong).unwrap();
+    assert_ne!(
+        other.serialize_compressed(),
+        public_key.serialize_compressed()
+    );
+}
+
+/// 多个私钥与消息的 ecrecover 往返：只有签名时的恢复 id 得到签名者公钥
+#[test]
+fn ecrecover_round_trips() {
+    let keys = [
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "c90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22",
+        "4646464646464646464646464646464646464646464646464646464646464646",
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+    ];
+    for key_hex in keys {
+        let key = secret_key(key_hex);
+        let public_key = PublicKey::from_secret_key(&key).unwrap();
+        for message in [&b""[..], b"abc", b"Satoshi Nakamoto", &[0xff; 100]] {
+            let hash = Sha256::digest(message);
+            let signature = key.sign_ecdsa_recoverable(&hash);
+            assert!(signature.signature().is_low_s());
+            assert!(public_key.verify_ecdsa(&hash, &signature.signature()));
+
+            let (compact, recovery_id) = signature.serialize_compact();
+            for id in 0..4 {
+                let candidate = RecoverableSignature::from_compact(&compact, id).unwrap();
+                let recovered = PublicKey::recover(&hash, &candidate);
+                let matches = recovered.is_ok_and(|recovered| {
+                    recovered.serialize_uncompressed() == public_key.serialize_uncompressed()
+                });
+                assert_eq!(matches, id == recovery_id, "{} {}", key_hex, id);
+            }
+
+            // 以太坊 v 值往返
+            let v = signature.ethereum_v(Some(1)).unwrap();
+            let parsed = RecoverableSignature::from_ethereum(&compact, v).unwrap();
+            assert_eq!(
+                PublicKey::recover(&hash, &parsed)
+                    .unwrap()
+                    .serialize_compressed(),
+                public_key.serialize_compressed()
+            );
+        }
+    }
+}
+
+/// EIP-155 规范中的示例交易签名（私钥 0x4646...46，chain_id 1，v = 37）
+#[test]
+fn eip155_example() {
+    let hash: [u8; 32] = unhex("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53")
+        .try_into()
+        .unwrap();
+    let compact: [u8; 64] = unhex(concat!(
+        "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+        "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+    ))
+    .try_into()
+    .unwrap();
+
+    let signature = RecoverableSignature::from_ethereum(&compact, 37).unwrap();
+    assert_eq!(signature.recovery_id(), 0);
+    assert_eq!(signature.ethereum_v(Some(1)), Ok(37));
+    let public_key = PublicKey::recover(&hash, &signature).unwrap();
+    assert_eq!(
+        generate_ethereum_address(&public_key).unwrap(),
+        b"0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+    );
+    let key = secret_key("4646464646464646464646464646464646464646464646464646464646464646");
+    assert_eq!(
+        public_key.serialize_uncompressed(),
+        PublicKey::from_secret_key(&key)
+            .unwrap()
+            .serialize_uncompressed()
+    );
+
+    // 另一个奇偶性恢复出其他公钥
+    let other = RecoverableSignature::from_ethereum(&compact, 38).unwrap();
+    let other = PublicKey::recover(&hash, &other).unwrap();
+    assert_ne!(
+        other.serialize_uncompressed(),
+        public_key.serialize_uncompressed()
+    );
+
+    for v in [2, 26, 29, 34] {
+        assert_eq!(
+            RecoverableSignature::from_ethereum(&compact, v).err(),
+            Some("Invalid Ethereum v value"),
+            "{}",
+            v
+        );
+    }
+    assert_eq!(
+        RecoverableSignature::from_compact(&compact, 4).err(),
+        Some("Invalid recovery id")
+    );
+}
+
+/// 高 S 签名可以验证，规范化后变为 low-S 且仍然有效
+#[test]
+fn high_s_normalization() {
+    let key = secret_key("0000000000000000000000000000000000000000000000000000000000000001");
+    let hash = Sha256::digest(b"Satoshi Nakamoto");
+    let signature = key.sign_ecdsa(&hash);
+
+    // s' = n - s
+    let order = unhex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+    let s = signature.s();
+    let mut high = [0u8; 32];
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let digit = order[i] as i16 - s[i] as i16 - borrow;
+        borrow = (digit < 0) as i16;
+        high[i] = digit.rem_euclid(256) as u8;
+    }
+    let mut compact = [0u8; 64];
+    compact[..32].copy_from_slice(&signature.r());
+    compact[32..].copy_from_slice(&high);
+
+    let mut high_s = Signature::from_compact(&compact).unwrap();
+    assert!(!high_s.is_low_s());
+    let public_key = PublicKey::from_secret_key(&key).unwrap();
+    assert!(public_key.verify_ecdsa(&hash, &high_s));
+    high_s.normalize_s();
+    assert!(high_s == signature);
+}
+
+/// 以太坊 v 值与 from_ethereum 往返；chain_id 过大时 v 会溢出
+#[test]
+fn ethereum_v_values() {
+    let key = secret_key("0000000000000000000000000000000000000000000000000000000000000001");
+    let hash = Sha256::digest(b"Satoshi Nakamoto");
+    let signature = key.sign_ecdsa_recoverable(&hash);
+    let (compact, recovery_id) = signature.serialize_compact();
+    let parity = (recovery_id & 1) as u64;
+
+    let largest = (u64::MAX - 36) / 2;
+    for (chain_id, v) in [
+        (None, 27 + parity),
+        (Some(1), 37 + parity),
+        (Some(56), 147 + parity),
+        (Some(largest), largest * 2 + 35 + parity),
+    ] {
+        assert_eq!(signature.ethereum_v(chain_id), Ok(v));
+        let parsed = RecoverableSignature::from_ethereum(&compact, v).unwrap();
+        assert_eq!(parsed.recovery_id(), recovery_id & 1);
+    }
+    for chain_id in [u64::MAX / 2, u64::MAX] {
+        assert_eq!(
+            signature.ethereum_v(Some(chain_id)),
+            Err("Chain id too large for EIP-155 v")
+        );
+    }
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use common::{hex, unhex};
+use flipper_zero_wallet::address::{
+    ethereum_checksum_address, generate_ethereum_address, validate_ethereum_address,
+};
+use flipper_zero_wallet::keccak256::Keccak256;
+use flipper_zero_wallet::secp256k1::{PublicKey, SecretKey};
+
+/// EIP-55 规范中的示例：全大写、全小写与大小写混合
+const EIP55_ALL_CAPS: [&str; 2] = [
+    "0x52908400098527886E0F7030069857D2E4169EE7",
+    "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+];
+const EIP55_ALL_LOWER: [&str; 2] = [
+    "0xde709f2102306220921060314715629080e2fb77",
+    "0x27b1fdb04752bbc536007a920d24acb045561c26",
+];
+const EIP55_NORMAL: [&str; 4] = [
+    "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+    "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+    "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+    "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+];
+
+fn address_bytes(address: &str) -> [u8; 20] {
+    unhex(&address[2..].to_ascii_lowercase())
+        .try_into()
+        .unwrap()
+}
+
+/// 原始 Keccak-256（填充为 0x01，不同于 SHA3-256 的 0x06）
+#[test]
+fn keccak256_vectors() {
+    let vectors = [
+        (
+            &b""[..],
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        ),
+        (
+            b"abc",
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        ),
+        (
+            b"The quick brown fox jumps over the lazy dog",
+            "4d741b6f1eb29cb2a9b9911c82f56fa8d73b04959d3d9d222895df6c0b28aa15",
+        ),
+    ];
+    for (message, digest) in vectors {
+        assert_eq!(hex(&Keccak256::digest(message)), digest);
+    }
+}
+
+/// 跨越 136 字节分组边界时，分段输入与一次输入的结果相同
+#[test]
+fn keccak256_incremental() {
+    let message: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+    let expected = Keccak256::digest(&message);
+    for split in [0, 1, 135, 136, 137, 272, 999, 1000] {
+        let mut hasher = Keccak256::new();
+        hasher.update(&message[..split]);
+        hasher.update(&message[split..]);
+        assert_eq!(hasher.finalize(), expected, "split at {}", split);
+    }
+}
+
+#[test]
+fn eip55_checksum() {
+    for address in EIP55_ALL_CAPS.iter().chain(&EIP55_ALL_LOWER) {
+        assert_eq!(
+            validate_ethereum_address(address, None),
+            Ok(address_bytes(address)),
+            "{}",
+            address
+        );
+    }
+    for address in EIP55_NORMAL {
+        let bytes = address_bytes(address);
+        assert_eq!(ethereum_checksum_address(&bytes, None), address.as_bytes());
+        assert_eq!(validate_ethereum_address(address, None), Ok(bytes));
+
+        // 任意一个字母改变大小写后校验失败
+        for (i, c) in address.char_indices().skip(2) {
+            if !c.is_ascii_alphabetic() {
+                continue;
+            }
+            let mut flipped = address.to_string();
+            let swapped = if c.is_ascii_uppercase() {
+                c.to_ascii_lowercase()
+            } else {
+                c.to_ascii_uppercase()
+            };
+            flipped.replace_range(i..i + 1, &swapped.to_string());
+            assert_eq!(
+                validate_ethereum_address(&flipped, None),
+                Err("Invalid Ethereum address checksum"),
+                "{}",
+                flipped
+            );
+        }
+    }
+
+    // 全小写、全大写的形式不带校验信息，总是接受
+    let address = EIP55_NORMAL[0];
+    let lower = format!("0x{}", address[2..].to_ascii_lowercase());
+    let upper = format!("0x{}", address[2..].to_ascii_uppercase());
+    assert!(validate_ethereum_address(&lower, None).is_ok());
+    assert!(validate_ethereum_address(&upper, None).is_ok());
+}
+
+/// EIP-1191 的校验大小写与链 ID 绑定
+#[test]
+fn eip1191_checksum() {
+    let bytes = address_bytes(EIP55_NORMAL[0]);
+    let rsk = ethereum_checksum_address(&bytes, Some(30));
+    let rsk = std::str::from_utf8(&rsk).unwrap();
+    assert_ne!(rsk, EIP55_NORMAL[0]);
+    assert_eq!(validate_ethereum_address(rsk, Some(30)), Ok(bytes));
+    assert_eq!(
+        validate_ethereum_address(rsk, None),
+        Err("Invalid Ethereum address checksum")
+    );
+    assert_eq!(
+        validate_ethereum_address(EIP55_NORMAL[0], Some(30)),
+        Err("Invalid Ethereum address checksum")
+    );
+}
+
+#[test]
+fn rejects_malformed_addresses() {
+    for (address, error) in [
+        (
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "Ethereum address must start with 0x",
+        ),
+        (
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe",
+            "Invalid Ethereum address length",
+        ),
+        (
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAedd",
+            "Invalid Ethereum address length",
+        ),
+        (
+            "0xg2908400098527886e0f7030069857d2e4169ee7",
+            "Invalid hex character",
+        ),
+    ] {
+        assert_eq!(
+            validate_ethereum_address(address, None),
+            Err(error),
+            "{}",
+            address
+        );
+    }
+}
+
+/// 私钥 1 对应的地址
+#[test]
+fn address_from_public_key() {
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let public_key = PublicKey::from_secret_key(&SecretKey::from_bytes(&one).unwrap()).unwrap();
+    assert_eq!(
+        generate_ethereum_address(&public_key).unwrap(),
+        b"0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+    );
+}
@@ -0,0 +1,505 @@
+mod common;
+
+use common::{hex, unhex};
+use flipper_zero_wallet::address::hash160;
+use flipper_zero_wallet::flipper_wallet_core::Wallet;
+use flipper_zero_wallet::psbt::{Map, Pair, Psbt};
+use flipper_zero_wallet::script;
+use flipper_zero_wallet::secp256k1::{SchnorrSignature, XOnlyPublicKey};
+use flipper_zero_wallet::transaction::{write_var_bytes, OutPoint, Transaction, TxIn, TxOut};
+
+/// BIP174 中的有效 PSBT（v0）
+const BIP174_VALID: [&str; 6] = [
+    // 一个 P2PKH 输入，输出映射为空
+    "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab300000000000000",
+    // P2PKH 与 P2SH-P2WPKH 输入，第一个已最终化
+    "70736274ff0100a00200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40000000000feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac000000000001076a47304402204759661797c01b036b25928948686218347d89864b719e1f7fcf57d1e511658702205309eabf56aa4d8891ffd111fdf1336f3a29da866d7f8486d75546ceedaf93190121035cdc61fc7ba971c0b501a646a2a83b102cb43881217ca682dc86e2d73fa882920001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb82308000000",
+    // 一个 P2PKH 输入，指定了哈希类型
+    "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000001030401000000000000",
+    // P2PKH 与 P2SH-P2WPKH 输入，带赎回脚本，输出带派生路径
+    "70736274ff0100a00200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40000000000feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac00000000000100df0200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf6000000006a473044022070b2245123e6bf474d60c5b50c043d4c691a5d2435f09a34a7662a9dc251790a022001329ca9dacf280bdf30740ec0390422422c81cb45839457aeb76fc12edd95b3012102657d118d3357b8e0f4c2cd46db7b39f6d9c38d9a70abcb9b2de5dc8dbfe4ce31feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e13000001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb8230800220202ead596687ca806043edc3de116cdf29d5e9257c196cd055cf698c8d02bf24e9910b4a6ba670000008000000080020000800022020394f62be9df19952c5587768aeb7698061ad2c4a25c894f47d8c162b4d7213d0510b4a6ba6700000080010000800200008000",
+    // P2SH-P2WSH 2-of-2 多签输入，带一个部分签名
+    "70736274ff0100550200000001279a2323a5dfb51fc45f220fa58b0fc13e1e3342792a85d7e36cd6333b5cbc390000000000ffffffff01a05aea0b000000001976a914ffe9c0061097cc3b636f2cb0460fa4fc427d2b4588ac0000000000010120955eea0b0000000017a9146345200f68d189e1adc0df1c4d16ea8f14c0dbeb87220203b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd4646304302200424b58effaaa694e1559ea5c93bbfd4a89064224055cdf070b6771469442d07021f5c8eb0fea6516d60b8acb33ad64ede60e8785bfb3aa94b99bdf86151db9a9a010104220020771fd18ad459666dd49f3d564e3dbc42f4c84774e360ada16816a8ed488d5681010547522103b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd462103de55d1e1dac805e3f8a58c1fbf9b94c02f3dbaafe127fefca4995f26f82083bd52ae220603b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd4610b4a6ba67000000800000008004000080220603de55d1e1dac805e3f8a58c1fbf9b94c02f3dbaafe127fefca4995f26f82083bd10b4a6ba670000008000000080050000800000",
+    // P2WSH 2-of-2 多签输入，带全局扩展公钥
+    "70736274ff01005202000000019dfc6628c26c5899fe1bd3dc338665bfd55d7ada10f6220973df2d386dec12760100000000ffffffff01f03dcd1d000000001600147b3a00bfdc14d27795c2b74901d09da6ef133579000000004f01043587cf02da3fd0088000000097048b1ad0445b1ec8275517727c87b4e4ebc18a203ffa0f94c01566bd38e9000351b743887ee1d40dc32a6043724f2d6459b3b5a4d73daec8fbae0472f3bc43e20cd90c6a4fae000080000000804f01043587cf02da3fd00880000001b90452427139cd78c2cff2444be353cd58605e3e513285e528b407fae3f6173503d30a5e97c8adbc557dac2ad9a7e39c1722ebac69e668b6f2667cc1d671c83cab0cd90c6a4fae000080010000800001012b0065cd1d000000002200202c5486126c4978079a814e13715d65f36459e4d6ccaded266d0508645bafa6320105475221029da12cdb5b235692b91536afefe5c91c3ab9473d8e43b533836ab456299c88712103372b34234ed7cf9c1fea5d05d441557927be9542b162eb02e1ab2ce80224c00b52ae2206029da12cdb5b235692b91536afefe5c91c3ab9473d8e43b533836ab456299c887110d90c6a4fae0000800000008000000000220603372b34234ed7cf9c1fea5d05d441557927be9542b162eb02e1ab2ce80224c00b10d90c6a4fae0000800100008000000000002202039eff1f547a1d5f92dfa2ba7af6ac971a4bd03ba4a734b03156a256b8ad3a1ef910ede45cc500000080000000800100008000",
+];
+
+/// BIP370 中的有效 PSBT（v2）：一个输入、两个输出
+const BIP370_VALID: [&str; 3] = [
+    // 只有必需字段
+    "70736274ff01020402000000010401010105010201fb040200000000010e200b0ad921419c1c8719735d72dc739f9ea9e0638d1fe4c1eef0f9944084815fc8010f0400000000000103080008af2f000000000104160014c430f64c4756da310dbd1a085572ef299926272c000103088bbdeb0b0000000001041600144dd193ac964a56ac1b9e1cca8454fe2f474f851300",
+    // 更新后：带前序交易、见证 UTXO 与派生路径
+    "70736274ff01020402000000010401010105010201fb0402000000000100520200000001c1aa256e214b96a1822f93de42bff3b5f3ff8d0519306e3515d7515a5e805b120000000000ffffffff0118c69a3b00000000160014b0a3af144208412693ca7d166852b52db0aef06e0000000001011f18c69a3b00000000160014b0a3af144208412693ca7d166852b52db0aef06e010e200b0ad921419c1c8719735d72dc739f9ea9e0638d1fe4c1eef0f9944084815fc8010f040000000000220202d601f84846a6755f776be00e3d9de8fb10acc935fb83c45fb0162d4cad5ab79218f69d873e540000800100008000000080000000002a0000000103080008af2f000000000104160014c430f64c4756da310dbd1a085572ef299926272c00220202e36fbff53dd534070cf8fd396614680f357a9b85db7340bf1cfa745d2ad7b34018f69d873e54000080010000800000008001000000640000000103088bbdeb0b0000000001041600144dd193ac964a56ac1b9e1cca8454fe2f474f851300",
+    // 全部 v2 字段（必需与可选）
+    "70736274ff010204020000000103040000000001040101010501020106010701fb040200000000010e200b0ad921419c1c8719735d72dc739f9ea9e0638d1fe4c1eef0f9944084815fc8010f0400000000011004feffffff0111048c8dc46201120410270000000103080008af2f000000000104160014c430f64c4756da310dbd1a085572ef299926272c000103088bbdeb0b0000000001041600144dd193ac964a56ac1b9e1cca8454fe2f474f851300",
+];
+
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+/// MNEMONIC 的主密钥指纹
+const FINGERPRINT: [u8; 4] = [0x73, 0xc5, 0xda, 0x0a];
+
+/// 签名测试的四个输入：BIP44/49/84/86 的第一个接收地址（m/purpose'/0'/0'/0/0）
+/// 的公钥、输出脚本与金额
+const INPUTS: [(u32, &str, &str, u64); 4] = [
+    (
+        44,
+        "03aaeb52dd7494c361049de67cc680e83ebcbbbdbeb13637d92cd845f70308af5e",
+        "76a914d986ed01b7a22225a70edbf2ba7cfb63a15cb3aa88ac",
+        100_000,
+    ),
+    (
+        49,
+        "039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c24",
+        "a9143fb6e95812e57bb4691f9a4a628862a61a4f769b87",
+        200_000,
+    ),
+    (
+        84,
+        "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+        "0014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2",
+        300_000,
+    ),
+    (
+        86,
+        "03cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+        "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+        400_000,
+    ),
+];
+
+/// 前三个输入的 ECDSA 部分签名（RFC 6979 确定性 nonce，末尾是 SIGHASH_ALL）
+const ECDSA_SIGNATURES: [&str; 3] = [
+    "304402204c85ca082736842cde1035435a075f3874082d536c16f6d7f98206bcfeaad2c9022063c05f31c1d9494e09319068ac471c053a2a423fe0e928f3ae3c21c00946e98a01",
+    "30450221008633c04cc8680b459909a20a8dbe5c5681ff1b6a7c3ce249d7760e346aa11eea02207878ee14f026b38cd353c8cec3fa427d4bc2b457a3fb9d7a55039e2a5e675a4e01",
+    "3045022100bc448f2f464e780409bc5fd9f085486a3b13699f117ea82e498a9f03a935935902206fa705ee8bbaf3d9dd79ab9bebb392ac60ac6cc53996167bf1a2ee59dd523e6b01",
+];
+
+/// Taproot 输入的 BIP341 签名哈希（SIGHASH_DEFAULT）
+const TAPROOT_SIGHASH: &str = "5cdaedccc7ddcce9018fbf76484d36e6ecb6d4d1e78545f01f977cba37aa1380";
+
+/// 付款输出（非本钱包）与找零输出（BIP84 第一个地址）
+const PAYMENT: u64 = 950_000;
+const CHANGE: u64 = 40_000;
+const LOCK_TIME: u32 = 800_000;
+
+fn pair(out: &mut Vec<u8>, key: &[u8], value: &[u8]) {
+    write_var_bytes(out, key);
+    write_var_bytes(out, value);
+}
+
+/// 密钥来源：指纹 || m/purpose'/0'/0'/0/index
+fn key_origin(fingerprint: [u8; 4], purpose: u32, index: u32) -> Vec<u8> {
+    let mut origin = fingerprint.to_vec();
+    for step in [purpose | 1 << 31, 1 << 31, 1 << 31, 0, index] {
+        origin.extend_from_slice(&step.to_le_bytes());
+    }
+    origin
+}
+
+/// 四个输入共同花费的前序交易
+fn prev_tx() -> Transaction {
+    Transaction {
+        version: 2,
+        inputs: vec![TxIn::new(OutPoint::new([0x11; 32], 0), 0xffff_ffff)],
+        outputs: INPUTS
+            .iter()
+            .map(|&(_, _, script_pubkey, value)| TxOut {
+                value,
+                script_pubkey: unhex(script_pubkey),
+            })
+            .collect(),
+        lock_time: 0,
+    }
+}
+
+fn unsigned_tx(prev_txid: [u8; 32]) -> Transaction {
+    Transaction {
+        version: 2,
+        inputs: (0..INPUTS.len() as u32)
+            .map(|vout| TxIn::new(OutPoint::new(prev_txid, vout), 0xffff_fffd))
+            .collect(),
+        outputs: vec![
+            TxOut {
+                value: PAYMENT,
+                script_pubkey: script::p2wpkh(&[0x22; 20]),
+            },
+            TxOut {
+                value: CHANGE,
+                script_pubkey: unhex(INPUTS[2].2),
+            },
+        ],
+        lock_time: LOCK_TIME,
+    }
+}
+
+/// 由更新者填好 UTXO、赎回脚本与派生路径、等待签名的 PSBT
+fn unsigned_psbt(v2: bool) -> Vec<u8> {
+    let prev = prev_tx();
+    let tx = unsigned_tx(prev.txid());
+
+    let mut out = b"psbt\xff".to_vec();
+    if v2 {
+        pair(&mut out, &[0x02], &tx.version.to_le_bytes());
+        pair(&mut out, &[0x03], &tx.lock_time.to_le_bytes());
+        pair(&mut out, &[0x04], &[tx.inputs.len() as u8]);
+        pair(&mut out, &[0x05], &[tx.outputs.len() as u8]);
+        pair(&mut out, &[0xfb], &2u32.to_le_bytes());
+    } else {
+        pair(&mut out, &[0x00], &tx.serialize());
+    }
+    out.push(0x00);
+
+    for (index, &(purpose, pubkey, _, _)) in INPUTS.iter().enumerate() {
+        let pubkey = unhex(pubkey);
+        pair(&mut out, &[0x00], &prev.serialize());
+        // 传统输入不带见证 UTXO
+        if purpose != 44 {
+            let mut utxo = Vec::new();
+            prev.outputs[index].consensus_encode(&mut utxo);
+            pair(&mut out, &[0x01], &utxo);
+        }
+        if purpose == 49 {
+            let redeem_script = script::p2wpkh(&hash160(&pubkey));
+            pair(&mut out, &[0x04], &redeem_script);
+        }
+        if purpose == 86 {
+            let mut key = vec![0x16];
+            key.extend_from_slice(&pubkey[1..]);
+            let mut origin = vec![0x00];
+            origin.extend(key_origin(FINGERPRINT, purpose, 0));
+            pair(&mut out, &key, &origin);
+            pair(&mut out, &[0x17], &pubkey[1..]);
+        } else {
+            let mut key = vec![0x06];
+            key.extend_from_slice(&pubkey);
+            pair(&mut out, &key, &key_origin(FINGERPRINT, purpose, 0));
+        }
+        if v2 {
+            let input = &tx.inputs[index];
+            pair(&mut out, &[0x0e], &input.previous_output.txid);
+            pair(&mut out, &[0x0f], &input.previous_output.vout.to_le_bytes());
+            pair(&mut out, &[0x10], &input.sequence.to_le_bytes());
+        }
+        out.push(0x00);
+    }
+
+    for (index, output) in tx.outputs.iter().enumerate() {
+        if index == 1 {
+            let mut key = vec![0x02];
+            key.extend(unhex(INPUTS[2].1));
+            pair(&mut out, &key, &key_origin(FINGERPRINT, 84, 0));
+        }
+        if v2 {
+            pair(&mut out, &[0x03], &output.value.to_le_bytes());
+            pair(&mut out, &[0x04], &output.script_pubkey);
+        }
+        out.push(0x00);
+    }
+    out
+}
+
+fn wallet() -> Wallet {
+    Wallet::from_mnemonic(MNEMONIC, "").unwrap()
+}
+
+/// 删除映射中指定类型的全部字段
+fn remove(map: &mut Map, key_type: u8) {
+    map.pairs.retain(|pair| pair.key_type() != key_type);
+}
+
+fn set(map: &mut Map, key: &[u8], value: &[u8]) {
+    map.pairs.retain(|pair| pair.key != key);
+    map.pairs.push(Pair {
+        key: key.to_vec(),
+        value: value.to_vec(),
+    });
+}
+
+/// 改写一个有效 PSBT 使其无效
+type Mutation = fn(&mut Psbt);
+
+fn reparse(psbt: &Psbt) -> Result<Psbt, &'static str> {
+    Psbt::parse(&psbt.serialize())
+}
+
+#[test]
+fn bip174_valid_round_trip() {
+    for vector in BIP174_VALID {
+        let raw = unhex(vector);
+        let psbt = Psbt::parse(&raw).unwrap_or_else(|e| panic!("{}: {}", e, vector));
+        assert_eq!(psbt.version, 0);
+        assert_eq!(psbt.serialize(), raw);
+    }
+
+    // 金额来自前序交易或见证 UTXO
+    let fees = [
+        Ok(301),
+        Err("Missing UTXO information"),
+        Ok(301),
+        Ok(90341),
+        Ok(1013),
+    ];
+    for (vector, fee) in BIP174_VALID.iter().zip(fees) {
+        assert_eq!(Psbt::parse(&unhex(vector)).unwrap().fee(), fee);
+    }
+}
+
+/// 按 BIP174 无效向量的各类错误改写有效 PSBT
+#[test]
+fn bip174_invalid() {
+    let valid = Psbt::parse(&unhex(BIP174_VALID[3])).unwrap();
+
+    // 普通交易而不是 PSBT
+    let network_tx = valid.inputs[0].get(0x00, &[]).unwrap();
+    assert_eq!(Psbt::parse(network_tx).unwrap_err(), "Invalid PSBT magic");
+
+    // 缺少输出映射
+    let raw = valid.serialize();
+    assert!(Psbt::parse(&raw[..raw.len() - 1]).is_err());
+    let mut trailing = raw.clone();
+    trailing.push(0x00);
+    assert!(Psbt::parse(&trailing).is_err());
+
+    // 缺少未签名交易
+    let mut psbt = valid.clone();
+    remove(&mut psbt.global, 0x00);
+    assert_eq!(reparse(&psbt).unwrap_err(), "Missing unsigned transaction");
+
+    // 未签名交易带有 scriptSig
+    let mut tx = Transaction::deserialize(valid.global.get(0x00, &[]).unwrap()).unwrap();
+    tx.inputs[0].script_sig = vec![0x51];
+    let mut psbt = valid.clone();
+    set(&mut psbt.global, &[0x00], &tx.serialize());
+    assert_eq!(
+        reparse(&psbt).unwrap_err(),
+        "Unsigned transaction has scriptSigs or witnesses"
+    );
+
+    // 重复的键
+    let mut psbt = valid.clone();
+    let duplicate = psbt.inputs[1].pairs[0].clone();
+    psbt.inputs[1].pairs.push(duplicate);
+    assert_eq!(reparse(&psbt).unwrap_err(), "Duplicate PSBT key");
+
+    // 不认识的版本
+    let mut psbt = valid.clone();
+    set(&mut psbt.global, &[0xfb], &1u32.to_le_bytes());
+    assert_eq!(reparse(&psbt).unwrap_err(), "Unsupported PSBT version");
+}
+
+#[test]
+fn bip370_valid_round_trip() {
+    for vector in BIP370_VALID {
+        let raw = unhex(vector);
+        let psbt = Psbt::parse(&raw).unwrap_or_else(|e| panic!("{}: {}", e, vector));
+        assert_eq!(psbt.version, 2);
+        assert_eq!(psbt.tx_version(), 2);
+        assert_eq!(psbt.serialize(), raw);
+        assert_eq!(psbt.output(0).unwrap().value, 800_000_000);
+        assert_eq!(psbt.output(1).unwrap().value, 199_998_859);
+    }
+
+    let updated = Psbt::parse(&unhex(BIP370_VALID[1])).unwrap();
+    assert_eq!(updated.fee(), Ok(141));
+
+    // 没有输入要求锁定时间时使用后备值（默认 0）；同时支持两种时优先区块高度
+    let required_only = Psbt::parse(&unhex(BIP370_VALID[0])).unwrap();
+    assert_eq!(required_only.lock_time(), 0);
+    let all_fields = Psbt::parse(&unhex(BIP370_VALID[2])).unwrap();
+    assert_eq!(all_fields.lock_time(), 10000);
+    let mut time_only = all_fields.clone();
+    remove(&mut time_only.inputs[0], 0x12);
+    assert_eq!(reparse(&time_only).unwrap().lock_time(), 1_657_048_460);
+}
+
+/// 按 BIP370 无效向量的各类错误改写有效 PSBT
+#[test]
+fn bip370_invalid() {
+    let valid = Psbt::parse(&unhex(BIP370_VALID[2])).unwrap();
+    let cases: [(Mutation, &str); 8] = [
+        (
+            |psbt| set(&mut psbt.global, &[0x00], &[0x00]),
+            "PSBT v2 must not contain an unsigned transaction",
+        ),
+        (
+            |psbt| remove(&mut psbt.global, 0x02),
+            "Missing transaction version",
+        ),
+        (|psbt| remove(&mut psbt.global, 0x04), "Missing input count"),
+        (
+            |psbt| remove(&mut psbt.global, 0x05),
+            "Missing output count",
+        ),
+        (
+            |psbt| remove(&mut psbt.inputs[0], 0x0e),
+            "Missing previous txid",
+        ),
+        (
+            |psbt| remove(&mut psbt.inputs[0], 0x0f),
+            "Missing output index",
+        ),
+        (
+            |psbt| remove(&mut psbt.outputs[0], 0x03),
+            "Missing output amount",
+        ),
+        (
+            |psbt| remove(&mut psbt.outputs[1], 0x04),
+            "Missing output script",
+        ),
+    ];
+    for (mutate, error) in cases {
+        let mut psbt = valid.clone();
+        mutate(&mut psbt);
+        assert_eq!(reparse(&psbt).unwrap_err(), error);
+    }
+
+    // v0 中不允许出现 v2 专用字段
+    let mut v0 = Psbt::parse(&unhex(BIP174_VALID[0])).unwrap();
+    set(&mut v0.inputs[0], &[0x0f], &0u32.to_le_bytes());
+    assert_eq!(reparse(&v0).unwrap_err(), "PSBT v0 contains v2 fields");
+    let mut v0 = Psbt::parse(&unhex(BIP174_VALID[0])).unwrap();
+    set(&mut v0.outputs[0], &[0x03], &0u64.to_le_bytes());
+    assert_eq!(reparse(&v0).unwrap_err(), "PSBT v0 contains v2 fields");
+}
+
+/// 为 P2PKH、P2SH-P2WPKH、P2WPKH 与 Taproot 密钥路径输入各添加一个签名
+#[test]
+fn signs_supported_input_types() {
+    let wallet = wallet();
+    for v2 in [false, true] {
+        let raw = unsigned_psbt(v2);
+        let mut psbt = Psbt::parse(&raw).unwrap();
+        assert_eq!(psbt.serialize(), raw);
+        assert_eq!(psbt.lock_time(), LOCK_TIME);
+        assert_eq!(psbt.fee(), Ok(1_000_000 - PAYMENT - CHANGE));
+
+        assert_eq!(psbt.sign(&wallet), Ok(4));
+        // 已有签名的输入不再重复签名
+        assert_eq!(psbt.sign(&wallet), Ok(0));
+
+        for (index, signature) in ECDSA_SIGNATURES.iter().enumerate() {
+            let pubkey = unhex(INPUTS[index].1);
+            assert_eq!(
+                hex(psbt.inputs[index].get(0x02, &pubkey).unwrap()),
+                *signature,
+                "input {}",
+                index
+            );
+        }
+
+        let signature = psbt.inputs[3].get(0x13, &[]).unwrap();
+        assert_eq!(signature.len(), 64);
+        let output_key =
+            XOnlyPublicKey::from_bytes(&unhex(INPUTS[3].2)[2..].try_into().unwrap()).unwrap();
+        let signature = SchnorrSignature::from_bytes(signature.try_into().unwrap());
+        assert!(output_key.verify_schnorr(&unhex(TAPROOT_SIGHASH), &signature));
+
+        let signed = reparse(&psbt).unwrap();
+        assert_eq!(signed.serialize(), psbt.serialize());
+    }
+}
+
+/// 无法签名的输入被跳过，其余输入照常签名
+#[test]
+fn skips_inputs_it_cannot_sign() {
+    let mut psbt = Psbt::parse(&unsigned_psbt(false)).unwrap();
+    // SIGHASH_NONE
+    set(&mut psbt.inputs[0], &[0x03], &2u32.to_le_bytes());
+    // 缺少前序交易，见证 UTXO 中的金额无法核对
+    remove(&mut psbt.inputs[2], 0x00);
+    // Taproot 不接受 SIGHASH_ALL | ANYONECANPAY
+    set(&mut psbt.inputs[3], &[0x03], &0x81u32.to_le_bytes());
+
+    assert_eq!(psbt.sign(&wallet()), Ok(1));
+    assert!(psbt.inputs[1].contains(0x02));
+    for index in [0, 2] {
+        assert!(!psbt.inputs[index].contains(0x02));
+    }
+    assert!(!psbt.inputs[3].contains(0x13));
+
+    // 内部密钥与输出脚本不符：不是本钱包的 Taproot 输出
+    let mut psbt = Psbt::parse(&unsigned_psbt(true)).unwrap();
+    set(&mut psbt.inputs[3], &[0x18], &[0x01; 32]);
+    assert_eq!(psbt.sign(&wallet()), Ok(3));
+    assert!(!psbt.inputs[3].contains(0x13));
+
+    // 派生路径指向其他密钥
+    let mut psbt = Psbt::parse(&unsigned_psbt(false)).unwrap();
+    let mut key = vec![0x06];
+    key.extend(unhex(INPUTS[0].1));
+    set(&mut psbt.inputs[0], &key, &key_origin(FINGERPRINT, 44, 1));
+    assert_eq!(psbt.sign(&wallet()), Ok(3));
+    assert!(!psbt.inputs[0].contains(0x02));
+}
+
+/// 见证 UTXO 与前序交易不一致时拒绝整个 PSBT
+#[test]
+fn rejects_mismatched_utxos() {
+    let wallet = wallet();
+
+    // 见证 UTXO 虚报金额
+    let mut psbt = Psbt::parse(&unsigned_psbt(false)).unwrap();
+    let mut utxo = Vec::new();
+    TxOut {
+        value: INPUTS[2].3 + 1_000_000,
+        script_pubkey: unhex(INPUTS[2].2),
+    }
+    .consensus_encode(&mut utxo);
+    set(&mut psbt.inputs[2], &[0x01], &utxo);
+    assert_eq!(
+        psbt.fee(),
+        Err("Witness UTXO does not match previous transaction")
+    );
+    assert_eq!(
+        psbt.sign(&wallet),
+        Err("Witness UTXO does not match previous transaction")
+    );
+
+    // 前序交易与输入引用的 txid 不符
+    let mut psbt = Psbt::parse(&unsigned_psbt(false)).unwrap();
+    let mut prev = prev_tx();
+    prev.outputs[0].value += 1_000_000;
+    set(&mut psbt.inputs[0], &[0x00], &prev.serialize());
+    assert_eq!(psbt.fee(), Err("Previous transaction does not match input"));
+    assert_eq!(
+        psbt.sign(&wallet),
+        Err("Previous transaction does not match input")
+    );
+}
+
+/// 只有派生路径属于本钱包、且输出脚本确实付给派生公钥的输出才算找零
+#[test]
+fn rejects_fake_change_outputs() {
+    let wallet = wallet();
+    let psbt = Psbt::parse(&unsigned_psbt(false)).unwrap();
+    assert!(!psbt.is_change_output(0, &wallet));
+    assert!(psbt.is_change_output(1, &wallet));
+    let review = psbt.review_output(1, &wallet).unwrap();
+    assert_eq!(review.value, CHANGE);
+    assert!(review.is_change);
+    assert_eq!(
+        review.address.as_deref(),
+        Some(&b"bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"[..])
+    );
+
+    let mut key = vec![0x02];
+    key.extend(unhex(INPUTS[2].1));
+
+    // 把本钱包的派生路径附在付款输出上
+    let mut psbt = Psbt::parse(&unsigned_psbt(false)).unwrap();
+    set(&mut psbt.outputs[0], &key, &key_origin(FINGERPRINT, 84, 0));
+    assert!(!psbt.is_change_output(0, &wallet));
+    assert!(!psbt.review_output(0, &wallet).unwrap().is_change);
+
+    // 指纹不是本钱包
+    let mut psbt = Psbt::parse(&unsigned_psbt(false)).unwrap();
+    set(&mut psbt.outputs[1], &key, &key_origin([0; 4], 84, 0));
+    assert!(!psbt.is_change_output(1, &wallet));
+
+    // 路径派生出的公钥与记录不符
+    let mut psbt = Psbt::parse(&unsigned_psbt(false)).unwrap();
+    set(&mut psbt.outputs[1], &key, &key_origin(FINGERPRINT, 84, 1));
+    assert!(!psbt.is_change_output(1, &wallet));
+}
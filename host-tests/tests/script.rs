@@ -0,0 +1,328 @@
+mod common;
+
+use common::{hex, unhex};
+use flipper_zero_wallet::address::{AddressType, Cryptocurrency};
+use flipper_zero_wallet::network::Network;
+use flipper_zero_wallet::script::{
+    classify, for_address_type, from_address, instructions, op_return, pays_to_public_key,
+    to_address, witness_program, Builder, Instruction, ScriptKind, OP_CHECKSIG, OP_DUP,
+};
+use flipper_zero_wallet::secp256k1::PublicKey;
+
+/// (地址, 网络, 输出脚本)：创世区块地址、BIP13 示例与 BIP173/BIP350 的地址向量
+const ADDRESS_VECTORS: [(&str, Network, &str); 8] = [
+    (
+        "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+        Network::Mainnet,
+        "76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac",
+    ),
+    (
+        "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+        Network::Mainnet,
+        "a914b472a266d0bd89c13706a4132ccfb16f7c3b9fcb87",
+    ),
+    (
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+        Network::Mainnet,
+        "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+    ),
+    (
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+        Network::Testnet3,
+        "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+    ),
+    (
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+        Network::Mainnet,
+        "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    ),
+    (
+        "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+        Network::Mainnet,
+        "5210751e76e8199196d454941c45d1b3a323",
+    ),
+    ("bc1sw50qgdz25j", Network::Mainnet, "6002751e"),
+    (
+        "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn",
+        Network::Testnet3,
+        "76a914243f1394f44554f4ce3fd68649c19adc483ce92488ac",
+    ),
+];
+
+/// BIP86 第一个接收地址的内部公钥与 tweak 后的输出密钥
+const BIP86_INTERNAL_KEY: &str =
+    "03cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115";
+const BIP86_OUTPUT_KEY: &str = "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c";
+/// BIP84 第一个接收地址的公钥
+const BIP84_KEY: &str = "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c";
+
+fn public_key(key: &str) -> PublicKey {
+    PublicKey::from_slice(&unhex(key)).unwrap()
+}
+
+#[test]
+fn address_round_trip() {
+    for (address, network, script) in ADDRESS_VECTORS {
+        assert_eq!(hex(&from_address(address).unwrap()), script, "{}", address);
+        assert_eq!(
+            to_address(&unhex(script), Cryptocurrency::Bitcoin, network).unwrap(),
+            address.as_bytes()
+        );
+    }
+
+    // 同一脚本在其他币种与网络上得到对应的地址
+    let p2wpkh = unhex("0014751e76e8199196d454941c45d1b3a323f1433bd6");
+    let litecoin = to_address(&p2wpkh, Cryptocurrency::Litecoin, Network::Mainnet).unwrap();
+    assert_eq!(
+        from_address(std::str::from_utf8(&litecoin).unwrap()).unwrap(),
+        p2wpkh
+    );
+    assert_eq!(
+        to_address(&p2wpkh, Cryptocurrency::Bitcoin, Network::Regtest).unwrap(),
+        b"bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080"
+    );
+    let p2pkh = unhex("76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac");
+    let dogecoin = to_address(&p2pkh, Cryptocurrency::Dogecoin, Network::Mainnet).unwrap();
+    assert_eq!(dogecoin[0], b'D');
+    assert_eq!(
+        from_address(std::str::from_utf8(&dogecoin).unwrap()).unwrap(),
+        p2pkh
+    );
+}
+
+#[test]
+fn addresses_without_scripts() {
+    assert_eq!(
+        from_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+        Err("Address has no output script")
+    );
+    assert_eq!(
+        from_address("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh"),
+        Err("Address has no output script")
+    );
+    assert!(from_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb").is_err());
+
+    let p2pkh = unhex("76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac");
+    assert_eq!(
+        to_address(&p2pkh, Cryptocurrency::Ethereum, Network::Mainnet),
+        Err("Cryptocurrency has no output scripts")
+    );
+    // 没有 bech32 前缀的币种无法表示见证输出
+    let p2wpkh = unhex("0014751e76e8199196d454941c45d1b3a323f1433bd6");
+    assert_eq!(
+        to_address(&p2wpkh, Cryptocurrency::Dogecoin, Network::Mainnet),
+        Err("Cryptocurrency has no segwit addresses")
+    );
+    for script in [
+        op_return(b"hello").unwrap(),
+        unhex(&format!("21{}ac", BIP84_KEY)),
+        vec![OP_CHECKSIG],
+    ] {
+        assert_eq!(
+            to_address(&script, Cryptocurrency::Bitcoin, Network::Mainnet),
+            Err("Script has no address")
+        );
+    }
+}
+
+#[test]
+fn classify_templates() {
+    let hash20: [u8; 20] = unhex("751e76e8199196d454941c45d1b3a323f1433bd6")
+        .try_into()
+        .unwrap();
+    let compressed = unhex(BIP84_KEY);
+    let uncompressed = public_key(BIP84_KEY).serialize_uncompressed();
+
+    let cases = [
+        (
+            "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac".to_string(),
+            ScriptKind::P2pkh(hash20),
+        ),
+        (
+            "a914751e76e8199196d454941c45d1b3a323f1433bd687".to_string(),
+            ScriptKind::P2sh(hash20),
+        ),
+        (
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6".to_string(),
+            ScriptKind::P2wpkh(hash20),
+        ),
+        (
+            format!("0020{}", "11".repeat(32)),
+            ScriptKind::P2wsh([0x11; 32]),
+        ),
+        (
+            format!("5120{}", "22".repeat(32)),
+            ScriptKind::P2tr([0x22; 32]),
+        ),
+        (
+            format!("21{}ac", hex(&compressed)),
+            ScriptKind::P2pk(compressed.clone()),
+        ),
+        (
+            format!("41{}ac", hex(&uncompressed)),
+            ScriptKind::P2pk(uncompressed.to_vec()),
+        ),
+        (
+            "6002751e".to_string(),
+            ScriptKind::WitnessUnknown {
+                version: 16,
+                program: unhex("751e"),
+            },
+        ),
+        // 长度不是 32 字节的 v1 程序
+        (
+            "5114751e76e8199196d454941c45d1b3a323f1433bd6".to_string(),
+            ScriptKind::WitnessUnknown {
+                version: 1,
+                program: hash20.to_vec(),
+            },
+        ),
+        // OP_RETURN 后的所有推入数据串联在一起
+        (
+            "6a0568656c6c6f4c0120".to_string(),
+            ScriptKind::OpReturn(b"hello ".to_vec()),
+        ),
+        ("6a".to_string(), ScriptKind::OpReturn(Vec::new())),
+        // OP_RETURN 后跟非推入操作码、推入被截断
+        ("6a0568656c6c6f76".to_string(), ScriptKind::NonStandard),
+        ("6a0568656c6c".to_string(), ScriptKind::NonStandard),
+        // 见证版本 0 的程序只能是 20 或 32 字节
+        (
+            "0010751e76e8199196d454941c45d1b3a323".to_string(),
+            ScriptKind::NonStandard,
+        ),
+        // 多出一个字节的 P2PKH
+        (
+            "76a914751e76e8199196d454941c45d1b3a323f1433bd688acac".to_string(),
+            ScriptKind::NonStandard,
+        ),
+        // 推入长度与公钥长度不符
+        (
+            format!("21{}ac", hex(&uncompressed)),
+            ScriptKind::NonStandard,
+        ),
+        (String::new(), ScriptKind::NonStandard),
+    ];
+    for (script, kind) in cases {
+        let script_bytes = unhex(&script);
+        assert_eq!(classify(&script_bytes), kind, "{}", script);
+    }
+
+    assert!(ScriptKind::P2tr([0; 32]).has_address());
+    assert!(!ScriptKind::OpReturn(Vec::new()).has_address());
+    assert!(!ScriptKind::P2pk(compressed).has_address());
+}
+
+/// 钱包地址类型的输出脚本只属于对应的公钥；P2TR 必须使用 BIP86 tweak 后的输出密钥
+#[test]
+fn pays_to_public_key_by_type() {
+    let key = public_key(BIP84_KEY);
+    let other = public_key(BIP86_INTERNAL_KEY);
+    for address_type in [
+        AddressType::P2pkh,
+        AddressType::P2shP2wpkh,
+        AddressType::P2wpkh,
+        AddressType::P2tr,
+    ] {
+        let script = for_address_type(address_type, &key).unwrap();
+        assert!(pays_to_public_key(&script, &key), "{:?}", address_type);
+        assert!(!pays_to_public_key(&script, &other), "{:?}", address_type);
+    }
+
+    let p2wpkh = for_address_type(AddressType::P2wpkh, &key).unwrap();
+    assert_eq!(hex(&p2wpkh), "0014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2");
+    // P2WSH 包含同样长度的哈希也不算
+    let mut p2wsh = vec![0x00, 0x20];
+    p2wsh.extend_from_slice(&[0u8; 12]);
+    p2wsh.extend_from_slice(&p2wpkh[2..]);
+    assert!(!pays_to_public_key(&p2wsh, &key));
+
+    let internal_key = public_key(BIP86_INTERNAL_KEY);
+    let tweaked = unhex(&format!("5120{}", BIP86_OUTPUT_KEY));
+    assert_eq!(
+        for_address_type(AddressType::P2tr, &internal_key).unwrap(),
+        tweaked
+    );
+    assert!(pays_to_public_key(&tweaked, &internal_key));
+    // 直接使用内部公钥（未 tweak）的输出不属于该密钥
+    let untweaked = unhex(&format!("5120{}", &BIP86_INTERNAL_KEY[2..]));
+    assert!(!pays_to_public_key(&untweaked, &internal_key));
+    assert_eq!(
+        to_address(&tweaked, Cryptocurrency::Bitcoin, Network::Mainnet).unwrap(),
+        b"bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+    );
+}
+
+#[test]
+fn builder_encodings() {
+    for (value, encoded) in [
+        (0, "00"),
+        (-1, "4f"),
+        (1, "51"),
+        (16, "60"),
+        (17, "0111"),
+        (-2, "0182"),
+        (127, "017f"),
+        (128, "028000"),
+        (-128, "028080"),
+        (255, "02ff00"),
+        (1000, "02e803"),
+        (i64::MAX, "08ffffffffffffff7f"),
+    ] {
+        assert_eq!(
+            hex(&Builder::new().push_int(value).into_script()),
+            encoded,
+            "{}",
+            value
+        );
+    }
+
+    // 推入长度的前缀：直接推入、OP_PUSHDATA1、OP_PUSHDATA2
+    for (len, prefix) in [(75, "4b"), (76, "4c4c"), (255, "4cff"), (256, "4d0001")] {
+        let script = Builder::new().push_slice(&vec![0xab; len]).into_script();
+        assert_eq!(hex(&script[..prefix.len() / 2]), prefix);
+        assert_eq!(script.len(), prefix.len() / 2 + len);
+        assert_eq!(
+            instructions(&script)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            [Instruction::Push(&script[prefix.len() / 2..])]
+        );
+    }
+
+    let script = Builder::new()
+        .push_opcode(OP_DUP)
+        .push_slice(b"ab")
+        .into_script();
+    assert_eq!(
+        instructions(&script)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+        [Instruction::Op(OP_DUP), Instruction::Push(b"ab")]
+    );
+    assert!(instructions(&unhex("4c05ab")).any(|i| i.is_err()));
+}
+
+#[test]
+fn template_limits() {
+    // 80 字节数据使用 OP_PUSHDATA1：6a 4c 50 <80 字节>
+    assert_eq!(op_return(&[0; 80]).unwrap().len(), 83);
+    assert_eq!(op_return(&[0; 81]), Err("OP_RETURN data too long"));
+
+    assert_eq!(
+        hex(&witness_program(16, &[0x75, 0x1e]).unwrap()),
+        "6002751e"
+    );
+    assert_eq!(
+        witness_program(17, &[0; 32]),
+        Err("Invalid witness version")
+    );
+    assert_eq!(
+        witness_program(1, &[0; 41]),
+        Err("Invalid witness program length")
+    );
+    assert_eq!(
+        witness_program(0, &[0; 21]),
+        Err("Invalid witness program length")
+    );
+}
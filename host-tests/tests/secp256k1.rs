@@ -0,0 +1,331 @@
+mod common;
+
+use common::{hex, unhex};
+use flipper_zero_wallet::secp256k1::{FieldElement, PublicKey, Scalar, SecretKey};
+
+const P: &str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+const N: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+const GX: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+const GY: &str = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+fn bytes32(s: &str) -> [u8; 32] {
+    unhex(s).try_into().unwrap()
+}
+
+fn field(s: &str) -> FieldElement {
+    FieldElement::from_bytes(&bytes32(s)).unwrap()
+}
+
+fn scalar(s: &str) -> Scalar {
+    Scalar::from_bytes(&bytes32(s)).unwrap()
+}
+
+/// 32 字节大端整数减去一个小整数
+fn minus(s: &str, k: u8) -> String {
+    let mut bytes = bytes32(s);
+    let mut borrow = k as u16;
+    for byte in bytes.iter_mut().rev() {
+        let value = (*byte as u16).wrapping_sub(borrow);
+        borrow = (value > 0xff) as u16;
+        *byte = value as u8;
+    }
+    hex(&bytes)
+}
+
+fn small(k: u8) -> String {
+    format!("{:064x}", k)
+}
+
+/// 私钥 k 对应的未压缩公钥
+fn mul_base(k: &str) -> String {
+    let key = SecretKey::from_bytes(&bytes32(k)).unwrap();
+    hex(&PublicKey::from_secret_key(&key)
+        .unwrap()
+        .serialize_uncompressed())
+}
+
+#[test]
+fn field_arithmetic() {
+    assert!(FieldElement::from_bytes(&bytes32(P)).is_err());
+    let p_minus_1 = field(&minus(P, 1));
+    let one = FieldElement::ONE;
+    assert_eq!(p_minus_1.add(&one), FieldElement::ZERO);
+    assert_eq!(FieldElement::ZERO.sub(&one), p_minus_1);
+    assert_eq!(one.neg(), p_minus_1);
+    assert_eq!(p_minus_1.mul(&p_minus_1), one);
+    assert_eq!(FieldElement::ZERO.neg(), FieldElement::ZERO);
+
+    let gx = field(GX);
+    let gy = field(GY);
+    assert_eq!(
+        hex(&gx.mul(&gy).to_bytes()),
+        "fd3dc529c6eb60fb9d166034cf3c1a5a72324aa9dfd3428a56d7e1ce0179fd9b"
+    );
+    assert_eq!(
+        hex(&gx.invert().to_bytes()),
+        "237afdf1d2938d86870aaeb8ad77626a67b8e794abfb076be61d003687ca9ef6"
+    );
+    assert_eq!(gx.mul(&gx.invert()), one);
+    assert_eq!(p_minus_1.invert(), p_minus_1);
+    assert_eq!(FieldElement::ZERO.invert(), FieldElement::ZERO);
+
+    // G 在曲线上：y^2 = x^3 + 7
+    let seven = field(&small(7));
+    assert_eq!(gy.square(), gx.square().mul(&gx).add(&seven));
+}
+
+#[test]
+fn field_square_roots() {
+    let two = field(&small(2));
+    let root = two.sqrt().unwrap();
+    assert_eq!(
+        hex(&root.to_bytes()),
+        "210c790573632359b1edb4302c117d8a132654692c3feeb7de3a86ac3f3b53f7"
+    );
+    assert_eq!(root.square(), two);
+
+    let four = field(&small(4));
+    let root = four.sqrt().unwrap();
+    assert!(root == field(&small(2)) || root == field(&small(2)).neg());
+    assert_eq!(FieldElement::ZERO.sqrt(), Some(FieldElement::ZERO));
+    // 3 与 7 不是模 p 的二次剩余
+    assert_eq!(field(&small(3)).sqrt(), None);
+    assert_eq!(field(&small(7)).sqrt(), None);
+    assert_eq!(field(&minus(P, 1)).sqrt(), None);
+}
+
+#[test]
+fn scalar_arithmetic() {
+    assert!(Scalar::from_bytes(&bytes32(N)).is_err());
+    assert!(Scalar::from_bytes_reduced(&bytes32(N)).is_zero());
+    assert_eq!(
+        hex(&Scalar::from_bytes_reduced(&[0xff; 32]).to_bytes()),
+        "000000000000000000000000000000014551231950b75fc4402da1732fc9bebe"
+    );
+
+    let n_minus_1 = scalar(&minus(N, 1));
+    assert!(n_minus_1.add(&Scalar::ONE).is_zero());
+    assert!(Scalar::ZERO.sub(&Scalar::ONE) == n_minus_1);
+    assert!(Scalar::ONE.neg() == n_minus_1);
+    assert!(n_minus_1.mul(&n_minus_1) == Scalar::ONE);
+
+    let a = scalar("c90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22");
+    let b = scalar("243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89");
+    assert_eq!(
+        hex(&a.mul(&b).to_bytes()),
+        "d1170412b7f4cf95f164cf730fc7a5257bf841d8614f4e5e651b1f2436ab7f8d"
+    );
+    assert_eq!(
+        hex(&a.add(&b).to_bytes()),
+        "ed4f452aa70bcb07d7dfecb9844c9015cd0b862ab406fe440a3ab93f276207ab"
+    );
+    assert_eq!(
+        hex(&b.sub(&a).to_bytes()),
+        "5b2f8fe6643a469e4e5327a28294567235b5c7004e800597c5f59a7f817112a8"
+    );
+    assert_eq!(
+        hex(&a.invert().to_bytes()),
+        "97e9f2f05c40846ed3d67e24f109cdb52f3d6bb8f54fa98c6807d48c14bbcd50"
+    );
+    assert!(a.mul(&a.invert()) == Scalar::ONE);
+    assert!(Scalar::ZERO.invert().is_zero());
+
+    // n / 2 向下取整是最大的低 S 值
+    let half = scalar("7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0");
+    assert!(!half.is_high());
+    assert!(half.add(&Scalar::ONE).is_high());
+    assert!(n_minus_1.is_high());
+}
+
+/// k * G：k = 1、2、3、(n-1)/2、n-2 与 n-1；n-k 与 k 的 x 坐标相同而 y 互为相反数
+#[test]
+fn base_point_multiples() {
+    let vectors = [
+        (small(1), format!("04{}{}", GX, GY)),
+        (
+            small(2),
+            "04c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee51ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a".to_string(),
+        ),
+        (
+            small(3),
+            "04f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672".to_string(),
+        ),
+        (
+            "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0".to_string(),
+            "0400000000000000000000003b78ce563f89a0ed9414f5aa28ad0d96d6795f9c633f3979bf72ae8202983dc989aec7f2ff2ed91bdd69ce02fc0700ca100e59ddf3".to_string(),
+        ),
+        (
+            minus(N, 2),
+            "04c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5e51e970159c23cc65c3a7be6b99315110809cd9acd992f1edc9bce55af301705".to_string(),
+        ),
+        (
+            minus(N, 1),
+            format!("04{}b7c52588d95c3b9aa25b0403f1eef75702e84bb7597aabe663b82f6f04ef2777", GX),
+        ),
+    ];
+    for (k, point) in vectors {
+        assert_eq!(mul_base(&k), point, "{}", k);
+    }
+
+    assert!(SecretKey::from_bytes(&[0; 32]).is_err());
+    assert!(SecretKey::from_bytes(&bytes32(N)).is_err());
+}
+
+/// 覆盖预计算表每一行与每个窗口值的标量（表中第 i 行对应 2^(32i) 的倍数）
+#[test]
+fn base_table_windows() {
+    let vectors = [
+        (
+            "0000000000000000000000000000000000000000000000000000000100000000",
+            "03100f44da696e71672791d0a09b7bde459f1215a29b3c03bfefd7835b39a48db0",
+        ),
+        (
+            "00000000000000000000000000000000000000000000000000000000ffffffff",
+            "02ba7e7b78e1ff713857bcc6432dcee5f7c6ca7fc8f84af479811bcabec921305e",
+        ),
+        (
+            "8000000000000000000000000000000000000000000000000000000000000000",
+            "02b23790a42be63e1b251ad6c94fdef07271ec0aada31db6c3e8bd32043f8be384",
+        ),
+        (
+            "0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f",
+            "021a7a569e91dbf60581509c7fc946d1003b60c7dee85299538db6353538d59574",
+        ),
+        (
+            "f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0",
+            "039e5f7dbe6d62ade5aab476b40559852ea1b5fc7bb99a61a42eab550f69ffafb4",
+        ),
+        (
+            "1111111111111111111111111111111111111111111111111111111111111111",
+            "034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa",
+        ),
+        (
+            "8888888888888888888888888888888888888888888888888888888888888888",
+            "021617d38ed8d8657da4d4761e8057bc396ea9e4b9d29776d4be096016dbd2509b",
+        ),
+        (
+            "ffffffff00000000ffffffff00000000ffffffff00000000ffffffff00000000",
+            "024efd7c3ec6bc78394138b2e550189884d14bbb948ec399e8671423975b9a97ad",
+        ),
+        (
+            "123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0",
+            "03192d7e7a302bd4ff27f48d7852621e0d3cb863a6dd67dd44e0314a25a3aa8668",
+        ),
+        (
+            "0000000100000001000000010000000100000001000000010000000100000001",
+            "02d798be011def700daf1a62a3670eb5c606dc4cb11acf9366f86d5a82c657135b",
+        ),
+    ];
+    for (k, point) in vectors {
+        let key = SecretKey::from_bytes(&bytes32(k)).unwrap();
+        let public_key = PublicKey::from_secret_key(&key).unwrap();
+        assert_eq!(hex(&public_key.serialize_compressed()), point, "{}", k);
+    }
+}
+
+/// a*G + b*G = (a+b)*G，包括 a = b（完备公式中的倍点）与和为零（无穷远点）
+#[test]
+fn point_addition() {
+    let a = "c90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b139b22";
+    let b = "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89";
+    for (a, b) in [(a, b), (a, a), (&small(1)[..], &minus(N, 2)[..])] {
+        let sum = scalar(a).add(&scalar(b));
+        let tweaked = PublicKey::from_slice(&unhex(&mul_base(a)))
+            .unwrap()
+            .add_exp_tweak(&bytes32(b))
+            .unwrap();
+        assert_eq!(
+            hex(&tweaked.serialize_uncompressed()),
+            mul_base(&hex(&sum.to_bytes()))
+        );
+    }
+
+    let g = PublicKey::from_slice(&unhex(&mul_base(&small(1)))).unwrap();
+    assert!(g.add_exp_tweak(&bytes32(&minus(N, 1))).is_err());
+    assert!(g.add_exp_tweak(&bytes32(N)).is_err());
+}
+
+/// 压缩与未压缩编码互相转换；0x02/0x03 前缀选择 y 的奇偶性
+#[test]
+fn public_key_encodings() {
+    for k in [small(1), small(2), small(3), minus(N, 1)] {
+        let uncompressed = unhex(&mul_base(&k));
+        let public_key = PublicKey::from_slice(&uncompressed).unwrap();
+        let compressed = public_key.serialize_compressed();
+        let decompressed = PublicKey::from_slice(&compressed).unwrap();
+        assert_eq!(decompressed.serialize_uncompressed()[..], uncompressed[..]);
+
+        // 翻转前缀得到 y 互为相反数的点
+        let mut flipped = compressed;
+        flipped[0] ^= 1;
+        let negated = PublicKey::from_slice(&flipped).unwrap();
+        assert_eq!(negated.x(), public_key.x());
+        assert_eq!(field(&hex(negated.y())), field(&hex(public_key.y())).neg());
+    }
+
+    // x = 1 在曲线上
+    let x1 = PublicKey::from_slice(&unhex(&format!("02{}", small(1)))).unwrap();
+    assert_eq!(hex(x1.x()), small(1));
+    assert_eq!(x1.y()[31] & 1, 0);
+}
+
+#[test]
+fn rejects_invalid_public_keys() {
+    let g = unhex(&mul_base(&small(1)));
+    let mut g_compressed = vec![0x02];
+    g_compressed.extend_from_slice(&g[1..33]);
+
+    let mut off_curve = g.clone();
+    off_curve[64] ^= 1;
+    let mut hybrid = g.clone();
+    hybrid[0] = 0x06;
+
+    let cases = [
+        // x = 5 时 x^3 + 7 不是二次剩余
+        (
+            unhex(&format!("02{}", small(5))),
+            "Public key is not on the curve",
+        ),
+        (off_curve, "Public key is not on the curve"),
+        // 坐标必须 < p：p 与 p + 1 不能作为 0 与 1 的另一种写法
+        (
+            unhex(&format!("02{}", P)),
+            "Public key x coordinate is out of range",
+        ),
+        (
+            unhex(&format!(
+                "02{}",
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc30"
+            )),
+            "Public key x coordinate is out of range",
+        ),
+        (
+            unhex(&format!("04{}{}", P, GY)),
+            "Public key x coordinate is out of range",
+        ),
+        (
+            unhex(&format!("04{}{}", GX, P)),
+            "Public key y coordinate is out of range",
+        ),
+        (vec![0x00], "Public key is the point at infinity"),
+        // 混合格式（0x06/0x07）、前缀与长度不符、缺少前缀
+        (hybrid, "Invalid public key encoding"),
+        (
+            [&[0x04], &g_compressed[1..]].concat(),
+            "Invalid public key encoding",
+        ),
+        ([&[0x02], &g[1..]].concat(), "Invalid public key encoding"),
+        (g[1..].to_vec(), "Invalid public key encoding"),
+        (g_compressed[..32].to_vec(), "Invalid public key encoding"),
+        (vec![0x00; 33], "Invalid public key encoding"),
+        (Vec::new(), "Invalid public key encoding"),
+    ];
+    for (data, error) in cases {
+        assert_eq!(
+            PublicKey::from_slice(&data).err(),
+            Some(error),
+            "{}",
+            hex(&data)
+        );
+    }
+}
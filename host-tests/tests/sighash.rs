@@ -0,0 +1,189 @@
+mod common;
+
+use common::{hex, load_vectors, unhex};
+use flipper_zero_wallet::bech32;
+use flipper_zero_wallet::secp256k1::{PublicKey, SchnorrSignature, SecretKey, XOnlyPublicKey};
+use flipper_zero_wallet::sighash::{legacy, segwit_v0, taproot};
+use flipper_zero_wallet::transaction::{Transaction, TxOut};
+
+fn bytes32(s: &str) -> [u8; 32] {
+    unhex(s).try_into().unwrap()
+}
+
+/// BIP143 示例：原生 P2WPKH、P2SH-P2WPKH 与 P2WSH 多签的全部六种哈希类型
+#[test]
+fn bip143_vectors() {
+    let tx = Transaction::deserialize(&unhex("0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000")).unwrap();
+    let script_code = unhex("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac");
+    assert_eq!(
+        hex(&segwit_v0(&tx, 1, &script_code, 600000000, 1).unwrap()),
+        "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670"
+    );
+
+    let tx = Transaction::deserialize(&unhex("0100000001db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a54770100000000feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac92040000")).unwrap();
+    let script_code = unhex("76a91479091972186c449eb1ded22b78e40d009bdf008988ac");
+    assert_eq!(
+        hex(&segwit_v0(&tx, 0, &script_code, 1000000000, 1).unwrap()),
+        "64f3b0f4dd2bb3aa1ce8566d220cc74dda9df97d8490cc81d89d735c92e59fb6"
+    );
+
+    let tx = Transaction::deserialize(&unhex("010000000136641869ca081e70f394c6948e8af409e18b619df2ed74aa106c1ca29787b96e0100000000ffffffff0200e9a435000000001976a914389ffce9cd9ae88dcc0631e88a821ffdbe9bfe2688acc0832f05000000001976a9147480a33f950689af511e6e84c138dbbd3c3ee41588ac00000000")).unwrap();
+    let witness_script = unhex("56210307b8ae49ac90a048e9b53357a2354b3334e9c8bee813ecb98e99a7e07e8c3ba32103b28f0c28bfab54554ae8c658ac5c3e0ce6e79ad336331f78c428dd43eea8449b21034b8113d703413d57761b8b9781957b8c0ac1dfe69f492580ca4195f50376ba4a21033400f6afecb833092a9a21cfdf1ed1376e58c5d1f47de74683123987e967a8f42103a6d48b1131e94ba04d9737d61acdaa1322008af9602b3b14862c07a1789aac162102d8b661b0b3302ee2f162b09e07a55ad5dfbe673a9f01d9f0c19617681024306b56ae");
+    let expected = [
+        (
+            0x01,
+            "185c0be5263dce5b4bb50a047973c1b6272bfbd0103a89444597dc40b248ee7c",
+        ),
+        (
+            0x02,
+            "e9733bc60ea13c95c6527066bb975a2ff29a925e80aa14c213f686cbae5d2f36",
+        ),
+        (
+            0x03,
+            "1e1f1c303dc025bd664acb72e583e933fae4cff9148bf78c157d1e8f78530aea",
+        ),
+        (
+            0x81,
+            "2a67f03e63a6a422125878b40b82da593be8d4efaafe88ee528af6e5a9955c6e",
+        ),
+        (
+            0x82,
+            "781ba15f3779d5542ce8ecb5c18716733a5ee42a6f51488ec96154934e2c890a",
+        ),
+        (
+            0x83,
+            "511e8e52ed574121fc1b654970395502128263f62662e076dc6baf05c2e6a99b",
+        ),
+    ];
+    for (sighash_type, digest) in expected {
+        assert_eq!(
+            hex(&segwit_v0(&tx, 0, &witness_script, 987654321, sighash_type).unwrap()),
+            digest,
+            "type {:#x}",
+            sighash_type
+        );
+    }
+}
+
+/// 传统 SIGHASH_SINGLE 没有对应输出时按共识规则返回整数 1
+#[test]
+fn legacy_single_without_output() {
+    let tx = Transaction::deserialize(&unhex("010000000136641869ca081e70f394c6948e8af409e18b619df2ed74aa106c1ca29787b96e0100000000ffffffff0200e9a435000000001976a914389ffce9cd9ae88dcc0631e88a821ffdbe9bfe2688acc0832f05000000001976a9147480a33f950689af511e6e84c138dbbd3c3ee41588ac00000000")).unwrap();
+    let mut two_inputs = tx.clone();
+    two_inputs.inputs.push(tx.inputs[0].clone());
+    two_inputs.inputs.push(tx.inputs[0].clone());
+
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    assert_eq!(legacy(&two_inputs, 2, &[], 0x03).unwrap(), one);
+    assert_ne!(legacy(&two_inputs, 1, &[], 0x03).unwrap(), one);
+    assert!(legacy(&two_inputs, 3, &[], 0x01).is_err());
+}
+
+/// Bitcoin Core 的 src/test/data/sighash.json（传统签名哈希，结果按 uint256 十六进制倒序显示）
+#[test]
+#[ignore = "needs vectors/ from fetch-vectors.sh"]
+fn bitcoin_core_sighash_json() {
+    let vectors = load_vectors("sighash.json");
+    let mut checked = 0;
+    for case in vectors.as_array() {
+        let fields = case.as_array();
+        // 第一项是格式说明
+        if fields.len() == 1 {
+            continue;
+        }
+        let tx = Transaction::deserialize(&unhex(fields[0].as_str())).unwrap();
+        let script_code = unhex(fields[1].as_str());
+        let input_index = fields[2].as_i64() as usize;
+        // 哈希类型是有符号 32 位整数
+        let sighash_type = fields[3].as_i64() as i32 as u32;
+
+        let mut digest = legacy(&tx, input_index, &script_code, sighash_type).unwrap();
+        digest.reverse();
+        assert_eq!(hex(&digest), fields[4].as_str(), "{:?}", fields);
+        checked += 1;
+    }
+    assert!(checked > 0);
+}
+
+/// BIP341 wallet-test-vectors.json：输出密钥调整与密钥路径花费
+#[test]
+#[ignore = "needs vectors/ from fetch-vectors.sh"]
+fn bip341_wallet_vectors() {
+    let vectors = load_vectors("bip341_wallet_vectors.json");
+
+    for case in vectors.get("scriptPubKey").as_array() {
+        let internal =
+            XOnlyPublicKey::from_bytes(&bytes32(case.get("given").get("internalPubkey").as_str()))
+                .unwrap();
+        let intermediary = case.get("intermediary");
+        let merkle_root = match intermediary.get("merkleRoot") {
+            root if root.is_null() => None,
+            root => Some(bytes32(root.as_str())),
+        };
+        let (output_key, _) = internal.tap_tweak(merkle_root.as_ref()).unwrap();
+        let program = output_key.serialize();
+        assert_eq!(hex(&program), intermediary.get("tweakedPubkey").as_str());
+
+        let expected = case.get("expected");
+        assert_eq!(
+            format!("5120{}", hex(&program)),
+            expected.get("scriptPubKey").as_str()
+        );
+        let address = bech32::encode_segwit_address("bc", 1, &program).unwrap();
+        assert_eq!(
+            String::from_utf8(address).unwrap(),
+            expected.get("bip350Address").as_str()
+        );
+    }
+
+    for case in vectors.get("keyPathSpending").as_array() {
+        let given = case.get("given");
+        let tx = Transaction::deserialize(&unhex(given.get("rawUnsignedTx").as_str())).unwrap();
+        let spent: Vec<TxOut> = given
+            .get("utxosSpent")
+            .as_array()
+            .iter()
+            .map(|utxo| TxOut {
+                value: utxo.get("amountSats").as_i64() as u64,
+                script_pubkey: unhex(utxo.get("scriptPubKey").as_str()),
+            })
+            .collect();
+
+        for input in case.get("inputSpending").as_array() {
+            let given = input.get("given");
+            let intermediary = input.get("intermediary");
+            let input_index = given.get("txinIndex").as_i64() as usize;
+            let sighash_type = given.get("hashType").as_i64() as u32;
+
+            let digest = taproot(&tx, input_index, &spent, sighash_type, None, None).unwrap();
+            assert_eq!(hex(&digest), intermediary.get("sigHash").as_str());
+
+            let merkle_root = match given.get("merkleRoot") {
+                root if root.is_null() => None,
+                root => Some(bytes32(root.as_str())),
+            };
+            let internal =
+                SecretKey::from_bytes(&bytes32(given.get("internalPrivkey").as_str())).unwrap();
+            let tweaked = internal.tap_tweak(merkle_root.as_ref()).unwrap();
+            assert_eq!(
+                hex(tweaked.as_bytes()),
+                intermediary.get("tweakedPrivkey").as_str()
+            );
+
+            // 见证只有一项签名；非 SIGHASH_DEFAULT 时末尾附加哈希类型
+            let witness = unhex(input.get("expected").get("witness").as_array()[0].as_str());
+            if sighash_type == 0 {
+                assert_eq!(witness.len(), 64);
+            } else {
+                assert_eq!(witness.len(), 65);
+                assert_eq!(witness[64] as u32, sighash_type);
+            }
+            let signature = SchnorrSignature::from_bytes(&witness[..64].try_into().unwrap());
+            let (output_key, _) = PublicKey::from_secret_key(&tweaked)
+                .unwrap()
+                .x_only_public_key();
+            assert!(output_key.verify_schnorr(&digest, &signature));
+        }
+    }
+}
@@ -0,0 +1,336 @@
+mod common;
+
+use common::unhex;
+use flipper_zero_wallet::address::hash160;
+use flipper_zero_wallet::bip32::DerivationPath;
+use flipper_zero_wallet::coin_selection::Strategy;
+use flipper_zero_wallet::flipper_wallet_core::Wallet;
+use flipper_zero_wallet::script::{self, ScriptKind};
+use flipper_zero_wallet::secp256k1::{
+    PublicKey, SchnorrSignature, SecretKey, Signature, XOnlyPublicKey,
+};
+use flipper_zero_wallet::sighash::{self, SIGHASH_ALL, SIGHASH_DEFAULT};
+use flipper_zero_wallet::spend::{build, Spend, SpendRequest, MAX_FEE_RATE};
+use flipper_zero_wallet::transaction::{OutPoint, Transaction, TxIn, TxOut};
+use flipper_zero_wallet::utxo::Utxo;
+
+/// BIP39 测试助记词（指纹 73c5da0a）
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+/// 账户 0 第一个收款地址的输出脚本：BIP84、BIP44、BIP49、BIP86
+const SCRIPTS: [(&str, u64); 4] = [
+    ("0014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2", 300_000),
+    (
+        "76a914d986ed01b7a22225a70edbf2ba7cfb63a15cb3aa88ac",
+        100_000,
+    ),
+    ("a9143fb6e95812e57bb4691f9a4a628862a61a4f769b87", 200_000),
+    (
+        "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+        400_000,
+    ),
+];
+
+/// BIP84 测试向量 m/84'/0'/0'/1/0
+const BIP84_CHANGE: &str = "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el";
+
+const DESTINATION: &str = "bc1qyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zc6v074";
+
+fn utxo(script_pubkey: &str, value: u64, vout: u32) -> Utxo {
+    Utxo {
+        outpoint: OutPoint::new([0x11; 32], vout),
+        value,
+        script_pubkey: unhex(script_pubkey),
+        path: None,
+        verified: true,
+    }
+}
+
+fn utxos() -> Vec<Utxo> {
+    SCRIPTS
+        .iter()
+        .zip(0..)
+        .map(|(&(script_pubkey, value), vout)| utxo(script_pubkey, value, vout))
+        .collect()
+}
+
+fn request(amount: u64, fee_rate: u64) -> SpendRequest<'static> {
+    SpendRequest {
+        destination: DESTINATION,
+        amount,
+        fee_rate,
+        strategy: Strategy::LargestFirst,
+        change_index: Some(0),
+    }
+}
+
+/// 手续费等于输入减输出，且按签名后的实际大小不低于请求的费率
+fn assert_fee(spend: &Spend, utxos: &[Utxo], fee_rate: u64) {
+    let inputs: u64 = spend
+        .tx
+        .inputs
+        .iter()
+        .map(|input| {
+            utxos
+                .iter()
+                .find(|utxo| utxo.outpoint == input.previous_output)
+                .unwrap()
+                .value
+        })
+        .sum();
+    let outputs: u64 = spend.tx.outputs.iter().map(|output| output.value).sum();
+    assert_eq!(spend.fee, inputs - outputs);
+    assert!(spend.fee >= spend.tx.vsize() as u64 * fee_rate);
+}
+
+/// 独立计算每个输入的签名哈希并验证签名，同时确认密钥与输出脚本相符
+fn assert_signed(tx: &Transaction, spent: &[TxOut]) {
+    for (index, (input, prevout)) in tx.inputs.iter().zip(spent).enumerate() {
+        let script_pubkey = &prevout.script_pubkey;
+        match script::classify(script_pubkey) {
+            ScriptKind::P2tr(_) => {
+                assert!(input.script_sig.is_empty());
+                let signature = input.witness.get(0).unwrap();
+                assert_eq!(signature.len(), 64);
+                let digest =
+                    sighash::taproot(tx, index, spent, SIGHASH_DEFAULT, None, None).unwrap();
+                let output_key =
+                    XOnlyPublicKey::from_bytes(script_pubkey[2..].try_into().unwrap()).unwrap();
+                let signature = SchnorrSignature::from_bytes(signature.try_into().unwrap());
+                assert!(output_key.verify_schnorr(&digest, &signature));
+            }
+            kind => {
+                let (signature, pubkey) = match kind {
+                    ScriptKind::P2pkh(_) => {
+                        assert!(input.witness.is_empty());
+                        let script_sig = &input.script_sig;
+                        let sig_len = script_sig[0] as usize;
+                        (&script_sig[1..1 + sig_len], &script_sig[2 + sig_len..])
+                    }
+                    _ => (input.witness.get(0).unwrap(), input.witness.get(1).unwrap()),
+                };
+                let public_key = PublicKey::from_slice(pubkey).unwrap();
+                assert!(script::pays_to_public_key(script_pubkey, &public_key));
+
+                let digest = match kind {
+                    ScriptKind::P2pkh(_) => {
+                        sighash::legacy(tx, index, script_pubkey, SIGHASH_ALL).unwrap()
+                    }
+                    _ => {
+                        let script_code = script::p2pkh(&hash160(pubkey));
+                        sighash::segwit_v0(tx, index, &script_code, prevout.value, SIGHASH_ALL)
+                            .unwrap()
+                    }
+                };
+                let (der, sighash_type) = signature.split_at(signature.len() - 1);
+                assert_eq!(sighash_type, [SIGHASH_ALL as u8]);
+                let signature = Signature::from_der(der).unwrap();
+                assert!(signature.is_low_s());
+                assert!(public_key.verify_ecdsa(&digest, &signature));
+            }
+        }
+    }
+}
+
+fn spent_outputs(spend: &Spend, utxos: &[Utxo]) -> Vec<TxOut> {
+    spend
+        .tx
+        .inputs
+        .iter()
+        .map(|input| {
+            utxos
+                .iter()
+                .find(|utxo| utxo.outpoint == input.previous_output)
+                .unwrap()
+                .txout()
+        })
+        .collect()
+}
+
+#[test]
+fn signs_every_input_type() {
+    let wallet = Wallet::from_mnemonic(MNEMONIC, "").unwrap();
+    let utxos = utxos();
+    let spend = build(&wallet, &utxos, &request(950_000, 2)).unwrap();
+
+    assert_eq!(spend.tx.version, 2);
+    assert_eq!(spend.tx.inputs.len(), 4);
+    assert!(spend
+        .tx
+        .inputs
+        .iter()
+        .all(|input| input.sequence < 0xffff_fffe));
+    assert_eq!(spend.tx.outputs[0].value, 950_000);
+    assert_eq!(
+        spend.tx.outputs[0].script_pubkey,
+        script::from_address(DESTINATION).unwrap()
+    );
+
+    // 找零沿用第一个 UTXO 的地址类型，派生在找零链上
+    assert_eq!(spend.change_output, Some(1));
+    assert_eq!(
+        spend.tx.outputs[1].script_pubkey,
+        script::from_address(BIP84_CHANGE).unwrap()
+    );
+    assert_eq!(
+        spend.change_path,
+        Some(DerivationPath::parse("m/84'/0'/0'/1/0").unwrap())
+    );
+
+    assert_fee(&spend, &utxos, 2);
+    assert_signed(&spend.tx, &spent_outputs(&spend, &utxos));
+    // 序列化后能原样解析回来
+    assert_eq!(
+        Transaction::deserialize(&unhex(&spend.raw_hex())).unwrap(),
+        spend.tx
+    );
+}
+
+#[test]
+fn change_is_dropped_when_it_would_be_dust() {
+    let wallet = Wallet::from_mnemonic(MNEMONIC, "").unwrap();
+    let utxos = utxos();
+    let with_change = build(&wallet, &utxos[3..], &request(300_000, 1)).unwrap();
+    let change = with_change.tx.outputs[1].value;
+
+    // 把找零加到付款中：剩下的零头不足以支付找零输出，全部计入手续费
+    let spend = build(&wallet, &utxos[3..], &request(300_000 + change, 1)).unwrap();
+    assert_eq!(spend.tx.outputs.len(), 1);
+    assert_eq!(spend.change_output, None);
+    assert_eq!(spend.change_path, None);
+    assert_fee(&spend, &utxos, 1);
+    assert_signed(&spend.tx, &spent_outputs(&spend, &utxos));
+
+    let branch_and_bound = SpendRequest {
+        strategy: Strategy::BranchAndBound,
+        ..request(300_000 + change, 1)
+    };
+    let spend = build(&wallet, &utxos, &branch_and_bound).unwrap();
+    assert_eq!(spend.change_output, None);
+    assert_fee(&spend, &utxos, 1);
+}
+
+#[test]
+fn fee_rate_limits() {
+    let wallet = Wallet::from_mnemonic(MNEMONIC, "").unwrap();
+    let utxos = utxos();
+    let spend = build(&wallet, &utxos, &request(100_000, MAX_FEE_RATE)).unwrap();
+    assert_fee(&spend, &utxos, MAX_FEE_RATE);
+
+    assert_eq!(
+        build(&wallet, &utxos, &request(100_000, MAX_FEE_RATE + 1)).unwrap_err(),
+        "Fee rate too high"
+    );
+    assert_eq!(
+        build(&wallet, &utxos, &request(100_000, 0)).unwrap_err(),
+        "Fee rate must be at least 1 sat/vB"
+    );
+}
+
+#[test]
+fn rejects_invalid_requests() {
+    let wallet = Wallet::from_mnemonic(MNEMONIC, "").unwrap();
+    let utxos = utxos();
+    assert_eq!(
+        build(&wallet, &utxos, &request(293, 1)).unwrap_err(),
+        "Amount below dust limit"
+    );
+    assert_eq!(
+        build(&wallet, &utxos, &request(1_000_000, 1)).unwrap_err(),
+        "Insufficient funds"
+    );
+    assert_eq!(
+        build(&wallet, &[], &request(10_000, 1)).unwrap_err(),
+        "No UTXOs to spend"
+    );
+
+    // 测试网地址
+    let testnet = SpendRequest {
+        destination: "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx",
+        ..request(10_000, 1)
+    };
+    assert_eq!(
+        build(&wallet, &utxos, &testnet).unwrap_err(),
+        "Destination is for a different network"
+    );
+
+    // UTXO 中没有找零链上的输出时必须给出找零索引
+    let no_change_index = SpendRequest {
+        change_index: None,
+        ..request(10_000, 1)
+    };
+    assert_eq!(
+        build(&wallet, &utxos, &no_change_index).unwrap_err(),
+        "Change index required"
+    );
+
+    let mut foreign = utxos.clone();
+    foreign[1].script_pubkey = unhex("76a914000000000000000000000000000000000000000088ac");
+    assert_eq!(
+        build(&wallet, &foreign, &request(10_000, 1)).unwrap_err(),
+        "UTXO not owned by wallet"
+    );
+}
+
+/// 没有前序交易的 P2PKH 与 SegWit v0 UTXO 可能被篡改了金额，拒绝花费；Taproot 不受影响
+#[test]
+fn requires_previous_transaction_for_non_taproot() {
+    let wallet = Wallet::from_mnemonic(MNEMONIC, "").unwrap();
+    for i in 0..3 {
+        let mut utxos = utxos();
+        utxos[i].verified = false;
+        assert_eq!(
+            build(&wallet, &utxos, &request(10_000, 1)).unwrap_err(),
+            "Non-Taproot UTXOs need prev_tx"
+        );
+    }
+
+    let mut taproot = utxos().split_off(3);
+    taproot[0].verified = false;
+    let spend = build(&wallet, &taproot, &request(10_000, 1)).unwrap();
+    assert_signed(&spend.tx, &[taproot[0].txout()]);
+}
+
+/// 单私钥钱包只有一个地址，找零回到被花费的地址
+#[test]
+fn single_key_change_reuses_input_address() {
+    let secret_key = SecretKey::from_bytes(&[0x42; 32]).unwrap();
+    let public_key = PublicKey::from_secret_key(&secret_key).unwrap();
+    let wallet = Wallet::from_secret_key(secret_key, true);
+    let script_pubkey = script::p2wpkh(&hash160(&public_key.serialize_compressed()));
+
+    let prev = Transaction {
+        version: 2,
+        inputs: vec![TxIn::new(OutPoint::new([0x22; 32], 0), 0xffff_ffff)],
+        outputs: vec![TxOut {
+            value: 50_000,
+            script_pubkey: script_pubkey.clone(),
+        }],
+        lock_time: 0,
+    };
+    let utxos = [Utxo {
+        outpoint: OutPoint::new(prev.txid(), 0),
+        value: 50_000,
+        script_pubkey: script_pubkey.clone(),
+        path: None,
+        verified: true,
+    }];
+    let request = SpendRequest {
+        change_index: None,
+        ..request(20_000, 5)
+    };
+    let spend = build(&wallet, &utxos, &request).unwrap();
+    assert_eq!(spend.change_output, Some(1));
+    assert_eq!(spend.tx.outputs[1].script_pubkey, script_pubkey);
+    assert_eq!(spend.change_path, None);
+    assert_fee(&spend, &utxos, 5);
+    assert_signed(&spend.tx, &[utxos[0].txout()]);
+
+    // 不属于这把私钥的 UTXO
+    let bip84 = [utxo(SCRIPTS[0].0, SCRIPTS[0].1, 0)];
+    assert_eq!(
+        build(&wallet, &bip84, &request).unwrap_err(),
+        "UTXO not owned by wallet"
+    );
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::unhex;
+use flipper_zero_wallet::transaction::{
+    txid_to_hex, write_compact_size, Decoder, Transaction, Witness,
+};
+
+/// 创世区块 coinbase 交易
+const GENESIS_COINBASE: &str = "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000";
+
+/// 区块 170：第一笔非 coinbase 交易（P2PK 花费，10 BTC 付给 Hal Finney）
+const BLOCK_170_TX: &str = "0100000001c997a5e56e104102fa209c6a852dd90660a20b2d9c352423edce25857fcd3704000000004847304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd410220181522ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d0901ffffffff0200ca9a3b00000000434104ae1a62fe09c5f51b13905f07f06b99a2f7159b2225f374cd378d71302fa28414e7aab37397f554a7df5f142c21c1b7303b8a0626f1baded5c72a704f7e6cd84cac00286bee0000000043410411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3ac00000000";
+
+/// BIP143 P2SH-P2WPKH 示例的已签名交易
+const BIP143_P2SH_P2WPKH: &str = "01000000000101db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a5477010000001716001479091972186c449eb1ded22b78e40d009bdf0089feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac02473044022047ac8e878352d3ebbde1c94ce3a10d057c24175747116f8288e5d794d12d482f0220217f36a485cae903c713331d877c1f64677e3622ad4010726870540656fe9dcb012103ad1d8e89212f0b92c74d23bb710c00662ad1470198ac48c43f7d6f93a2a2687392040000";
+
+#[test]
+fn genesis_coinbase_round_trip() {
+    let raw = unhex(GENESIS_COINBASE);
+    let tx = Transaction::deserialize(&raw).unwrap();
+
+    assert!(tx.is_coinbase());
+    assert!(!tx.has_witness());
+    assert_eq!(tx.serialize(), raw);
+    assert_eq!(
+        txid_to_hex(&tx.txid()).as_str(),
+        "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+    );
+    assert_eq!(tx.txid(), tx.wtxid());
+    assert_eq!(tx.outputs[0].value, 50_0000_0000);
+    assert_eq!(tx.weight(), raw.len() * 4);
+}
+
+#[test]
+fn block_170_round_trip() {
+    let raw = unhex(BLOCK_170_TX);
+    let tx = Transaction::deserialize(&raw).unwrap();
+
+    assert!(!tx.is_coinbase());
+    assert_eq!(tx.serialize(), raw);
+    assert_eq!(
+        txid_to_hex(&tx.txid()).as_str(),
+        "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16"
+    );
+    assert_eq!(
+        txid_to_hex(&tx.inputs[0].previous_output.txid).as_str(),
+        "0437cd7f8525ceed2324359c2d0ba26006d92d856a9c20fa0241106ee5a597c9"
+    );
+    assert_eq!(tx.outputs[0].value, 10_0000_0000);
+    assert_eq!(tx.outputs[1].value, 40_0000_0000);
+    assert_eq!(tx.total_output_value(), Some(50_0000_0000));
+}
+
+#[test]
+fn segwit_round_trip() {
+    let raw = unhex(BIP143_P2SH_P2WPKH);
+    let tx = Transaction::deserialize(&raw).unwrap();
+
+    assert!(tx.has_witness());
+    assert_eq!(tx.serialize(), raw);
+    assert_eq!(tx.inputs[0].witness.len(), 2);
+    assert_eq!(tx.serialize_no_witness().len(), 142);
+    assert_eq!(
+        txid_to_hex(&tx.txid()).as_str(),
+        "ef48d9d0f595052e0f8cdcf825f7a5e50b6a388a81f206f3f4846e5ecd7a0c23"
+    );
+    assert_eq!(
+        txid_to_hex(&tx.wtxid()).as_str(),
+        "680f483b2bf6c5dcbf111e69e885ba248a41a5e92070cfb0afec3cfc49a9fabb"
+    );
+    assert_eq!(tx.weight(), 142 * 3 + 251);
+    assert_eq!(tx.vsize(), 170);
+}
+
+#[test]
+fn rejects_malformed_encodings() {
+    let raw = unhex(BIP143_P2SH_P2WPKH);
+    let tx = Transaction::deserialize(&raw).unwrap();
+
+    // 有 marker/flag 但所有见证为空
+    let mut stripped = tx.clone();
+    for input in stripped.inputs.iter_mut() {
+        input.witness = Witness::new();
+    }
+    let mut superfluous = stripped.serialize();
+    superfluous.splice(4..4, [0x00, 0x01]);
+    let end = superfluous.len() - 4;
+    superfluous.splice(end..end, vec![0u8; stripped.inputs.len()]);
+    assert_eq!(
+        Transaction::deserialize(&superfluous).unwrap_err(),
+        "Superfluous witness flag"
+    );
+
+    let mut trailing = raw.clone();
+    trailing.push(0);
+    assert!(Transaction::deserialize(&trailing).is_err());
+    assert!(Transaction::deserialize(&raw[..raw.len() - 1]).is_err());
+
+    // CompactSize 必须使用最短编码
+    assert!(Decoder::new(&[0xfd, 0x10, 0x00]).compact_size().is_err());
+    let mut encoded = Vec::new();
+    write_compact_size(&mut encoded, 0x10000);
+    assert_eq!(encoded, [0xfe, 0x00, 0x00, 0x01, 0x00]);
+    assert_eq!(Decoder::new(&encoded).compact_size().unwrap(), 0x10000);
+}
@@ -0,0 +1,274 @@
+mod common;
+
+use common::{hex, unhex};
+use flipper_zero_wallet::bip32::DerivationPath;
+use flipper_zero_wallet::transaction::{txid_to_hex, OutPoint, Transaction, TxIn, TxOut};
+use flipper_zero_wallet::utxo::{parse, Utxo, MAX_UTXOS};
+
+const TXID: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+/// BIP84 第一个接收地址及其输出脚本
+const ADDRESS: &str = "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu";
+const SCRIPT: &str = "0014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2";
+
+fn amount_of(amount: &str) -> Result<u64, &'static str> {
+    let json = format!(
+        r#"[{{"txid":"{}","vout":0,"amount":{},"address":"{}"}}]"#,
+        TXID, amount, ADDRESS
+    );
+    parse(json.as_bytes()).map(|utxos| utxos[0].value)
+}
+
+#[test]
+fn bitcoin_core_listunspent() {
+    // listunspent 的字段，外加未知字段、嵌套数组与非 ASCII 标签
+    let json = format!(
+        r#"
+        [
+          {{
+            "txid": "{TXID}",
+            "vout": 0,
+            "address": "{ADDRESS}",
+            "label": "café \"é\"",
+            "scriptPubKey": "{SCRIPT}",
+            "amount": 0.00100000,
+            "confirmations": 6,
+            "spendable": true,
+            "solvable": true,
+            "desc": "wpkh([73c5da0a/84h/0h/0h/0/0]0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c)#7zgtp5ql",
+            "parent_descs": [],
+            "safe": true
+          }},
+          {{"txid": "{TXID}", "vout": 1, "value": 250000, "address": "{ADDRESS}", "path": "m/84'/0'/0'/1/7", "extra": {{"a": [1, {{"b": null}}]}}}}
+        ]"#
+    );
+    let utxos = parse(json.as_bytes()).unwrap();
+    assert_eq!(
+        utxos[0],
+        Utxo {
+            outpoint: OutPoint::new(unhex(TXID).try_into().unwrap(), 0),
+            value: 100_000,
+            script_pubkey: unhex(SCRIPT),
+            path: Some(DerivationPath::parse("m/84'/0'/0'/0/0").unwrap()),
+            verified: false,
+        }
+    );
+    assert_eq!(utxos[1].value, 250_000);
+    assert_eq!(utxos[1].script_pubkey, unhex(SCRIPT));
+    assert_eq!(
+        utxos[1].path,
+        Some(DerivationPath::parse("m/84'/0'/0'/1/7").unwrap())
+    );
+}
+
+#[test]
+fn csv() {
+    let csv = format!(
+        "\u{feff}# exported from a watch-only wallet\r\n\
+         txid,vout,amount,address,label\r\n\
+         \r\n\
+         {TXID},0,0.001,{ADDRESS},\"rent\"\r\n\
+         \"{TXID}\",\"1\",\"1.5\",\"{ADDRESS}\",\r\n"
+    );
+    let utxos = parse(csv.as_bytes()).unwrap();
+    assert_eq!(utxos.len(), 2);
+    assert_eq!(utxos[0].value, 100_000);
+    assert_eq!(utxos[1].value, 150_000_000);
+    assert_eq!(utxos[1].outpoint.vout, 1);
+
+    let short_row = format!("txid,vout,value,address\n{TXID},0,1000\n");
+    assert_eq!(
+        parse(short_row.as_bytes()).unwrap_err(),
+        "CSV column count mismatch"
+    );
+    assert_eq!(
+        parse(b"txid,vout,value,address\n").unwrap_err(),
+        "UTXO file is empty"
+    );
+}
+
+#[test]
+fn btc_amounts() {
+    assert_eq!(amount_of("1"), Ok(100_000_000));
+    assert_eq!(amount_of("0.00000001"), Ok(1));
+    assert_eq!(amount_of("21000000.00000000"), Ok(2_100_000_000_000_000));
+    assert_eq!(amount_of("\"0.12345678\""), Ok(12_345_678));
+    // 末尾的零不算小数位
+    assert_eq!(amount_of("0.1000000000"), Ok(10_000_000));
+    assert_eq!(amount_of("\".5\""), Ok(50_000_000));
+    assert_eq!(amount_of("\"5.\""), Ok(500_000_000));
+
+    // 超过 8 位小数、指数形式、负数与溢出
+    for amount in [
+        "0.000000001",
+        "1e-8",
+        "1E2",
+        "-1",
+        "\".\"",
+        "\"\"",
+        "\"1.2.3\"",
+        "184467440738",
+    ] {
+        assert_eq!(amount_of(amount), Err("Invalid UTXO amount"), "{}", amount);
+    }
+    assert_eq!(amount_of("0"), Err("Invalid UTXO value"));
+}
+
+#[test]
+fn rejects_duplicates() {
+    let json = format!(
+        r#"[{{"txid":"{TXID}","vout":3,"value":1000,"address":"{ADDRESS}"}},
+            {{"txid":"{TXID}","vout":3,"value":2000,"address":"{ADDRESS}"}}]"#
+    );
+    assert_eq!(parse(json.as_bytes()).unwrap_err(), "Duplicate UTXO");
+
+    let csv =
+        format!("txid,vout,value,address\n{TXID},3,1000,{ADDRESS}\n{TXID},3,1000,{ADDRESS}\n");
+    assert_eq!(parse(csv.as_bytes()).unwrap_err(), "Duplicate UTXO");
+}
+
+#[test]
+fn utxo_count_limit() {
+    let row = |vout: usize| {
+        format!(r#"{{"txid":"{TXID}","vout":{vout},"value":1000,"script_pubkey":"{SCRIPT}"}}"#)
+    };
+    let json = |count: usize| format!("[{}]", (0..count).map(row).collect::<Vec<_>>().join(","));
+    assert_eq!(parse(json(MAX_UTXOS).as_bytes()).unwrap().len(), MAX_UTXOS);
+    assert_eq!(
+        parse(json(MAX_UTXOS + 1).as_bytes()).unwrap_err(),
+        "Too many UTXOs"
+    );
+
+    let csv = |count: usize| {
+        (0..count).fold(
+            String::from("txid,vout,value,script_pubkey\n"),
+            |mut csv, vout| {
+                csv.push_str(&format!("{TXID},{vout},1000,{SCRIPT}\n"));
+                csv
+            },
+        )
+    };
+    assert_eq!(parse(csv(MAX_UTXOS).as_bytes()).unwrap().len(), MAX_UTXOS);
+    assert_eq!(
+        parse(csv(MAX_UTXOS + 1).as_bytes()).unwrap_err(),
+        "Too many UTXOs"
+    );
+}
+
+#[test]
+fn malformed_records() {
+    let cases = [
+        (
+            r#"[{"vout":0,"value":1000,"address":"ADDRESS"}]"#,
+            "UTXO txid missing",
+        ),
+        (
+            r#"[{"txid":"TXID","value":1000,"address":"ADDRESS"}]"#,
+            "UTXO vout missing",
+        ),
+        (
+            r#"[{"txid":"TXID","vout":-1,"value":1000,"address":"ADDRESS"}]"#,
+            "Invalid UTXO vout",
+        ),
+        (
+            r#"[{"txid":"TXID","vout":0,"address":"ADDRESS"}]"#,
+            "UTXO amount missing",
+        ),
+        (
+            r#"[{"txid":"TXID","vout":0,"value":1.5,"address":"ADDRESS"}]"#,
+            "Invalid UTXO value",
+        ),
+        (
+            r#"[{"txid":"TXID","vout":0,"value":1000}]"#,
+            "UTXO script missing",
+        ),
+        (
+            r#"[{"txid":"TXID","vout":0,"value":1000,"scriptPubKey":"0g"}]"#,
+            "Invalid UTXO script",
+        ),
+        (
+            r#"[{"txid":"TXID","vout":0,"value":1000,"address":"ADDRESS"}] x"#,
+            "Trailing data after JSON",
+        ),
+        (
+            r#"[{"txid":"TXID","vout":0,"value":1000,"address":"ADDRESS"}"#,
+            "Invalid JSON",
+        ),
+        (
+            r#"[{"txid":"TXID","vout":0,"value":1000,"address":"ADDRESS","x":[[[[[[[[[[1]]]]]]]]]]}]"#,
+            "JSON nested too deeply",
+        ),
+    ];
+    for (json, error) in cases {
+        let json = json.replace("TXID", TXID).replace("ADDRESS", ADDRESS);
+        assert_eq!(parse(json.as_bytes()).unwrap_err(), error, "{}", json);
+    }
+    assert_eq!(parse(b"[]").unwrap_err(), "UTXO file is empty");
+    assert_eq!(parse(b"\xff").unwrap_err(), "UTXO file is not UTF-8");
+}
+
+/// prev_tx 给出的前序交易决定金额与脚本
+#[test]
+fn previous_transaction() {
+    let prev = Transaction {
+        version: 2,
+        inputs: vec![TxIn::new(OutPoint::new([0x11; 32], 0), 0xffff_ffff)],
+        outputs: vec![
+            TxOut {
+                value: 70_000,
+                script_pubkey: unhex("76a914d986ed01b7a22225a70edbf2ba7cfb63a15cb3aa88ac"),
+            },
+            TxOut {
+                value: 30_000,
+                script_pubkey: unhex(SCRIPT),
+            },
+        ],
+        lock_time: 0,
+    };
+    let txid = txid_to_hex(&prev.txid());
+    let txid = txid.as_str();
+    let prev_tx = hex(&prev.serialize());
+
+    let json = format!(
+        r#"[{{"txid":"{txid}","vout":1,"prev_tx":"{prev_tx}"}},
+            {{"txid":"{txid}","vout":0,"amount":0.0007,"prev_tx":"{prev_tx}"}}]"#
+    );
+    let utxos = parse(json.as_bytes()).unwrap();
+    assert_eq!(utxos[0].value, 30_000);
+    assert_eq!(utxos[0].script_pubkey, unhex(SCRIPT));
+    assert!(utxos[0].verified);
+    assert_eq!(utxos[1].value, 70_000);
+    assert!(utxos[1].verified);
+
+    let cases = [
+        (
+            format!(r#"{{"txid":"{txid}","vout":1,"value":30001,"prev_tx":"{prev_tx}"}}"#),
+            "UTXO does not match previous transaction",
+        ),
+        (
+            format!(
+                r#"{{"txid":"{txid}","vout":1,"address":"1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA","prev_tx":"{prev_tx}"}}"#
+            ),
+            "UTXO does not match previous transaction",
+        ),
+        (
+            format!(r#"{{"txid":"{TXID}","vout":1,"prev_tx":"{prev_tx}"}}"#),
+            "Previous transaction does not match UTXO",
+        ),
+        (
+            format!(r#"{{"txid":"{txid}","vout":2,"prev_tx":"{prev_tx}"}}"#),
+            "UTXO vout out of range",
+        ),
+        (
+            format!(r#"{{"txid":"{txid}","vout":1,"prev_tx":"zz"}}"#),
+            "Invalid previous transaction",
+        ),
+    ];
+    for (record, error) in cases {
+        assert_eq!(
+            parse(format!("[{}]", record).as_bytes()).unwrap_err(),
+            error,
+            "{}",
+            record
+        );
+    }
+}
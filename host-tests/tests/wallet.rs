@@ -0,0 +1,93 @@
+use flipper_zero_wallet::address::{AddressType, Cryptocurrency};
+use flipper_zero_wallet::bip32::DerivationPath;
+use flipper_zero_wallet::flipper_wallet_core::Wallet;
+
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+/// BIP84 m/84'/0'/0'/0/0 与 m/84'/0'/0'/1/0
+const RECEIVE_0: &str = "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu";
+const CHANGE_0: &str = "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el";
+
+fn wallet() -> Wallet {
+    Wallet::from_mnemonic(MNEMONIC, "").unwrap()
+}
+
+fn path(s: &str) -> DerivationPath {
+    DerivationPath::parse(s).unwrap()
+}
+
+/// 账户按完整派生路径去重：账户索引或链不同的地址都会单独添加
+#[test]
+fn accounts_are_keyed_by_full_path() {
+    let mut wallet = wallet();
+    let btc = Cryptocurrency::Bitcoin;
+    wallet.add_account(btc, AddressType::P2wpkh, 0, 0).unwrap();
+    wallet.add_account(btc, AddressType::P2wpkh, 0, 0).unwrap();
+    assert_eq!(wallet.account_count(), 1);
+
+    // 账户 1 的第 0 个地址与账户 0 的第 0 个地址不同
+    wallet.add_account(btc, AddressType::P2wpkh, 1, 0).unwrap();
+    assert_eq!(wallet.account_count(), 2);
+    let first = wallet.get_account(0).unwrap();
+    let second = wallet.get_account(1).unwrap();
+    assert_eq!(first.address.as_str(), RECEIVE_0);
+    assert_eq!(first.derivation_path, path("m/84'/0'/0'/0/0"));
+    assert_eq!(second.derivation_path, path("m/84'/0'/1'/0/0"));
+    assert_ne!(second.address.as_str(), RECEIVE_0);
+
+    // 同一路径通过 add_account_at_path 添加时同样去重，找零链则是新账户
+    wallet
+        .add_account_at_path(btc, AddressType::P2wpkh, path("m/84h/0h/0h/0/0"))
+        .unwrap();
+    assert_eq!(wallet.account_count(), 2);
+    wallet
+        .add_account_at_path(btc, AddressType::P2wpkh, path("m/84'/0'/0'/1/0"))
+        .unwrap();
+    assert_eq!(wallet.account_count(), 3);
+    assert_eq!(wallet.get_account(2).unwrap().address.as_str(), CHANGE_0);
+
+    // 地址类型不同的同一路径也是新账户
+    wallet
+        .add_account_at_path(btc, AddressType::P2pkh, path("m/84'/0'/0'/0/0"))
+        .unwrap();
+    assert_eq!(wallet.account_count(), 4);
+}
+
+/// get_address 只返回路径完全相同的已添加账户
+#[test]
+fn get_address_matches_full_path() {
+    let mut wallet = wallet();
+    let btc = Cryptocurrency::Bitcoin;
+    let account_1 = wallet
+        .get_address(btc, AddressType::P2wpkh, 1, 0)
+        .unwrap()
+        .as_str()
+        .to_string();
+
+    // 已添加的找零地址与账户 1 的地址都不会被当作账户 0 的接收地址
+    wallet
+        .add_account_at_path(btc, AddressType::P2wpkh, path("m/84'/0'/0'/1/0"))
+        .unwrap();
+    wallet.add_account(btc, AddressType::P2wpkh, 1, 0).unwrap();
+    assert_eq!(
+        wallet
+            .get_address(btc, AddressType::P2wpkh, 0, 0)
+            .unwrap()
+            .as_str(),
+        RECEIVE_0
+    );
+    assert_eq!(
+        wallet
+            .get_address(btc, AddressType::P2wpkh, 1, 0)
+            .unwrap()
+            .as_str(),
+        account_1
+    );
+    assert_eq!(
+        wallet
+            .get_change_address(btc, AddressType::P2wpkh, 0, 0)
+            .unwrap()
+            .as_str(),
+        CHANGE_0
+    );
+}
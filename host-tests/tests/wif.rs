@@ -0,0 +1,192 @@
+mod common;
+
+use common::{hex, unhex};
+use flipper_zero_wallet::address::{AddressType, Cryptocurrency};
+use flipper_zero_wallet::base58;
+use flipper_zero_wallet::flipper_wallet_core::Wallet;
+use flipper_zero_wallet::network::Network;
+use flipper_zero_wallet::secp256k1::SecretKey;
+use flipper_zero_wallet::wif;
+
+/// Bitcoin Wiki "Wallet import format" 中的示例私钥
+const KEY: &str = "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d";
+const UNCOMPRESSED: &str = "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ";
+const COMPRESSED: &str = "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617";
+
+fn secret_key() -> SecretKey {
+    SecretKey::from_bytes(&unhex(KEY).try_into().unwrap()).unwrap()
+}
+
+/// 重新计算 Base58Check，得到任意版本字节与 payload 的 WIF
+fn wif_with(version: u8, key: &[u8], flag: Option<u8>) -> String {
+    let mut payload = vec![version];
+    payload.extend_from_slice(key);
+    payload.extend(flag);
+    String::from_utf8(base58::check_encode(&payload, &base58::BITCOIN)).unwrap()
+}
+
+#[test]
+fn bitcoin_wiki_vectors() {
+    let key = secret_key();
+    for (wif_str, compressed) in [(UNCOMPRESSED, false), (COMPRESSED, true)] {
+        let encoded = wif::encode(&key, Cryptocurrency::Bitcoin, Network::Mainnet, compressed);
+        assert_eq!(encoded.unwrap().as_str(), wif_str);
+
+        let decoded = wif::decode(wif_str).unwrap();
+        assert_eq!(hex(decoded.secret_key.as_bytes()), KEY);
+        assert_eq!(decoded.compressed, compressed);
+        assert_eq!(decoded.cryptocurrency, Cryptocurrency::Bitcoin);
+        assert_eq!(decoded.network, Network::Mainnet);
+    }
+
+    // 首尾空白被忽略
+    assert!(wif::decode(&format!(" {}\n", COMPRESSED)).is_ok());
+}
+
+/// 各币种的版本字节；版本字节相同时解码为 ALL 中靠前的币种
+#[test]
+fn per_coin_version_bytes() {
+    let key = secret_key();
+    let vectors = [
+        (
+            Cryptocurrency::Bitcoin,
+            Network::Mainnet,
+            0x80,
+            Cryptocurrency::Bitcoin,
+        ),
+        (
+            Cryptocurrency::Ravencoin,
+            Network::Mainnet,
+            0x80,
+            Cryptocurrency::Bitcoin,
+        ),
+        (
+            Cryptocurrency::Litecoin,
+            Network::Mainnet,
+            0xb0,
+            Cryptocurrency::Litecoin,
+        ),
+        (
+            Cryptocurrency::Dogecoin,
+            Network::Mainnet,
+            0x9e,
+            Cryptocurrency::Dogecoin,
+        ),
+        (
+            Cryptocurrency::Dash,
+            Network::Mainnet,
+            0xcc,
+            Cryptocurrency::Dash,
+        ),
+        (
+            Cryptocurrency::Bitcoin,
+            Network::Testnet3,
+            0xef,
+            Cryptocurrency::Bitcoin,
+        ),
+        (
+            Cryptocurrency::Litecoin,
+            Network::Testnet3,
+            0xef,
+            Cryptocurrency::Bitcoin,
+        ),
+        (
+            Cryptocurrency::Dogecoin,
+            Network::Testnet3,
+            0xf1,
+            Cryptocurrency::Dogecoin,
+        ),
+    ];
+    for (cryptocurrency, network, version, decoded_as) in vectors {
+        for compressed in [false, true] {
+            let encoded = wif::encode(&key, cryptocurrency, network, compressed).unwrap();
+            let flag = compressed.then_some(0x01);
+            assert_eq!(encoded.as_str(), wif_with(version, &unhex(KEY), flag));
+
+            let decoded = wif::decode(encoded.as_str()).unwrap();
+            assert_eq!(decoded.secret_key.as_bytes(), key.as_bytes());
+            assert_eq!(decoded.compressed, compressed);
+            assert_eq!(decoded.cryptocurrency, decoded_as, "{}", encoded.as_str());
+            assert_eq!(decoded.network, network);
+        }
+    }
+
+    for cryptocurrency in [Cryptocurrency::Ethereum, Cryptocurrency::Ripple] {
+        assert_eq!(
+            wif::encode(&key, cryptocurrency, Network::Mainnet, true).unwrap_err(),
+            "WIF not supported for this cryptocurrency"
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_wif() {
+    // 任意一个字符改变后校验和失败
+    for (i, c) in COMPRESSED.char_indices() {
+        let replacement = if c == '2' { "3" } else { "2" };
+        let mut corrupted = COMPRESSED.to_string();
+        corrupted.replace_range(i..i + 1, replacement);
+        assert!(wif::decode(&corrupted).is_err(), "{}", corrupted);
+    }
+
+    let key = unhex(KEY);
+    assert_eq!(
+        wif::decode(&wif_with(0x80, &key, Some(0x02))).err(),
+        Some("Invalid WIF length")
+    );
+    assert_eq!(
+        wif::decode(&wif_with(0x80, &key[..31], None)).err(),
+        Some("Invalid WIF length")
+    );
+    assert_eq!(
+        wif::decode(&wif_with(0x81, &key, Some(0x01))).err(),
+        Some("Unknown WIF version")
+    );
+
+    // 私钥为零或不小于曲线阶
+    assert!(wif::decode(&wif_with(0x80, &[0u8; 32], Some(0x01))).is_err());
+    let order = unhex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+    assert!(wif::decode(&wif_with(0x80, &order, Some(0x01))).is_err());
+}
+
+/// 导入 WIF 得到单私钥钱包，地址取决于压缩标志
+#[test]
+fn wallet_import() {
+    let wallet = Wallet::from_wif(UNCOMPRESSED).unwrap();
+    assert!(wallet.is_single_key());
+    assert_eq!(wallet.network(), Network::Mainnet);
+    assert_eq!(
+        wallet.export_wif(Cryptocurrency::Bitcoin).unwrap().as_str(),
+        UNCOMPRESSED
+    );
+    let address = wallet
+        .get_address(Cryptocurrency::Bitcoin, AddressType::P2pkh, 0, 0)
+        .unwrap();
+    assert_eq!(address.as_str(), "1GAehh7TsJAHuUAeKZcXf5CnwuGuGgyX2S");
+    // 未压缩公钥不能用于隔离见证
+    assert!(wallet
+        .get_address(Cryptocurrency::Bitcoin, AddressType::P2wpkh, 0, 0)
+        .is_err());
+
+    let wallet = Wallet::from_wif(COMPRESSED).unwrap();
+    assert_eq!(
+        wallet.export_wif(Cryptocurrency::Bitcoin).unwrap().as_str(),
+        COMPRESSED
+    );
+    let address = wallet
+        .get_address(Cryptocurrency::Bitcoin, AddressType::P2pkh, 0, 0)
+        .unwrap();
+    assert_eq!(address.as_str(), "1LoVGDgRs9hTfTNJNuXKSpywcbdvwRXpmK");
+
+    let testnet = wif::encode(
+        &secret_key(),
+        Cryptocurrency::Bitcoin,
+        Network::Testnet3,
+        true,
+    )
+    .unwrap();
+    let wallet = Wallet::from_wif(testnet.as_str()).unwrap();
+    assert_eq!(wallet.network(), Network::Testnet3);
+
+    assert!(Wallet::from_wif("not a wif").is_err());
+}
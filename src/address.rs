@@ -5,6 +5,9 @@ use crate::sha256::Sha256;
 use crate::secp256k1::PublicKey;
 use crate::ripemd160::Ripemd160;
 use crate::keccak256::Keccak256;
+use crate::base58;
+use crate::bech32;
+use crate::network::Network;
 
 /// 支持的加密货币类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +22,17 @@ pub enum Cryptocurrency {
 }
 
 impl Cryptocurrency {
+    /// 所有支持的币种
+    pub const ALL: [Cryptocurrency; 7] = [
+        Cryptocurrency::Bitcoin,
+        Cryptocurrency::Ethereum,
+        Cryptocurrency::Dogecoin,
+        Cryptocurrency::Litecoin,
+        Cryptocurrency::Dash,
+        Cryptocurrency::Ravencoin,
+        Cryptocurrency::Ripple,
+    ];
+
     /// 获取币种的 BIP44 币种索引
     pub fn coin_type(&self) -> u32 {
         match self {
@@ -69,102 +83,183 @@ impl Cryptocurrency {
             Cryptocurrency::Ripple => 0x00, // XRP 使用特殊编码
         }
     }
-}
 
-/// RIPEMD-160 哈希函数
-fn ripemd160(data: &[u8]) -> [u8; 20] {
-    Ripemd160::digest(data)
-}
+    /// P2SH（脚本哈希）地址的版本字节
+    pub fn script_hash_prefix(&self) -> u8 {
+        match self {
+            Cryptocurrency::Bitcoin => 0x05,   // 3...
+            Cryptocurrency::Ethereum => 0x00,  // ETH 不使用版本字节
+            Cryptocurrency::Dogecoin => 0x16,  // 9... / A...
+            Cryptocurrency::Litecoin => 0x32,  // M...
+            Cryptocurrency::Dash => 0x10,      // 7...
+            Cryptocurrency::Ravencoin => 0x7A, // r...
+            Cryptocurrency::Ripple => 0x00,    // XRP 没有脚本哈希地址
+        }
+    }
 
-/// Ripple 专用的 Base58 字母表
-const RIPPLE_B58_DIGITS: &[u8; 58] = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+    /// SegWit 地址的 bech32 前缀（不支持 SegWit 的币种返回 None）
+    pub fn bech32_hrp(&self) -> Option<&'static str> {
+        match self {
+            Cryptocurrency::Bitcoin => Some("bc"),
+            Cryptocurrency::Litecoin => Some("ltc"),
+            _ => None,
+        }
+    }
 
-/// 标准 Base58 字母表
-const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    /// 是否支持指定网络
+    ///
+    /// 比特币支持全部网络；其他比特币系币种只支持主网和各自的测试网（Testnet3），
+    /// 以太坊与瑞波只支持主网。
+    pub fn supports_network(&self, network: Network) -> bool {
+        match self {
+            Cryptocurrency::Bitcoin => true,
+            Cryptocurrency::Ethereum | Cryptocurrency::Ripple => network.is_mainnet(),
+            _ => matches!(network, Network::Mainnet | Network::Testnet3),
+        }
+    }
 
-/// Base58 编码（使用指定的字母表）
-fn base58_encode_with_alphabet(data: &[u8], alphabet: &[u8; 58]) -> Vec<u8> {
-    
-    // 计算前导零的数量
-    let mut leading_zeros = 0;
-    for &byte in data {
-        if byte == 0 {
-            leading_zeros += 1;
+    /// 指定网络上的币种索引（SLIP-44：所有测试网络统一使用 1）
+    pub fn coin_type_for(&self, network: Network) -> u32 {
+        if network.is_mainnet() {
+            self.coin_type()
         } else {
-            break;
+            1
         }
     }
-    
-    // 将数据转换为大数（使用 Vec<u8> 表示）
-    let mut num = Vec::new();
-    num.extend_from_slice(data);
-    
-    let mut result = Vec::new();
-    
-    // 重复除以 58
-    while !num.is_empty() {
-        let mut remainder = 0u16;
-        let mut new_num = Vec::new();
-        let mut started = false;
-        
-        for &byte in &num {
-            remainder = (remainder << 8) | (byte as u16);
-            if remainder >= 58 {
-                started = true;
-                new_num.push((remainder / 58) as u8);
-                remainder %= 58;
-            } else if started {
-                new_num.push(0);
-            }
+
+    /// 指定网络上的 P2PKH 版本字节
+    pub fn address_prefix_for(&self, network: Network) -> u8 {
+        if network.is_mainnet() {
+            return self.address_prefix();
+        }
+        match self {
+            Cryptocurrency::Dogecoin => 0x71, // n...
+            Cryptocurrency::Dash => 0x8C,     // y...
+            _ => 0x6F,                        // m... / n...
         }
-        
-        result.push(alphabet[remainder as usize]);
-        num = new_num;
     }
-    
-    // 添加前导 '1'
-    for _ in 0..leading_zeros {
-        result.push(b'1');
+
+    /// 指定网络上的 P2SH 版本字节
+    pub fn script_hash_prefix_for(&self, network: Network) -> u8 {
+        if network.is_mainnet() {
+            return self.script_hash_prefix();
+        }
+        match self {
+            Cryptocurrency::Litecoin => 0x3A, // Q...
+            Cryptocurrency::Dash => 0x13,     // 8... / 9...
+            _ => 0xC4,                        // 2...
+        }
+    }
+
+    /// 指定网络上 WIF 私钥的版本字节（没有 WIF 格式的币种返回 None）
+    pub fn wif_prefix_for(&self, network: Network) -> Option<u8> {
+        if !self.supports_network(network) {
+            return None;
+        }
+        if network.is_mainnet() {
+            return match self {
+                Cryptocurrency::Bitcoin | Cryptocurrency::Ravencoin => Some(0x80),
+                Cryptocurrency::Litecoin => Some(0xB0),
+                Cryptocurrency::Dogecoin => Some(0x9E),
+                Cryptocurrency::Dash => Some(0xCC),
+                Cryptocurrency::Ethereum | Cryptocurrency::Ripple => None,
+            };
+        }
+        match self {
+            Cryptocurrency::Dogecoin => Some(0xF1),
+            _ => Some(0xEF),
+        }
+    }
+
+    /// 指定网络上的 bech32 前缀
+    pub fn bech32_hrp_for(&self, network: Network) -> Option<&'static str> {
+        if network.is_mainnet() {
+            return self.bech32_hrp();
+        }
+        match (self, network) {
+            (Cryptocurrency::Bitcoin, Network::Regtest) => Some("bcrt"),
+            (Cryptocurrency::Bitcoin, _) => Some("tb"),
+            (Cryptocurrency::Litecoin, _) => Some("tltc"),
+            _ => None,
+        }
+    }
+
+    /// 是否支持指定的地址类型
+    pub fn supports_address_type(&self, address_type: AddressType) -> bool {
+        match address_type {
+            AddressType::P2pkh => true,
+            // 隔离见证地址只在已激活 SegWit 的币种上可用
+            AddressType::P2shP2wpkh | AddressType::P2wpkh | AddressType::P2tr => {
+                self.bech32_hrp().is_some()
+            }
+        }
     }
-    
-    result.reverse();
-    result
 }
 
-/// Base58 编码（使用标准字母表）
-fn base58_encode(data: &[u8]) -> Vec<u8> {
-    base58_encode_with_alphabet(data, BASE58_ALPHABET)
+/// 地址类型（决定脚本类型和 BIP 派生用途）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    /// 传统 P2PKH 地址（BIP44，m/44'）
+    P2pkh,
+    /// 嵌套隔离见证 P2SH-P2WPKH 地址（BIP49，m/49'）
+    P2shP2wpkh,
+    /// 原生隔离见证 P2WPKH 地址（BIP84，m/84'，bech32）
+    P2wpkh,
+    /// Taproot 单密钥 P2TR 地址（BIP86，m/86'，bech32m）
+    P2tr,
 }
 
-/// Ripple Base58 编码（使用 Ripple 专用字母表）
-fn ripple_base58_encode(data: &[u8]) -> Vec<u8> {
-    base58_encode_with_alphabet(data, RIPPLE_B58_DIGITS)
+impl AddressType {
+    /// 派生路径的 purpose 字段
+    pub fn purpose(&self) -> u32 {
+        match self {
+            AddressType::P2pkh => 44,
+            AddressType::P2shP2wpkh => 49,
+            AddressType::P2wpkh => 84,
+            AddressType::P2tr => 86,
+        }
+    }
+
+    /// 获取地址类型的显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AddressType::P2pkh => "Legacy",
+            AddressType::P2shP2wpkh => "Nested SegWit",
+            AddressType::P2wpkh => "Native SegWit",
+            AddressType::P2tr => "Taproot",
+        }
+    }
 }
 
-/// Base58Check 编码（带校验和，使用标准字母表）
-fn base58check_encode(payload: &[u8]) -> Vec<u8> {
-    let hash1 = Sha256::digest(payload);
-    let hash2 = Sha256::digest(&hash1);
-    let checksum = &hash2[..4];
-    
-    // 组合 payload 和校验和
-    let mut data = Vec::with_capacity(payload.len() + 4);
-    data.extend_from_slice(payload);
-    data.extend_from_slice(checksum);
-    
-    base58_encode(&data)
+/// RIPEMD-160 哈希函数
+fn ripemd160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(data)
+}
+
+/// HASH160 = RIPEMD-160(SHA-256(data))，用于地址和 BIP32 密钥标识符
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    ripemd160(&Sha256::digest(data))
 }
 
 /// 生成 Bitcoin 类型地址（Base58Check）
 pub fn generate_bitcoin_address(public_key: &PublicKey, prefix: u8) -> Result<Vec<u8>, &'static str> {
-    // 压缩公钥
-    let compressed_pubkey = public_key.serialize_compressed();
-    
-    // SHA-256
-    let sha256_hash = Sha256::digest(&compressed_pubkey);
-    
-    // RIPEMD-160
-    let ripemd160_hash = ripemd160(&sha256_hash);
+    generate_legacy_address(public_key, prefix, true)
+}
+
+/// 生成 P2PKH 地址，可选择对压缩或未压缩公钥做哈希
+///
+/// 旧的纸钱包和未压缩 WIF 私钥对应的地址由 65 字节未压缩公钥得到。
+pub fn generate_legacy_address(
+    public_key: &PublicKey,
+    prefix: u8,
+    compressed: bool,
+) -> Result<Vec<u8>, &'static str> {
+    // RIPEMD-160(SHA-256(公钥))
+    let ripemd160_hash = if compressed {
+        hash160(&public_key.serialize_compressed())
+    } else {
+        hash160(&public_key.serialize_uncompressed())
+    };
     
     // 添加版本字节
     let mut versioned = Vec::with_capacity(21);
@@ -172,29 +267,145 @@ pub fn generate_bitcoin_address(public_key: &PublicKey, prefix: u8) -> Result<Ve
     versioned.extend_from_slice(&ripemd160_hash);
     
     // Base58Check 编码
-    Ok(base58check_encode(&versioned))
+    Ok(base58::check_encode(&versioned, &base58::BITCOIN))
 }
 
-/// 生成 Ethereum 地址
-pub fn generate_ethereum_address(public_key: &PublicKey) -> Result<Vec<u8>, &'static str> {
+/// 生成嵌套隔离见证 P2SH-P2WPKH 地址（Base58Check）
+///
+/// 赎回脚本为 OP_0 <20 字节公钥哈希>，地址为其 HASH160 加上脚本哈希版本字节。
+pub fn generate_p2sh_p2wpkh_address(public_key: &PublicKey, prefix: u8) -> Result<Vec<u8>, &'static str> {
+    let mut redeem_script = [0u8; 22];
+    redeem_script[0] = 0x00; // OP_0
+    redeem_script[1] = 0x14; // 推入 20 字节
+    redeem_script[2..].copy_from_slice(&hash160(&public_key.serialize_compressed()));
+
+    let mut versioned = Vec::with_capacity(21);
+    versioned.push(prefix);
+    versioned.extend_from_slice(&hash160(&redeem_script));
+
+    Ok(base58::check_encode(&versioned, &base58::BITCOIN))
+}
+
+/// 生成原生隔离见证 P2WPKH 地址（bech32，见证版本 0）
+pub fn generate_p2wpkh_address(public_key: &PublicKey, hrp: &str) -> Result<Vec<u8>, &'static str> {
+    // 见证程序为压缩公钥的 HASH160
+    let program = hash160(&public_key.serialize_compressed());
+    bech32::encode_segwit_address(hrp, 0, &program)
+}
+
+/// 生成 Taproot P2TR 地址（bech32m，见证版本 1）
+///
+/// 输出密钥为内部 x-only 公钥按 BIP341 调整后的结果（BIP86：无脚本路径）。
+pub fn generate_p2tr_address(public_key: &PublicKey, hrp: &str) -> Result<Vec<u8>, &'static str> {
+    let (internal_key, _) = public_key.x_only_public_key();
+    let (output_key, _) = internal_key.tap_tweak(None)?;
+    bech32::encode_segwit_address(hrp, 1, output_key.as_bytes())
+}
+
+/// 计算 Ethereum 地址的 20 字节（未压缩公钥去掉前缀后 Keccak-256 的后 20 字节）
+pub fn ethereum_address_bytes(public_key: &PublicKey) -> [u8; 20] {
     let uncompressed = public_key.serialize_uncompressed();
-    let pubkey_no_prefix = &uncompressed[1..65];
-    
-    // Keccak-256 哈希
-    let hash = Keccak256::digest(pubkey_no_prefix);
-    
-    // 取后 20 字节作为地址
-    let mut address = Vec::with_capacity(42); // "0x" + 40 hex chars
-    address.extend_from_slice(b"0x");
-    
-    // 转换为十六进制
+    let hash = Keccak256::digest(&uncompressed[1..65]);
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..32]);
+    address
+}
+
+/// 将 u64 格式化为十进制 ASCII，返回写入的字节数
+fn write_decimal(mut value: u64, out: &mut [u8; 20]) -> usize {
+    let mut digits = [0u8; 20];
+    let mut len = 0;
+    loop {
+        digits[len] = b'0' + (value % 10) as u8;
+        len += 1;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    for i in 0..len {
+        out[i] = digits[len - 1 - i];
+    }
+    len
+}
+
+/// 带校验大小写的 Ethereum 地址（"0x" + 40 个十六进制字符）
+///
+/// chain_id 为 None 时使用 EIP-55：对小写十六进制做 Keccak-256，哈希对应半字节 >= 8
+/// 的字母改为大写。给定 chain_id 时使用 EIP-1191（RSK 等网络），哈希输入前加上
+/// 十进制链 ID 和 "0x"，因此不同链上的校验大小写互不兼容。
+pub fn ethereum_checksum_address(address: &[u8; 20], chain_id: Option<u64>) -> Vec<u8> {
     const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
-    for &byte in &hash[12..32] {
-        address.push(HEX_CHARS[(byte >> 4) as usize]);
-        address.push(HEX_CHARS[(byte & 0x0f) as usize]);
+
+    let mut lower = [0u8; 40];
+    for (i, &byte) in address.iter().enumerate() {
+        lower[i * 2] = HEX_CHARS[(byte >> 4) as usize];
+        lower[i * 2 + 1] = HEX_CHARS[(byte & 0x0f) as usize];
     }
-    
-    Ok(address)
+
+    let mut hasher = Keccak256::new();
+    if let Some(chain_id) = chain_id {
+        let mut decimal = [0u8; 20];
+        let len = write_decimal(chain_id, &mut decimal);
+        hasher.update(&decimal[..len]);
+        hasher.update(b"0x");
+    }
+    hasher.update(&lower);
+    let hash = hasher.finalize();
+
+    let mut result = Vec::with_capacity(42);
+    result.extend_from_slice(b"0x");
+    for (i, &c) in lower.iter().enumerate() {
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+        if c.is_ascii_alphabetic() && nibble >= 8 {
+            result.push(c.to_ascii_uppercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// 校验 Ethereum 地址字符串并返回 20 字节地址
+///
+/// 全小写或全大写的地址不带校验信息，按 EIP-55 直接接受；大小写混合的地址必须
+/// 与 EIP-55（或给定 chain_id 时的 EIP-1191）校验大小写完全一致，否则拒绝。
+pub fn validate_ethereum_address(address: &str, chain_id: Option<u64>) -> Result<[u8; 20], &'static str> {
+    let hex_part = address
+        .strip_prefix("0x")
+        .ok_or("Ethereum address must start with 0x")?;
+    if hex_part.len() != 40 {
+        return Err("Invalid Ethereum address length");
+    }
+
+    let mut bytes = [0u8; 20];
+    for (i, pair) in hex_part.as_bytes().chunks(2).enumerate() {
+        let high = (pair[0] as char).to_digit(16).ok_or("Invalid hex character")?;
+        let low = (pair[1] as char).to_digit(16).ok_or("Invalid hex character")?;
+        bytes[i] = ((high << 4) | low) as u8;
+    }
+
+    let has_lower = hex_part.bytes().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex_part.bytes().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper && ethereum_checksum_address(&bytes, chain_id) != address.as_bytes() {
+        return Err("Invalid Ethereum address checksum");
+    }
+
+    Ok(bytes)
+}
+
+/// 生成 Ethereum 地址（EIP-55 校验大小写）
+pub fn generate_ethereum_address(public_key: &PublicKey) -> Result<Vec<u8>, &'static str> {
+    generate_ethereum_address_with_chain_id(public_key, None)
+}
+
+/// 生成 Ethereum 地址，给定 chain_id 时使用 EIP-1191 链相关的校验大小写
+pub fn generate_ethereum_address_with_chain_id(
+    public_key: &PublicKey,
+    chain_id: Option<u64>,
+) -> Result<Vec<u8>, &'static str> {
+    Ok(ethereum_checksum_address(&ethereum_address_bytes(public_key), chain_id))
 }
 
 /// 生成标准加密货币地址
@@ -202,23 +413,12 @@ pub fn generate_address(public_key: &PublicKey, cryptocurrency: Cryptocurrency)
     match cryptocurrency {
         Cryptocurrency::Ethereum => generate_ethereum_address(public_key),
         Cryptocurrency::Ripple => {
-            // Ripple 使用特殊的 Base58 编码和字母表
-            let compressed_pubkey = public_key.serialize_compressed();
-            let sha256_hash = Sha256::digest(&compressed_pubkey);
-            let ripemd160_hash = ripemd160(&sha256_hash);
-            
-            // 计算校验和（双重 SHA-256 的前 4 字节）
-            let hash1 = Sha256::digest(&ripemd160_hash);
-            let hash2 = Sha256::digest(&hash1);
-            let checksum = &hash2[..4];
-            
-            // 组合 payload 和校验和
-            let mut data = Vec::with_capacity(24);
-            data.push(0x00); // Ripple 版本字节
-            data.extend_from_slice(&ripemd160_hash);
-            data.extend_from_slice(checksum);
-            
-            Ok(ripple_base58_encode(&data))
+            // Ripple 使用 Base58Check，但字母表不同
+            let mut versioned = [0u8; 21];
+            versioned[0] = 0x00; // Ripple 版本字节
+            versioned[1..].copy_from_slice(&hash160(&public_key.serialize_compressed()));
+
+            Ok(base58::check_encode(&versioned, &base58::RIPPLE))
         }
         _ => {
             // 其他币种使用标准 Base58Check
@@ -227,3 +427,221 @@ pub fn generate_address(public_key: &PublicKey, cryptocurrency: Cryptocurrency)
     }
 }
 
+/// 按地址类型生成主网地址（P2PKH 与 generate_address 相同）
+pub fn generate_address_with_type(
+    public_key: &PublicKey,
+    cryptocurrency: Cryptocurrency,
+    address_type: AddressType,
+) -> Result<Vec<u8>, &'static str> {
+    generate_address_for_network(public_key, cryptocurrency, address_type, Network::Mainnet)
+}
+
+/// 生成未压缩公钥对应的地址（只支持 P2PKH；Ethereum 地址与压缩无关）
+pub fn generate_uncompressed_address(
+    public_key: &PublicKey,
+    cryptocurrency: Cryptocurrency,
+    network: Network,
+) -> Result<Vec<u8>, &'static str> {
+    if !cryptocurrency.supports_network(network) {
+        return Err("Network not supported for this cryptocurrency");
+    }
+    match cryptocurrency {
+        Cryptocurrency::Ethereum => generate_ethereum_address(public_key),
+        Cryptocurrency::Ripple => Err("Uncompressed keys not supported for this cryptocurrency"),
+        _ => generate_legacy_address(public_key, cryptocurrency.address_prefix_for(network), false),
+    }
+}
+
+/// 按地址类型生成指定网络上的地址
+pub fn generate_address_for_network(
+    public_key: &PublicKey,
+    cryptocurrency: Cryptocurrency,
+    address_type: AddressType,
+    network: Network,
+) -> Result<Vec<u8>, &'static str> {
+    if !cryptocurrency.supports_address_type(address_type) {
+        return Err("Address type not supported for this cryptocurrency");
+    }
+    if !cryptocurrency.supports_network(network) {
+        return Err("Network not supported for this cryptocurrency");
+    }
+    if network.is_mainnet() && address_type == AddressType::P2pkh {
+        return generate_address(public_key, cryptocurrency);
+    }
+
+    match address_type {
+        AddressType::P2pkh => {
+            generate_bitcoin_address(public_key, cryptocurrency.address_prefix_for(network))
+        }
+        AddressType::P2shP2wpkh => generate_p2sh_p2wpkh_address(
+            public_key,
+            cryptocurrency.script_hash_prefix_for(network),
+        ),
+        AddressType::P2wpkh | AddressType::P2tr => {
+            let hrp = cryptocurrency
+                .bech32_hrp_for(network)
+                .ok_or("Address type not supported for this cryptocurrency")?;
+            if address_type == AddressType::P2wpkh {
+                generate_p2wpkh_address(public_key, hrp)
+            } else {
+                generate_p2tr_address(public_key, hrp)
+            }
+        }
+    }
+}
+
+/// 解析出的地址种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    /// 公钥哈希（Base58Check P2PKH，或 Ripple 账户 ID）
+    P2pkh,
+    /// 脚本哈希（P2SH，包括嵌套隔离见证）
+    P2sh,
+    /// 见证版本 0，20 字节公钥哈希
+    P2wpkh,
+    /// 见证版本 0，32 字节脚本哈希
+    P2wsh,
+    /// 见证版本 1，32 字节 x-only 输出密钥
+    P2tr,
+    /// 尚未定义的见证版本（2 到 16 或非标准长度的版本 1）
+    WitnessUnknown(u8),
+    /// 20 字节 Ethereum 地址
+    Ethereum,
+}
+
+/// parse_address 的解析结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedAddress {
+    pub cryptocurrency: Cryptocurrency,
+    pub network: Network,
+    pub kind: AddressKind,
+    /// 公钥/脚本哈希、见证程序或 Ethereum 地址字节
+    pub payload: Vec<u8>,
+}
+
+impl ParsedAddress {
+    /// 对应的本钱包地址类型（P2SH 无法确定是否为嵌套隔离见证，返回 None）
+    pub fn address_type(&self) -> Option<AddressType> {
+        match self.kind {
+            AddressKind::P2pkh | AddressKind::Ethereum => Some(AddressType::P2pkh),
+            AddressKind::P2wpkh => Some(AddressType::P2wpkh),
+            AddressKind::P2tr => Some(AddressType::P2tr),
+            AddressKind::P2sh | AddressKind::P2wsh | AddressKind::WitnessUnknown(_) => None,
+        }
+    }
+}
+
+/// 解析并校验地址字符串，识别币种、网络和地址种类
+///
+/// 依次尝试 Ethereum（EIP-55）、bech32/bech32m、Base58Check 和 Ripple Base58，
+/// 所有校验和都必须正确。测试网版本字节在多个币种间重复时优先识别为比特币，
+/// 测试网络之间也无法区分（tb1 与 m/n/2 开头的地址统一报告为 Testnet3）。
+pub fn parse_address(address: &str) -> Result<ParsedAddress, &'static str> {
+    let address = address.trim();
+    if address.is_empty() {
+        return Err("Empty address");
+    }
+
+    if address.starts_with("0x") {
+        let bytes = validate_ethereum_address(address, None)?;
+        return Ok(ParsedAddress {
+            cryptocurrency: Cryptocurrency::Ethereum,
+            network: Network::Mainnet,
+            kind: AddressKind::Ethereum,
+            payload: bytes.to_vec(),
+        });
+    }
+
+    if let Some(parsed) = parse_segwit_address(address)? {
+        return Ok(parsed);
+    }
+
+    parse_base58_address(address)
+}
+
+/// 解析 bech32/bech32m 隔离见证地址；HRP 不属于任何已知币种时返回 None
+fn parse_segwit_address(address: &str) -> Result<Option<ParsedAddress>, &'static str> {
+    let hrp = match bech32::decode(address) {
+        Ok((hrp, _, _)) => hrp,
+        Err(_) => return Ok(None),
+    };
+
+    for cryptocurrency in Cryptocurrency::ALL {
+        for network in [Network::Mainnet, Network::Testnet3, Network::Regtest] {
+            if !cryptocurrency.supports_network(network) {
+                continue;
+            }
+            let Some(expected) = cryptocurrency.bech32_hrp_for(network) else {
+                continue;
+            };
+            if expected.as_bytes() != hrp.as_slice() {
+                continue;
+            }
+
+            let (version, program) = bech32::decode_segwit_address(expected, address)?;
+            let kind = match (version, program.len()) {
+                (0, 20) => AddressKind::P2wpkh,
+                (0, 32) => AddressKind::P2wsh,
+                (1, 32) => AddressKind::P2tr,
+                (version, _) => AddressKind::WitnessUnknown(version),
+            };
+            return Ok(Some(ParsedAddress {
+                cryptocurrency,
+                network,
+                kind,
+                payload: program,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// 解析 Base58Check（标准或 Ripple 字母表）地址
+fn parse_base58_address(address: &str) -> Result<ParsedAddress, &'static str> {
+    let mut decoded = [0u8; 64];
+
+    if let Ok(len) = base58::check_decode_into(address.as_bytes(), &base58::BITCOIN, &mut decoded) {
+        if len != 21 {
+            return Err("Invalid address payload length");
+        }
+        let version = decoded[0];
+
+        for cryptocurrency in Cryptocurrency::ALL {
+            if matches!(cryptocurrency, Cryptocurrency::Ethereum | Cryptocurrency::Ripple) {
+                continue;
+            }
+            for network in [Network::Mainnet, Network::Testnet3] {
+                if !cryptocurrency.supports_network(network) {
+                    continue;
+                }
+                let kind = if version == cryptocurrency.address_prefix_for(network) {
+                    AddressKind::P2pkh
+                } else if version == cryptocurrency.script_hash_prefix_for(network) {
+                    AddressKind::P2sh
+                } else {
+                    continue;
+                };
+                return Ok(ParsedAddress {
+                    cryptocurrency,
+                    network,
+                    kind,
+                    payload: decoded[1..len].to_vec(),
+                });
+            }
+        }
+        return Err("Unknown address version");
+    }
+
+    let len = base58::check_decode_into(address.as_bytes(), &base58::RIPPLE, &mut decoded)
+        .map_err(|_| "Unrecognized address format")?;
+    if len != 21 || decoded[0] != 0x00 {
+        return Err("Unrecognized address format");
+    }
+    Ok(ParsedAddress {
+        cryptocurrency: Cryptocurrency::Ripple,
+        network: Network::Mainnet,
+        kind: AddressKind::P2pkh,
+        payload: decoded[1..len].to_vec(),
+    })
+}
@@ -54,7 +54,7 @@ fn sub_word(w: u32) -> u32 {
 }
 
 fn rot_word(w: u32) -> u32 {
-    (w << 8) | (w >> 24)
+    w.rotate_left(8)
 }
 
 fn bytes_to_u32(b: &[u8]) -> u32 {
@@ -85,7 +85,7 @@ impl Aes256 {
     fn add_round_key(state: &mut [u8;16], round: usize, rk: &[u32;60]) {
         for i in 0..4 {
             let rk_word = rk[round*4 + i];
-            state[i*4 + 0] ^= ((rk_word >> 24) & 0xFF) as u8;
+            state[i*4] ^= ((rk_word >> 24) & 0xFF) as u8;
             state[i*4 + 1] ^= ((rk_word >> 16) & 0xFF) as u8;
             state[i*4 + 2] ^= ((rk_word >> 8) & 0xFF) as u8;
             state[i*4 + 3] ^= (rk_word & 0xFF) as u8;
@@ -176,7 +176,7 @@ impl Aes256 {
         for _ in 0..8 {
             if (b & 1) != 0 { r ^= a; }
             let hi = (a & 0x80) != 0;
-            a = a << 1;
+            a <<= 1;
             if hi { a ^= 0x1b; }
             b >>= 1;
         }
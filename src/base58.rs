@@ -0,0 +1,195 @@
+extern crate alloc;
+
+use crate::sha256::Sha256;
+use alloc::vec::Vec;
+
+/// Base58 字母表（编码表与反查表）
+pub struct Alphabet {
+    encode: [u8; 58],
+    decode: [u8; 128],
+}
+
+/// 反查表中表示非法字符的值
+const INVALID: u8 = 0xff;
+
+impl Alphabet {
+    /// 由 58 个互不相同的 ASCII 字符构造字母表
+    pub const fn new(chars: &[u8; 58]) -> Self {
+        let mut decode = [INVALID; 128];
+        let mut i = 0;
+        while i < 58 {
+            decode[chars[i] as usize] = i as u8;
+            i += 1;
+        }
+        Self {
+            encode: *chars,
+            decode,
+        }
+    }
+
+    /// 按数值排列的 58 个字符
+    pub fn chars(&self) -> &[u8; 58] {
+        &self.encode
+    }
+
+    /// 字符对应的数值（非法字符返回 None）
+    fn digit(&self, c: u8) -> Option<u8> {
+        match self.decode.get(c as usize) {
+            Some(&d) if d != INVALID => Some(d),
+            _ => None,
+        }
+    }
+}
+
+/// 比特币字母表（地址、扩展密钥、WIF）
+pub const BITCOIN: Alphabet =
+    Alphabet::new(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
+/// Ripple 字母表
+pub const RIPPLE: Alphabet =
+    Alphabet::new(b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz");
+
+/// Base58Check 校验和（双重 SHA-256 的前 4 字节）
+fn checksum(data: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(&Sha256::digest(data));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// 编码多段连续数据，结果写入 out，返回写入的字符数
+///
+/// 在 out 中以小端序累积 58 进制数位，只做一次逐字节的乘加，不分配内存。
+fn encode_parts(parts: &[&[u8]], alphabet: &Alphabet, out: &mut [u8]) -> Result<usize, &'static str> {
+    let mut bytes = parts.iter().flat_map(|part| part.iter().copied());
+    let zeros = bytes.clone().take_while(|&b| b == 0).count();
+
+    let mut len = 0;
+    for byte in bytes.by_ref().skip(zeros) {
+        let mut carry = byte as u32;
+        for digit in out[..len].iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            if len == out.len() {
+                return Err("Base58 output buffer too small");
+            }
+            out[len] = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+
+    let total = zeros + len;
+    if total > out.len() {
+        return Err("Base58 output buffer too small");
+    }
+
+    // 数位转为大端序并映射成字符，前导零字节对应前导的零字符
+    out[..len].reverse();
+    out.copy_within(..len, zeros);
+    for c in out[..zeros].iter_mut() {
+        *c = alphabet.encode[0];
+    }
+    for c in out[zeros..total].iter_mut() {
+        *c = alphabet.encode[*c as usize];
+    }
+    Ok(total)
+}
+
+/// Base58 编码到调用者提供的缓冲区，返回写入的字符数
+pub fn encode_into(data: &[u8], alphabet: &Alphabet, out: &mut [u8]) -> Result<usize, &'static str> {
+    encode_parts(&[data], alphabet, out)
+}
+
+/// Base58 解码到调用者提供的缓冲区，返回写入的字节数
+pub fn decode_into(input: &[u8], alphabet: &Alphabet, out: &mut [u8]) -> Result<usize, &'static str> {
+    let zero_char = alphabet.encode[0];
+    let zeros = input.iter().take_while(|&&c| c == zero_char).count();
+
+    // 在 out 中以小端序累积字节
+    let mut len = 0;
+    for &c in &input[zeros..] {
+        let mut carry = alphabet.digit(c).ok_or("Invalid base58 character")? as u32;
+        for byte in out[..len].iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            if len == out.len() {
+                return Err("Base58 output buffer too small");
+            }
+            out[len] = (carry & 0xff) as u8;
+            len += 1;
+            carry >>= 8;
+        }
+    }
+
+    let total = zeros + len;
+    if total > out.len() {
+        return Err("Base58 output buffer too small");
+    }
+
+    out[..len].reverse();
+    out.copy_within(..len, zeros);
+    out[..zeros].fill(0);
+    Ok(total)
+}
+
+/// Base58Check 编码（payload || 4 字节校验和）到调用者提供的缓冲区
+pub fn check_encode_into(payload: &[u8], alphabet: &Alphabet, out: &mut [u8]) -> Result<usize, &'static str> {
+    encode_parts(&[payload, &checksum(payload)], alphabet, out)
+}
+
+/// Base58Check 解码并校验，payload 写入 out，返回 payload 长度
+pub fn check_decode_into(input: &[u8], alphabet: &Alphabet, out: &mut [u8]) -> Result<usize, &'static str> {
+    let len = decode_into(input, alphabet, out)?;
+    if len < 4 {
+        return Err("Base58Check data too short");
+    }
+
+    let payload_len = len - 4;
+    if checksum(&out[..payload_len]) != out[payload_len..len] {
+        return Err("Invalid Base58Check checksum");
+    }
+    Ok(payload_len)
+}
+
+/// 编码后的最大字符数（log(256) / log(58) < 1.38）
+fn max_encoded_len(data_len: usize) -> usize {
+    data_len * 138 / 100 + 1
+}
+
+/// Base58 编码，返回新分配的 ASCII 字节
+pub fn encode(data: &[u8], alphabet: &Alphabet) -> Vec<u8> {
+    let mut out = alloc::vec![0u8; max_encoded_len(data.len())];
+    let len = encode_into(data, alphabet, &mut out).unwrap_or(0);
+    out.truncate(len);
+    out
+}
+
+/// Base58 解码，返回新分配的字节
+pub fn decode(input: &[u8], alphabet: &Alphabet) -> Result<Vec<u8>, &'static str> {
+    // 每个字符至多产生一个字节
+    let mut out = alloc::vec![0u8; input.len()];
+    let len = decode_into(input, alphabet, &mut out)?;
+    out.truncate(len);
+    Ok(out)
+}
+
+/// Base58Check 编码，返回新分配的 ASCII 字节
+pub fn check_encode(payload: &[u8], alphabet: &Alphabet) -> Vec<u8> {
+    let mut out = alloc::vec![0u8; max_encoded_len(payload.len() + 4)];
+    let len = check_encode_into(payload, alphabet, &mut out).unwrap_or(0);
+    out.truncate(len);
+    out
+}
+
+/// Base58Check 解码并校验，返回 payload
+pub fn check_decode(input: &[u8], alphabet: &Alphabet) -> Result<Vec<u8>, &'static str> {
+    let mut out = alloc::vec![0u8; input.len()];
+    let len = check_decode_into(input, alphabet, &mut out)?;
+    out.truncate(len);
+    Ok(out)
+}
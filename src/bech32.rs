@@ -0,0 +1,230 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+/// Bech32 字符集（5 位值到字符的映射）
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// 校验和变体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// BIP173 bech32（见证版本 0）
+    Bech32,
+    /// BIP350 bech32m（见证版本 1 到 16）
+    Bech32m,
+}
+
+impl Variant {
+    /// 校验和常量
+    fn constant(&self) -> u32 {
+        match self {
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+}
+
+/// 完整字符串的最大长度（BIP173）
+const MAX_LENGTH: usize = 90;
+
+/// BCH 校验码多项式取模
+fn polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ (v as u32);
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+/// 展开 HRP：高 3 位 || 0 || 低 5 位
+fn hrp_expand(hrp: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(hrp.len() * 2 + 1);
+    result.extend(hrp.iter().map(|&c| c >> 5));
+    result.push(0);
+    result.extend(hrp.iter().map(|&c| c & 0x1f));
+    result
+}
+
+/// 计算 6 个 5 位校验和
+fn create_checksum(hrp: &[u8], data: &[u8], variant: Variant) -> [u8; 6] {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; 6]);
+
+    let pm = polymod(&values) ^ variant.constant();
+    let mut checksum = [0u8; 6];
+    for (i, c) in checksum.iter_mut().enumerate() {
+        *c = ((pm >> (5 * (5 - i))) & 0x1f) as u8;
+    }
+    checksum
+}
+
+/// Bech32/Bech32m 编码：hrp + "1" + data + 校验和（data 为 5 位值）
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> Result<Vec<u8>, &'static str> {
+    let hrp = hrp.as_bytes();
+    if hrp.is_empty() || hrp.iter().any(|&c| !(33..=126).contains(&c) || c.is_ascii_uppercase()) {
+        return Err("Invalid bech32 human-readable part");
+    }
+    if hrp.len() + 1 + data.len() + 6 > MAX_LENGTH {
+        return Err("Bech32 string too long");
+    }
+    if data.iter().any(|&d| d > 31) {
+        return Err("Invalid bech32 data value");
+    }
+
+    let checksum = create_checksum(hrp, data, variant);
+
+    let mut result = Vec::with_capacity(hrp.len() + 1 + data.len() + 6);
+    result.extend_from_slice(hrp);
+    result.push(b'1');
+    result.extend(data.iter().chain(checksum.iter()).map(|&d| CHARSET[d as usize]));
+    Ok(result)
+}
+
+/// Bech32/Bech32m 解码，返回（小写 HRP，5 位数据（不含校验和），校验和变体）
+///
+/// 拒绝大小写混合、非法字符以及校验和错误的字符串。
+pub fn decode(s: &str) -> Result<(Vec<u8>, Vec<u8>, Variant), &'static str> {
+    let bytes = s.as_bytes();
+    if bytes.len() > MAX_LENGTH {
+        return Err("Bech32 string too long");
+    }
+    if bytes.iter().any(|&c| !(33..=126).contains(&c)) {
+        return Err("Invalid bech32 character");
+    }
+
+    let has_lower = bytes.iter().any(|c| c.is_ascii_lowercase());
+    let has_upper = bytes.iter().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err("Mixed-case bech32 string");
+    }
+
+    // 最后一个 '1' 是分隔符
+    let sep = bytes
+        .iter()
+        .rposition(|&c| c == b'1')
+        .ok_or("Missing bech32 separator")?;
+    if sep == 0 || sep + 7 > bytes.len() {
+        return Err("Invalid bech32 separator position");
+    }
+
+    let hrp: Vec<u8> = bytes[..sep].iter().map(|c| c.to_ascii_lowercase()).collect();
+
+    let mut data = Vec::with_capacity(bytes.len() - sep - 1);
+    for &c in &bytes[sep + 1..] {
+        let c = c.to_ascii_lowercase();
+        let value = CHARSET
+            .iter()
+            .position(|&x| x == c)
+            .ok_or("Invalid bech32 character")?;
+        data.push(value as u8);
+    }
+
+    let mut values = hrp_expand(&hrp);
+    values.extend_from_slice(&data);
+    let variant = match polymod(&values) {
+        c if c == Variant::Bech32.constant() => Variant::Bech32,
+        c if c == Variant::Bech32m.constant() => Variant::Bech32m,
+        _ => return Err("Invalid bech32 checksum"),
+    };
+
+    data.truncate(data.len() - 6);
+    Ok((hrp, data, variant))
+}
+
+/// 位宽转换（例如 8 位字节与 5 位组之间）
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, &'static str> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_v: u32 = (1 << to) - 1;
+    let mut result = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+
+    for &value in data {
+        let v = value as u32;
+        if v >> from != 0 {
+            return Err("Invalid value for bit conversion");
+        }
+        acc = (acc << from) | v;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push(((acc >> bits) & max_v) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            result.push(((acc << (to - bits)) & max_v) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max_v) != 0 {
+        return Err("Invalid padding in bit conversion");
+    }
+
+    Ok(result)
+}
+
+/// 检查见证版本和见证程序长度（BIP141）
+fn check_witness_program(witness_version: u8, program: &[u8]) -> Result<(), &'static str> {
+    if witness_version > 16 {
+        return Err("Invalid witness version");
+    }
+    if program.len() < 2 || program.len() > 40 {
+        return Err("Invalid witness program length");
+    }
+    if witness_version == 0 && program.len() != 20 && program.len() != 32 {
+        return Err("Invalid witness program length");
+    }
+    Ok(())
+}
+
+/// 见证版本对应的校验和变体：v0 使用 bech32，v1 及以上使用 bech32m（BIP350）
+fn segwit_variant(witness_version: u8) -> Variant {
+    if witness_version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    }
+}
+
+/// 编码 SegWit 地址（BIP173 / BIP350）
+pub fn encode_segwit_address(
+    hrp: &str,
+    witness_version: u8,
+    program: &[u8],
+) -> Result<Vec<u8>, &'static str> {
+    check_witness_program(witness_version, program)?;
+
+    let mut data = Vec::with_capacity(1 + (program.len() * 8).div_ceil(5));
+    data.push(witness_version);
+    data.extend_from_slice(&convert_bits(program, 8, 5, true)?);
+    encode(hrp, &data, segwit_variant(witness_version))
+}
+
+/// 解码 SegWit 地址，返回（见证版本，见证程序）
+pub fn decode_segwit_address(hrp: &str, address: &str) -> Result<(u8, Vec<u8>), &'static str> {
+    let (decoded_hrp, data, variant) = decode(address)?;
+    if decoded_hrp != hrp.as_bytes() {
+        return Err("Wrong bech32 human-readable part");
+    }
+    if data.is_empty() {
+        return Err("Empty segwit data");
+    }
+
+    let witness_version = data[0];
+    if variant != segwit_variant(witness_version) {
+        return Err("Wrong checksum variant for witness version");
+    }
+
+    let program = convert_bits(&data[1..], 5, 8, false)?;
+    check_witness_program(witness_version, &program)?;
+
+    Ok((witness_version, program))
+}
@@ -1,60 +1,205 @@
 extern crate alloc;
 
+use core::fmt;
+use core::str::FromStr;
+
+use crate::address::hash160;
+use crate::base58;
+use crate::fixed_string::FixedString;
+use crate::network::Network;
 use crate::secp256k1::{PublicKey, SecretKey};
-use crate::sha256::Sha256;
-use alloc::vec::Vec;
-
-/// HMAC-SHA256 实现（用于 BIP32）
-/// 返回 32 字节的哈希结果
-fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
-    const BLOCK_SIZE: usize = 64;
-    
-    let mut k = [0u8; BLOCK_SIZE];
-    
-    // 如果密钥太长，先哈希它
-    if key.len() > BLOCK_SIZE {
-        let hash = Sha256::digest(key);
-        k[..32].copy_from_slice(&hash);
-    } else {
-        k[..key.len()].copy_from_slice(key);
-    }
-    
-    // 创建外层和内层的密钥
-    let mut o_key_pad = [0u8; BLOCK_SIZE];
-    let mut i_key_pad = [0u8; BLOCK_SIZE];
-    
-    for i in 0..BLOCK_SIZE {
-        o_key_pad[i] = k[i] ^ 0x5c;
-        i_key_pad[i] = k[i] ^ 0x36;
-    }
-    
-    // 计算内层 HMAC
-    let mut inner_input = Vec::with_capacity(BLOCK_SIZE + message.len());
-    inner_input.extend_from_slice(&i_key_pad);
-    inner_input.extend_from_slice(message);
-    
-    let inner_hash = Sha256::digest(&inner_input);
-    
-    // 计算外层 HMAC
-    let mut outer_input = Vec::with_capacity(BLOCK_SIZE + 32);
-    outer_input.extend_from_slice(&o_key_pad);
-    outer_input.extend_from_slice(&inner_hash);
-    
-    Sha256::digest(&outer_input)
+use crate::sha512::HmacSha512;
+
+/// 扩展密钥序列化后的长度（Base58Check 编码前）
+const EXTENDED_KEY_LEN: usize = 78;
+
+/// 硬化派生索引标志位
+pub const HARDENED: u32 = 0x80000000;
+
+/// 派生路径支持的最大深度
+pub const MAX_PATH_DEPTH: usize = 10;
+
+/// BIP32 派生路径，例如 m/84'/0'/0'/1/5
+///
+/// 索引按原始形式存储（硬化索引包含 0x80000000 位）。
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct DerivationPath {
+    indices: [u32; MAX_PATH_DEPTH],
+    len: usize,
+}
+
+impl DerivationPath {
+    /// 空路径（即 "m"，主密钥本身）
+    pub fn master() -> Self {
+        DerivationPath {
+            indices: [0; MAX_PATH_DEPTH],
+            len: 0,
+        }
+    }
+
+    /// BIP44 路径：m/44'/coin_type'/account'/change/address_index
+    pub fn bip44(coin_type: u32, account: u32, change: u32, address_index: u32) -> Self {
+        Self::from_purpose(44, coin_type, account, change, address_index)
+    }
+
+    /// BIP44 风格路径（BIP49/84/86 等）：m/purpose'/coin_type'/account'/change/address_index
+    pub fn from_purpose(
+        purpose: u32,
+        coin_type: u32,
+        account: u32,
+        change: u32,
+        address_index: u32,
+    ) -> Self {
+        let mut path = Self::master();
+        path.indices[..5].copy_from_slice(&[
+            purpose | HARDENED,
+            coin_type | HARDENED,
+            account | HARDENED,
+            change,
+            address_index,
+        ]);
+        path.len = 5;
+        path
+    }
+
+    /// 解析路径字符串，硬化标记可以是 '、h 或 H
+    pub fn parse(s: &str) -> Result<Self, &'static str> {
+        let mut components = s.trim().split('/');
+        if components.next() != Some("m") {
+            return Err("Invalid derivation path (must start with m)");
+        }
+
+        let mut path = Self::master();
+        for component in components {
+            let (digits, hardened) = match component.strip_suffix(['\'', 'h', 'H']) {
+                Some(digits) => (digits, true),
+                None => (component, false),
+            };
+
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err("Invalid derivation path component");
+            }
+            let index: u32 = digits
+                .parse()
+                .map_err(|_| "Derivation path index out of range (must be < 2^31)")?;
+
+            path.push(index, hardened)?;
+        }
+
+        Ok(path)
+    }
+
+    /// 在末尾追加一级（index 必须 < 2^31）
+    pub fn push(&mut self, index: u32, hardened: bool) -> Result<(), &'static str> {
+        if index & HARDENED != 0 {
+            return Err("Derivation path index out of range (must be < 2^31)");
+        }
+        if self.len == MAX_PATH_DEPTH {
+            return Err("Derivation path too deep");
+        }
+
+        self.indices[self.len] = if hardened { index | HARDENED } else { index };
+        self.len += 1;
+        Ok(())
+    }
+
+    /// 返回追加一级后的新路径
+    pub fn child(&self, index: u32, hardened: bool) -> Result<Self, &'static str> {
+        let mut path = *self;
+        path.push(index, hardened)?;
+        Ok(path)
+    }
+
+    /// 各级的原始索引（硬化索引包含 0x80000000 位）
+    pub fn indices(&self) -> &[u32] {
+        &self.indices[..self.len]
+    }
+
+    /// 路径深度
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 是否为空路径 "m"
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 格式化为字符串，硬化索引使用 ' 标记
+    pub fn format(&self) -> FixedString<128> {
+        let mut out = FixedString::new();
+        // 最长 "m" + 10 × "/2147483647'"，不会超出容量
+        let _ = fmt::write(&mut out, format_args!("{}", self));
+        out
+    }
 }
 
-/// 主密钥（从种子派生）
-pub struct MasterKey {
+impl Default for DerivationPath {
+    fn default() -> Self {
+        Self::master()
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for &index in self.indices() {
+            if index & HARDENED != 0 {
+                write!(f, "/{}'", index & !HARDENED)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// 扩展私钥（BIP32）
+#[derive(Clone, Copy)]
+pub struct ExtendedPrivKey {
+    network: Network,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
     key: SecretKey,
     chain_code: [u8; 32],
 }
 
-impl MasterKey {
-    /// 从种子创建主密钥
-    pub fn from_seed(seed: &[u8; 64]) -> Result<Self, &'static str> {
-        // BIP32: HMAC-SHA256(Key = "Bitcoin seed", Data = seed)
-        let hmac = hmac_sha256(b"Bitcoin seed", seed);
-        
+/// 主密钥即深度为 0 的扩展私钥
+pub type MasterKey = ExtendedPrivKey;
+
+impl ExtendedPrivKey {
+    /// 从种子创建主网主密钥（BIP32 要求种子长度为 16 到 64 字节）
+    pub fn from_seed(seed: &[u8]) -> Result<Self, &'static str> {
+        Self::new_master(Network::Mainnet, seed)
+    }
+
+    /// 从种子创建指定网络的主密钥
+    pub fn new_master(network: Network, seed: &[u8]) -> Result<Self, &'static str> {
+        if seed.len() < 16 || seed.len() > 64 {
+            return Err("Invalid seed length (must be 16..=64 bytes)");
+        }
+
+        // BIP32: I = HMAC-SHA512(Key = "Bitcoin seed", Data = seed)
+        let mut mac = HmacSha512::new(b"Bitcoin seed");
+        mac.update(seed);
+        let hmac = mac.finalize();
+
+        // IL 为主私钥，IR 为主链码
         let mut master_key_bytes = [0u8; 32];
         master_key_bytes.copy_from_slice(&hmac[..32]);
 
@@ -64,61 +209,76 @@ impl MasterKey {
         let key = SecretKey::from_bytes(&master_key_bytes)
             .map_err(|_| "Invalid master key (parsed key must be non-zero and < N)")?;
 
-        Ok(MasterKey { key, chain_code })
+        Ok(ExtendedPrivKey {
+            network,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+            key,
+            chain_code,
+        })
     }
 
-    /// 派生子密钥（BIP32）
+    /// 派生子密钥（BIP32 CKDpriv）
     pub fn derive_child(&self, index: u32, hardened: bool) -> Result<Self, &'static str> {
-        let final_index = if hardened { index | 0x80000000 } else { index };
+        if index & HARDENED != 0 {
+            return Err("Invalid child index (must be < 2^31)");
+        }
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or("Maximum derivation depth exceeded")?;
+        let final_index = if hardened { index | HARDENED } else { index };
+
+        // I = HMAC-SHA512(Key = c_par, Data = ...)
+        let mut mac = HmacSha512::new(&self.chain_code);
+        let public_key = PublicKey::from_secret_key(&self.key)?;
 
-        // 准备派生数据
-        let mut data = Vec::with_capacity(37);
-        
         if hardened {
-            // 硬派生（Hardened Derivation）：使用私钥
-            data.push(0x00);
-            data.extend_from_slice(self.key.as_bytes());
+            // 硬派生（Hardened Derivation）：0x00 || ser256(k_par) || ser32(i)
+            mac.update(&[0x00]);
+            mac.update(self.key.as_bytes());
         } else {
-            // 软派生（Normal Derivation）：使用公钥
-            let public_key = PublicKey::from_secret_key(&self.key)?;
-            let pubkey_bytes = public_key.serialize_compressed();
-            data.extend_from_slice(&pubkey_bytes);
+            // 软派生（Normal Derivation）：serP(point(k_par)) || ser32(i)
+            mac.update(&public_key.serialize_compressed());
         }
 
         // 添加索引（大端序 32 位）
-        data.extend_from_slice(&final_index.to_be_bytes());
-
-        // HMAC-SHA256(chain_code, data)
-        let hmac = hmac_sha256(&self.chain_code, &data);
+        mac.update(&final_index.to_be_bytes());
+        let hmac = mac.finalize();
 
-        // 分离私钥倍数和新链码
-        let mut child_key_bytes = [0u8; 32];
-        child_key_bytes.copy_from_slice(&hmac[..32]);
+        // 分离 IL（私钥调整量）和 IR（子链码）
+        let mut tweak = [0u8; 32];
+        tweak.copy_from_slice(&hmac[..32]);
 
         let mut child_chain_code = [0u8; 32];
         child_chain_code.copy_from_slice(&hmac[32..]);
 
-        // 派生的私钥 = (tweak + parent_key) mod n
-        // 这里使用简化版本：直接加法（mod n）
-        let mut new_key = [0u8; 32];
-        let mut carry = 0u16;
-        
-        // 大端序加法
-        for i in (0..32).rev() {
-            let sum = self.key.as_bytes()[i] as u16 + child_key_bytes[i] as u16 + carry;
-            new_key[i] = (sum & 0xff) as u8;
-            carry = sum >> 8;
-        }
-
-        let child_key = SecretKey::from_bytes(&new_key)
+        // k_i = (IL + k_par) mod n；IL >= n 或结果为零时该索引无效
+        let child_key = self
+            .key
+            .add_tweak(&tweak)
             .map_err(|_| "Derived child key is invalid")?;
 
-        Ok(MasterKey {
+        Ok(ExtendedPrivKey {
+            network: self.network,
+            depth,
+            parent_fingerprint: fingerprint_of(&public_key),
+            child_number: final_index,
             key: child_key,
             chain_code: child_chain_code,
         })
     }
 
+    /// 按路径逐级派生（相对于当前密钥；对主密钥即为绝对路径）
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, &'static str> {
+        let mut key = *self;
+        for &index in path.indices() {
+            key = key.derive_child(index & !HARDENED, index & HARDENED != 0)?;
+        }
+        Ok(key)
+    }
+
     /// 派生 BIP44 路径：m/44'/coin_type'/account'/change/address_index
     pub fn derive_bip44(
         &self,
@@ -127,18 +287,27 @@ impl MasterKey {
         change: u32,
         address_index: u32,
     ) -> Result<SecretKey, &'static str> {
-        // m/44'
-        let purpose = self.derive_child(44, true)?;
-        // m/44'/coin_type'
-        let coin = purpose.derive_child(coin_type, true)?;
-        // m/44'/coin_type'/account'
-        let account_key = coin.derive_child(account, true)?;
-        // m/44'/coin_type'/account'/change
-        let change_key = account_key.derive_child(change, false)?;
-        // m/44'/coin_type'/account'/change/address_index
-        let address_key = change_key.derive_child(address_index, false)?;
+        let path = DerivationPath::bip44(coin_type, account, change, address_index);
+        Ok(self.derive_path(&path)?.key)
+    }
 
-        Ok(address_key.key)
+    /// 派生 BIP44 账户级扩展私钥：m/44'/coin_type'/account'
+    pub fn derive_bip44_account(&self, coin_type: u32, account: u32) -> Result<Self, &'static str> {
+        self.derive_account(44, coin_type, account)
+    }
+
+    /// 派生账户级扩展私钥：m/purpose'/coin_type'/account'
+    pub fn derive_account(
+        &self,
+        purpose: u32,
+        coin_type: u32,
+        account: u32,
+    ) -> Result<Self, &'static str> {
+        let mut path = DerivationPath::master();
+        path.push(purpose, true)?;
+        path.push(coin_type, true)?;
+        path.push(account, true)?;
+        self.derive_path(&path)
     }
 
     /// 获取私钥
@@ -150,9 +319,317 @@ impl MasterKey {
     pub fn public_key(&self) -> Result<PublicKey, &'static str> {
         PublicKey::from_secret_key(&self.key)
     }
-    
+
     /// 获取链码
     pub fn chain_code(&self) -> &[u8; 32] {
         &self.chain_code
     }
+
+    /// 获取网络
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// 获取深度（主密钥为 0）
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// 获取父密钥指纹（主密钥为 0）
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// 获取子密钥索引（硬派生时包含 0x80000000 位）
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// 密钥标识符：HASH160(压缩公钥)
+    pub fn identifier(&self) -> Result<[u8; 20], &'static str> {
+        Ok(hash160(&self.public_key()?.serialize_compressed()))
+    }
+
+    /// 密钥指纹：标识符的前 4 字节
+    pub fn fingerprint(&self) -> Result<[u8; 4], &'static str> {
+        Ok(fingerprint_of(&self.public_key()?))
+    }
+
+    /// 对应的扩展公钥
+    pub fn to_extended_pub_key(&self) -> Result<ExtendedPubKey, &'static str> {
+        Ok(ExtendedPubKey {
+            network: self.network,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            public_key: self.public_key()?,
+            chain_code: self.chain_code,
+        })
+    }
+
+    /// 序列化为 78 字节：version || depth || fingerprint || child || chain code || 0x00 || key
+    pub fn encode(&self) -> [u8; EXTENDED_KEY_LEN] {
+        let mut data = encode_header(
+            &self.network.xprv_version(),
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+        );
+        data[45] = 0x00;
+        data[46..].copy_from_slice(self.key.as_bytes());
+        data
+    }
+
+    /// 从 78 字节序列化数据解析
+    pub fn decode(data: &[u8]) -> Result<Self, &'static str> {
+        let header = decode_header(data)?;
+        let network = Network::from_xprv_version(&header.version)
+            .ok_or("Unknown extended private key version")?;
+
+        if data[45] != 0x00 {
+            return Err("Invalid extended private key (missing 0x00 prefix)");
+        }
+        let mut key_bytes = [0u8; 32];
+        key_bytes.copy_from_slice(&data[46..]);
+        let key = SecretKey::from_bytes(&key_bytes)?;
+
+        Ok(ExtendedPrivKey {
+            network,
+            depth: header.depth,
+            parent_fingerprint: header.parent_fingerprint,
+            child_number: header.child_number,
+            key,
+            chain_code: header.chain_code,
+        })
+    }
+
+    /// Base58Check 编码（xprv... / tprv...）
+    pub fn to_base58(&self) -> FixedString<128> {
+        to_base58_string(&self.encode())
+    }
+
+    /// 从 Base58Check 字符串解析
+    pub fn from_base58(s: &str) -> Result<Self, &'static str> {
+        let mut payload = [0u8; EXTENDED_KEY_LEN + 4];
+        let len = base58::check_decode_into(s.as_bytes(), &base58::BITCOIN, &mut payload)?;
+        Self::decode(&payload[..len])
+    }
+}
+
+/// 扩展公钥（BIP32）
+#[derive(Clone, Copy)]
+pub struct ExtendedPubKey {
+    network: Network,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    public_key: PublicKey,
+    chain_code: [u8; 32],
+}
+
+impl ExtendedPubKey {
+    /// 从扩展私钥导出
+    pub fn from_priv(xprv: &ExtendedPrivKey) -> Result<Self, &'static str> {
+        xprv.to_extended_pub_key()
+    }
+
+    /// 派生非硬化子公钥（BIP32 CKDpub），无需私钥
+    pub fn derive_child(&self, index: u32) -> Result<Self, &'static str> {
+        if index & HARDENED != 0 {
+            return Err("Cannot derive hardened child from a public key");
+        }
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or("Maximum derivation depth exceeded")?;
+
+        // I = HMAC-SHA512(Key = c_par, Data = serP(K_par) || ser32(i))
+        let mut mac = HmacSha512::new(&self.chain_code);
+        mac.update(&self.public_key.serialize_compressed());
+        mac.update(&index.to_be_bytes());
+        let hmac = mac.finalize();
+
+        let mut tweak = [0u8; 32];
+        tweak.copy_from_slice(&hmac[..32]);
+
+        let mut child_chain_code = [0u8; 32];
+        child_chain_code.copy_from_slice(&hmac[32..]);
+
+        // K_i = point(IL) + K_par；IL >= n 或结果为无穷远点时该索引无效
+        let child_key = self
+            .public_key
+            .add_exp_tweak(&tweak)
+            .map_err(|_| "Derived child key is invalid")?;
+
+        Ok(ExtendedPubKey {
+            network: self.network,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            public_key: child_key,
+            chain_code: child_chain_code,
+        })
+    }
+
+    /// 按非硬化路径逐级派生（路径中出现硬化索引时失败）
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, &'static str> {
+        let mut key = *self;
+        for &index in path.indices() {
+            key = key.derive_child(index)?;
+        }
+        Ok(key)
+    }
+
+    /// 获取公钥
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// 获取链码
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// 获取网络
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// 获取深度（主密钥为 0）
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// 获取父密钥指纹（主密钥为 0）
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// 获取子密钥索引（硬派生时包含 0x80000000 位）
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// 密钥标识符：HASH160(压缩公钥)
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(&self.public_key.serialize_compressed())
+    }
+
+    /// 密钥指纹：标识符的前 4 字节
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint_of(&self.public_key)
+    }
+
+    /// 序列化为 78 字节：version || depth || fingerprint || child || chain code || 压缩公钥
+    pub fn encode(&self) -> [u8; EXTENDED_KEY_LEN] {
+        let mut data = encode_header(
+            &self.network.xpub_version(),
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+        );
+        data[45..].copy_from_slice(&self.public_key.serialize_compressed());
+        data
+    }
+
+    /// 从 78 字节序列化数据解析
+    pub fn decode(data: &[u8]) -> Result<Self, &'static str> {
+        let header = decode_header(data)?;
+        let network = Network::from_xpub_version(&header.version)
+            .ok_or("Unknown extended public key version")?;
+
+        if data[45] != 0x02 && data[45] != 0x03 {
+            return Err("Invalid extended public key (must be compressed)");
+        }
+        let public_key = PublicKey::from_slice(&data[45..])?;
+
+        Ok(ExtendedPubKey {
+            network,
+            depth: header.depth,
+            parent_fingerprint: header.parent_fingerprint,
+            child_number: header.child_number,
+            public_key,
+            chain_code: header.chain_code,
+        })
+    }
+
+    /// Base58Check 编码（xpub... / tpub...）
+    pub fn to_base58(&self) -> FixedString<128> {
+        to_base58_string(&self.encode())
+    }
+
+    /// 从 Base58Check 字符串解析
+    pub fn from_base58(s: &str) -> Result<Self, &'static str> {
+        let mut payload = [0u8; EXTENDED_KEY_LEN + 4];
+        let len = base58::check_decode_into(s.as_bytes(), &base58::BITCOIN, &mut payload)?;
+        Self::decode(&payload[..len])
+    }
+}
+
+/// 扩展密钥的公共头部字段
+struct ExtendedKeyHeader {
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+}
+
+/// 公钥指纹：HASH160(压缩公钥) 的前 4 字节
+fn fingerprint_of(public_key: &PublicKey) -> [u8; 4] {
+    let id = hash160(&public_key.serialize_compressed());
+    [id[0], id[1], id[2], id[3]]
+}
+
+/// 写入前 45 字节的头部，密钥数据由调用方填充
+fn encode_header(
+    version: &[u8; 4],
+    depth: u8,
+    parent_fingerprint: &[u8; 4],
+    child_number: u32,
+    chain_code: &[u8; 32],
+) -> [u8; EXTENDED_KEY_LEN] {
+    let mut data = [0u8; EXTENDED_KEY_LEN];
+    data[..4].copy_from_slice(version);
+    data[4] = depth;
+    data[5..9].copy_from_slice(parent_fingerprint);
+    data[9..13].copy_from_slice(&child_number.to_be_bytes());
+    data[13..45].copy_from_slice(chain_code);
+    data
+}
+
+/// 解析并校验头部字段
+fn decode_header(data: &[u8]) -> Result<ExtendedKeyHeader, &'static str> {
+    if data.len() != EXTENDED_KEY_LEN {
+        return Err("Invalid extended key length");
+    }
+
+    let mut header = ExtendedKeyHeader {
+        version: [0; 4],
+        depth: data[4],
+        parent_fingerprint: [0; 4],
+        child_number: u32::from_be_bytes([data[9], data[10], data[11], data[12]]),
+        chain_code: [0; 32],
+    };
+    header.version.copy_from_slice(&data[..4]);
+    header.parent_fingerprint.copy_from_slice(&data[5..9]);
+    header.chain_code.copy_from_slice(&data[13..45]);
+
+    // 主密钥的父指纹和索引必须为零
+    if header.depth == 0 && (header.parent_fingerprint != [0; 4] || header.child_number != 0) {
+        return Err("Invalid extended key (zero depth with non-zero parent or index)");
+    }
+
+    Ok(header)
+}
+
+/// Base58Check 编码为定长字符串（111 个字符）
+fn to_base58_string(data: &[u8; EXTENDED_KEY_LEN]) -> FixedString<128> {
+    let mut encoded = [0u8; 128];
+    base58::check_encode_into(data, &base58::BITCOIN, &mut encoded)
+        .ok()
+        .and_then(|len| FixedString::from_bytes(&encoded[..len]).ok())
+        .unwrap_or_default()
 }
@@ -0,0 +1,280 @@
+extern crate alloc;
+
+use crate::script::{self, ScriptKind};
+use crate::transaction::WITNESS_SCALE_FACTOR;
+use alloc::vec::Vec;
+
+/// 分支定界最多尝试的节点数（与 Bitcoin Core 相同）
+const BNB_MAX_TRIES: usize = 100_000;
+
+/// 计算粉尘门限时使用的费率（聪/vB，Bitcoin Core 的默认 dust relay fee）
+const DUST_RELAY_FEE_RATE: u64 = 3;
+
+/// 交易固定部分的权重：version、locktime 与输入输出计数（各 1 字节）
+const TX_OVERHEAD_WEIGHT: u64 = (4 + 4 + 1 + 1) * 4;
+
+/// 隔离见证交易的 marker 与 flag
+const SEGWIT_HEADER_WEIGHT: u64 = 2;
+
+/// 选币策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// 按金额从大到小依次加入，直到足够支付
+    LargestFirst,
+    /// 分支定界：寻找不需要找零、浪费最少的组合，找不到时退回 LargestFirst
+    BranchAndBound,
+}
+
+/// 选币结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    /// 选中的候选下标（按原顺序）
+    pub indices: Vec<usize>,
+    /// 交易手续费（聪）
+    pub fee: u64,
+    /// 找零金额；没有找零输出时为 None（零头计入手续费）
+    pub change: Option<u64>,
+}
+
+/// 一个可花费的候选输出
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candidate {
+    pub value: u64,
+    /// 花费它的输入权重（见 input_weight）
+    pub weight: u64,
+}
+
+/// 交易中除输入以外的部分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Target {
+    /// 支付给收款方的金额
+    pub amount: u64,
+    /// 费率（聪/vB）
+    pub fee_rate: u64,
+    /// 不含找零时交易固定部分与所有输出的权重（见 base_weight）
+    pub base_weight: u64,
+    /// 找零输出的权重
+    pub change_weight: u64,
+    /// 以后花费找零输出所需的输入权重
+    pub change_spend_weight: u64,
+    /// 找零低于此值时不创建找零输出
+    pub change_dust: u64,
+}
+
+/// 花费该脚本的单签名输入的权重（按 73 字节 DER 签名估计，结果不会偏小）
+///
+/// compressed 只影响 P2PKH；不支持的脚本返回 None。
+/// segwit 为 true 时传统输入另计 1 个单位的空见证计数。
+pub fn input_weight(script_pubkey: &[u8], compressed: bool, segwit: bool) -> Option<u64> {
+    // outpoint 36 + sequence 4
+    const BASE: u64 = 36 + 4;
+    // 签名推入：1 + 72 字节 DER + 1 字节哈希类型
+    const SIG_PUSH: u64 = 1 + 73;
+    let witness_count = if segwit { 1 } else { 0 };
+
+    match script::classify(script_pubkey) {
+        ScriptKind::P2pkh(_) => {
+            let key_push = if compressed { 1 + 33 } else { 1 + 65 };
+            let script_sig = SIG_PUSH + key_push;
+            Some((BASE + 1 + script_sig) * 4 + witness_count)
+        }
+        ScriptKind::P2wpkh(_) if compressed => Some((BASE + 1) * 4 + 1 + SIG_PUSH + 1 + 33),
+        // 只支持嵌套 P2WPKH：scriptSig 推入 22 字节赎回脚本
+        ScriptKind::P2sh(_) if compressed => Some((BASE + 1 + 23) * 4 + 1 + SIG_PUSH + 1 + 33),
+        // 密钥路径：一个 64 字节 Schnorr 签名（SIGHASH_DEFAULT）
+        ScriptKind::P2tr(_) => Some((BASE + 1) * 4 + 1 + 1 + 64),
+        _ => None,
+    }
+}
+
+/// 输入是否需要见证数据
+pub fn is_segwit_input(script_pubkey: &[u8]) -> bool {
+    matches!(
+        script::classify(script_pubkey),
+        ScriptKind::P2wpkh(_) | ScriptKind::P2sh(_) | ScriptKind::P2tr(_)
+    )
+}
+
+/// 输出的权重：金额 8 字节 + 脚本长度 + 脚本
+pub fn output_weight(script_pubkey: &[u8]) -> u64 {
+    (8 + 1 + script_pubkey.len() as u64) * 4
+}
+
+/// 交易中除输入以外部分的权重
+pub fn base_weight(output_scripts: &[&[u8]], segwit: bool) -> u64 {
+    let outputs: u64 = output_scripts.iter().map(|script| output_weight(script)).sum();
+    TX_OVERHEAD_WEIGHT + outputs + if segwit { SEGWIT_HEADER_WEIGHT } else { 0 }
+}
+
+/// 按费率计算给定权重的手续费（vsize 向上取整）
+pub fn fee_for_weight(weight: u64, fee_rate: u64) -> u64 {
+    weight.div_ceil(WITNESS_SCALE_FACTOR as u64).saturating_mul(fee_rate)
+}
+
+/// 粉尘门限：创建并花费该输出的成本（按 3 聪/vB 计算，与 Bitcoin Core 一致）
+pub fn dust_threshold(script_pubkey: &[u8]) -> u64 {
+    if script_pubkey.first() == Some(&script::OP_RETURN) {
+        return 0;
+    }
+    let output_size = 8 + 1 + script_pubkey.len() as u64;
+    // 花费它的输入：见证输入的签名与公钥按 1/4 计入
+    let spend_size = match script::classify(script_pubkey) {
+        ScriptKind::P2wpkh(_) | ScriptKind::P2wsh(_) | ScriptKind::P2tr(_) | ScriptKind::WitnessUnknown { .. } => {
+            32 + 4 + 1 + 107 / 4 + 4
+        }
+        _ => 32 + 4 + 1 + 107 + 4,
+    };
+    (output_size + spend_size) * DUST_RELAY_FEE_RATE
+}
+
+/// 选出足以支付目标金额与手续费的候选
+///
+/// 结果是确定的：同样的候选与目标总是得到同样的选择。
+pub fn select(
+    candidates: &[Candidate],
+    target: &Target,
+    strategy: Strategy,
+) -> Result<Selection, &'static str> {
+    if target.fee_rate == 0 {
+        return Err("Fee rate must be at least 1 sat/vB");
+    }
+    // 有效金额：扣除花费该输入本身的手续费，不足以支付自身费用的候选不参与
+    let effective: Vec<(usize, u64)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, candidate)| {
+            candidate
+                .value
+                .checked_sub(fee_for_weight(candidate.weight, target.fee_rate))
+                .filter(|&value| value > 0)
+                .map(|value| (i, value))
+        })
+        .collect();
+
+    let base_fee = fee_for_weight(target.base_weight, target.fee_rate);
+    let needed = target.amount.checked_add(base_fee).ok_or("Amount too large")?;
+    let available = effective.iter().try_fold(0u64, |sum, &(_, value)| sum.checked_add(value));
+    if available.is_none_or(|available| available < needed) {
+        return Err("Insufficient funds");
+    }
+
+    let indices = match strategy {
+        Strategy::BranchAndBound => {
+            let cost_of_change = fee_for_weight(target.change_weight, target.fee_rate)
+                + fee_for_weight(target.change_spend_weight, target.fee_rate);
+            branch_and_bound(&effective, needed, cost_of_change)
+        }
+        Strategy::LargestFirst => None,
+    }
+    .unwrap_or_else(|| largest_first(&effective, needed));
+
+    finish(candidates, target, indices)
+}
+
+/// 按金额从大到小加入（金额相同时按原顺序）
+fn largest_first(effective: &[(usize, u64)], needed: u64) -> Vec<usize> {
+    let mut sorted = effective.to_vec();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut indices = Vec::new();
+    let mut total = 0u64;
+    for (i, value) in sorted {
+        if total >= needed {
+            break;
+        }
+        indices.push(i);
+        total += value;
+    }
+    indices
+}
+
+/// 分支定界（Bitcoin Core 的 SelectCoinsBnB）
+///
+/// 在 [needed, needed + cost_of_change] 范围内寻找有效金额之和超出最少的组合，
+/// 多出的部分直接作为手续费，省去找零输出。
+fn branch_and_bound(effective: &[(usize, u64)], needed: u64, cost_of_change: u64) -> Option<Vec<usize>> {
+    let mut pool = effective.to_vec();
+    pool.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let upper = needed.saturating_add(cost_of_change);
+
+    let mut available: u64 = pool.iter().map(|&(_, value)| value).sum();
+    let mut value = 0u64;
+    let mut selection: Vec<usize> = Vec::new();
+    let mut best: Option<(Vec<usize>, u64)> = None;
+    let mut index = 0;
+
+    for _ in 0..BNB_MAX_TRIES {
+        let backtrack = if value + available < needed || value > upper {
+            true
+        } else if value >= needed {
+            let excess = value - needed;
+            if best.as_ref().is_none_or(|(_, best_excess)| excess < *best_excess) {
+                best = Some((selection.clone(), excess));
+            }
+            true
+        } else {
+            false
+        };
+
+        if backtrack {
+            let last = match selection.last() {
+                Some(&last) => last,
+                None => break,
+            };
+            // 退回到最后一个选中的位置，把途中跳过的候选重新计入可用金额
+            while index - 1 > last {
+                index -= 1;
+                available += pool[index].1;
+            }
+            index = last;
+            selection.pop();
+            value -= pool[index].1;
+        } else {
+            available -= pool[index].1;
+            // 上一个候选被排除且金额相同时同样排除，避免重复搜索等价分支
+            if selection
+                .last()
+                .is_none_or(|&last| last == index - 1 || pool[index].1 != pool[index - 1].1)
+            {
+                selection.push(index);
+                value += pool[index].1;
+            }
+        }
+        index += 1;
+    }
+
+    best.map(|(selection, _)| selection.into_iter().map(|i| pool[i].0).collect())
+}
+
+/// 计算手续费并决定是否创建找零
+fn finish(candidates: &[Candidate], target: &Target, mut indices: Vec<usize>) -> Result<Selection, &'static str> {
+    indices.sort_unstable();
+
+    let mut total = 0u64;
+    for &i in &indices {
+        total = total.checked_add(candidates[i].value).ok_or("Amount too large")?;
+    }
+    let input_fee: u64 = indices
+        .iter()
+        .map(|&i| fee_for_weight(candidates[i].weight, target.fee_rate))
+        .sum();
+    let fee = fee_for_weight(target.base_weight, target.fee_rate) + input_fee;
+    let excess = total
+        .checked_sub(target.amount)
+        .and_then(|rest| rest.checked_sub(fee))
+        .ok_or("Insufficient funds")?;
+
+    let change_fee = fee_for_weight(target.change_weight, target.fee_rate);
+    match excess.checked_sub(change_fee) {
+        Some(change) if change >= target.change_dust => Ok(Selection {
+            indices,
+            fee: fee + change_fee,
+            change: Some(change),
+        }),
+        _ => Ok(Selection {
+            indices,
+            fee: fee + excess,
+            change: None,
+        }),
+    }
+}
@@ -18,6 +18,7 @@ impl<const N: usize> FixedString<N> {
     }
 
     /// 从字符串切片创建（如果长度超过容量则截断）
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self, &'static str> {
         let bytes = s.as_bytes();
         if bytes.len() > N {
@@ -112,6 +113,12 @@ impl<const N: usize> fmt::Display for FixedString<N> {
     }
 }
 
+impl<const N: usize> fmt::Write for FixedString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
 impl<const N: usize> fmt::Debug for FixedString<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "\"{}\"", self.as_str())
@@ -1,21 +1,51 @@
-use core::ffi::c_void;
+use core::ffi::{c_void, CStr};
+use flipperzero::dialogs::{DialogFileBrowserOptions, DialogsApp};
+use flipperzero::furi::string::FuriString;
 use flipperzero_sys as sys;
 extern crate alloc;
 use super::qrcodegen::{DataTooLong, QrCode, QrCodeEcc};
+use crate::address::{AddressType, Cryptocurrency};
+use crate::base58;
 use crate::bip39::{entropy_to_mnemonic, MnemonicType};
+use crate::coin_selection::Strategy;
+use crate::fixed_string::FixedString;
 use crate::flipper_wallet_core::Wallet;
 use crate::hex;
+use crate::network::Network;
+use crate::psbt::{self, Psbt};
+use crate::secp256k1::SecretKey;
+use crate::spend::{self, Spend, SpendRequest};
 use crate::trng;
+use crate::utxo::{self, Utxo};
+use crate::wif;
 use crate::word_list::ENGLISH_WORD_LIST;
 use alloc::vec::Vec;
 
 const MAX_MNEMONIC_LEN: usize = 256;
 const MAX_PASSPHRASE_LEN: usize = 64;
+const MAX_WIF_LEN: usize = 64;
 const MAIN_MENU_VISIBLE: usize = 4;
 const SETTINGS_VISIBLE: usize = 4;
 const MNEMONIC_VISIBLE: usize = 4;
 const SUGGESTION_MAX: usize = 8;
 const SUGGESTION_VISIBLE: usize = 4;
+const REVIEW_VISIBLE: usize = 4;
+// characters per line on the transaction review screens (FontSecondary)
+const REVIEW_LINE_CHARS: usize = 21;
+// folder the file browser opens for PSBT and UTXO files
+const APP_DATA_DIR: &CStr = c"/ext/apps_data/flipperwallet";
+// send form fields: destination, amount (sat), fee rate (sat/vB), change index
+const SPEND_TO: usize = 0;
+const SPEND_AMOUNT: usize = 1;
+const SPEND_FEE_RATE: usize = 2;
+const SPEND_CHANGE: usize = 3;
+const SPEND_FIELD_COUNT: usize = 4;
+const MAX_SPEND_FIELD_LEN: usize = 96;
+// characters offered when typing a destination (bech32 is lowercase, base58 is mixed case)
+const ADDRESS_CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGIT_CHARSET: &[u8] = b"0123456789";
+// largest QR that fits the 64 px screen at one pixel per module
+const QR_MAX_MODULES: i32 = 62;
 // (multi-row keyboard definitions removed; using physical-key mapping CHARSET instead)
 
 // linear charset used when no suggestions present (letters, dash, underscore, space, digits)
@@ -43,6 +73,10 @@ pub enum Screen {
     ShowMnemonic = 5,
     About = 6,
     ConfirmAction = 7,
+    SignPsbt = 8,
+    Spend = 9,
+    SpendReview = 10,
+    SpendQr = 11,
 }
 
 #[repr(C)]
@@ -62,6 +96,8 @@ pub enum ConfirmAction {
     ClearPassphrase = 3,
     RevealPrivate = 4,
     SaveWallet = 5,
+    SignPsbt = 6,
+    SaveTransaction = 7,
 }
 
 #[repr(C)]
@@ -95,6 +131,8 @@ pub struct AppState {
     // Wallet configuration
     pub bip39_word_count: usize,
     pub selected_crypto: usize,
+    // network used for newly created/imported wallets (saved with each wallet)
+    pub network: Network,
 
     // Passphrase storage
     pub passphrase_buffer: [u8; MAX_PASSPHRASE_LEN],
@@ -129,6 +167,11 @@ pub struct AppState {
     pub suggestion_selected: usize,
     // whether we're editing the BIP39 passphrase instead of a mnemonic word
     pub editing_passphrase: bool,
+    // WIF private key being typed on the import screen (base58 characters)
+    pub wif_buffer: [u8; MAX_WIF_LEN],
+    pub wif_len: usize,
+    pub editing_wif: bool,
+    pub wif_error: bool,
     // whether to use the system keyboard/dialog for text entry (hide custom hints)
     pub use_system_keyboard: bool,
     // one-shot shift (Aa) state for keyboard (uppercase next char)
@@ -163,6 +206,29 @@ pub struct AppState {
     pub title_scroll_tick: u32,
     // mnemonic view state
     pub showing_qr: bool,
+    // PSBT signing: the file is picked and signed from the main loop, not the input callback
+    pub psbt_pick_requested: bool,
+    pub psbt_sign_requested: bool,
+    pub psbt: Option<Psbt>,
+    pub psbt_binary: bool,
+    pub psbt_path: alloc::string::String,
+    // lines shown on the transaction review screen (outputs, fee, then the result)
+    pub review_lines: Vec<alloc::string::String>,
+    pub review_scroll: usize,
+    // Sending from a UTXO file: pick, build and save also run from the main loop
+    pub spend_pick_requested: bool,
+    pub spend_build_requested: bool,
+    pub spend_save_requested: bool,
+    pub utxo_path: alloc::string::String,
+    pub utxos: Vec<Utxo>,
+    pub spend_fields: [[u8; MAX_SPEND_FIELD_LEN]; SPEND_FIELD_COUNT],
+    pub spend_field_lens: [usize; SPEND_FIELD_COUNT],
+    pub spend_field: usize,
+    pub editing_spend: bool,
+    // signed transaction kept in memory until the user confirms saving it
+    pub spend: Option<Spend>,
+    pub spend_qr: Option<QrCode>,
+    pub spend_show_text: bool,
 }
 
 impl AppState {
@@ -177,6 +243,7 @@ impl AppState {
             mnemonic_scroll: 0,
             bip39_word_count: 12,
             selected_crypto: 0,
+            network: Network::Mainnet,
             passphrase_buffer: [0u8; MAX_PASSPHRASE_LEN],
             passphrase_len: 0,
             input_mode: InputMode::Navigation,
@@ -197,6 +264,10 @@ impl AppState {
             suggestion_total: 0,
             suggestion_selected: 0,
             editing_passphrase: false,
+            wif_buffer: [0u8; MAX_WIF_LEN],
+            wif_len: 0,
+            editing_wif: false,
+            wif_error: false,
             use_system_keyboard: false,
             shift_enabled: false,
             keyboard_index: 0,
@@ -221,6 +292,25 @@ impl AppState {
             last_saved_aes: [0u8; 32],
             last_saved_aes_len: 0,
             last_saved_path: alloc::string::String::new(),
+            psbt_pick_requested: false,
+            psbt_sign_requested: false,
+            psbt: None,
+            psbt_binary: false,
+            psbt_path: alloc::string::String::new(),
+            review_lines: Vec::new(),
+            review_scroll: 0,
+            spend_pick_requested: false,
+            spend_build_requested: false,
+            spend_save_requested: false,
+            utxo_path: alloc::string::String::new(),
+            utxos: Vec::new(),
+            spend_fields: [[0u8; MAX_SPEND_FIELD_LEN]; SPEND_FIELD_COUNT],
+            spend_field_lens: [0usize; SPEND_FIELD_COUNT],
+            spend_field: 0,
+            editing_spend: false,
+            spend: None,
+            spend_qr: None,
+            spend_show_text: false,
         }
     }
 
@@ -422,15 +512,27 @@ pub extern "C" fn app_main() -> i32 {
                     Ok(s) => s.trim(),
                     Err(_) => "",
                 };
-                if !mnemonic_str.is_empty() {
+                // A generated private key is stored as WIF instead of a mnemonic
+                let wif_str = match generated_secret_key(&state) {
+                    Some(sk) => wif::encode(&sk, Cryptocurrency::Bitcoin, state.network, true)
+                        .unwrap_or_default(),
+                    None => FixedString::new(),
+                };
+                if !wif_str.is_empty() || (!state.show_private && !mnemonic_str.is_empty()) {
                     let wdata = crate::storage::WalletData {
                         name: alloc::format!("wallet-{}", state.wallets.len() + 1),
-                        mnemonic: alloc::string::String::from(mnemonic_str),
+                        mnemonic: if wif_str.is_empty() {
+                            alloc::string::String::from(mnemonic_str)
+                        } else {
+                            alloc::string::String::new()
+                        },
                         passphrase: alloc::string::String::from(
                             core::str::from_utf8(&state.passphrase_buffer[..state.passphrase_len])
                                 .unwrap_or(""),
                         ),
                         word_count: state.bip39_word_count as u16,
+                        network: state.network,
+                        wif: alloc::string::String::from(wif_str.as_str()),
                     };
 
                     // generate AES passphrase and encrypt
@@ -456,15 +558,7 @@ pub extern "C" fn app_main() -> i32 {
                                 filename.trim_end_matches(char::from(0)),
                             );
                             // Also add wallet into in-memory list
-                            if let Ok(mut wallet) = Wallet::from_mnemonic(
-                                wdata.mnemonic.as_str(),
-                                wdata.passphrase.as_str(),
-                            ) {
-                                let _ = wallet.add_account(
-                                    crate::address::Cryptocurrency::Bitcoin,
-                                    0,
-                                    0,
-                                );
+                            if let Some(wallet) = wallet_from_data(&wdata) {
                                 state.wallets.push(wallet);
                                 state.current_wallet = state.wallets.len().saturating_sub(1);
                             }
@@ -475,15 +569,7 @@ pub extern "C" fn app_main() -> i32 {
                         }
                         Err(_) => {
                             // persist failed (platform not implemented). Still add wallet to in-memory list
-                            if let Ok(mut wallet) = Wallet::from_mnemonic(
-                                wdata.mnemonic.as_str(),
-                                wdata.passphrase.as_str(),
-                            ) {
-                                let _ = wallet.add_account(
-                                    crate::address::Cryptocurrency::Bitcoin,
-                                    0,
-                                    0,
-                                );
+                            if let Some(wallet) = wallet_from_data(&wdata) {
                                 state.wallets.push(wallet);
                                 state.current_wallet = state.wallets.len().saturating_sub(1);
                             }
@@ -504,6 +590,77 @@ pub extern "C" fn app_main() -> i32 {
                 state.save_in_progress = false;
             }
 
+            // The file browser takes over the screen until the user picks a PSBT or backs out
+            if state.psbt_pick_requested {
+                state.psbt_pick_requested = false;
+
+                let null_ctx: *mut c_void = core::ptr::null_mut();
+                sys::view_port_draw_callback_set(viewport, None, null_ctx);
+                sys::view_port_input_callback_set(viewport, None, null_ctx);
+
+                match pick_file(c"psbt") {
+                    Some(path) => load_psbt(&mut state, path),
+                    None => state.current_screen = Screen::MainMenu,
+                }
+
+                sys::view_port_draw_callback_set(viewport, Some(draw_callback), state_ptr);
+                sys::view_port_input_callback_set(viewport, Some(input_callback), state_ptr);
+            }
+
+            // Signing derives keys and runs ECDSA/Schnorr for every input, so it runs here
+            if state.psbt_sign_requested {
+                // redraw first so the review screen shows "Signing..."
+                sys::view_port_update(viewport);
+
+                let null_ctx: *mut c_void = core::ptr::null_mut();
+                sys::view_port_draw_callback_set(viewport, None, null_ctx);
+                sys::view_port_input_callback_set(viewport, None, null_ctx);
+
+                sign_loaded_psbt(&mut state);
+                state.psbt_sign_requested = false;
+
+                sys::view_port_draw_callback_set(viewport, Some(draw_callback), state_ptr);
+                sys::view_port_input_callback_set(viewport, Some(input_callback), state_ptr);
+            }
+
+            // Same for the UTXO list to spend from
+            if state.spend_pick_requested {
+                state.spend_pick_requested = false;
+
+                let null_ctx: *mut c_void = core::ptr::null_mut();
+                sys::view_port_draw_callback_set(viewport, None, null_ctx);
+                sys::view_port_input_callback_set(viewport, None, null_ctx);
+
+                match pick_file(c"*") {
+                    Some(path) => load_utxos(&mut state, path),
+                    None => state.current_screen = Screen::MainMenu,
+                }
+
+                sys::view_port_draw_callback_set(viewport, Some(draw_callback), state_ptr);
+                sys::view_port_input_callback_set(viewport, Some(input_callback), state_ptr);
+            }
+
+            // Coin selection and signing, or saving the result, after the user asked for it
+            if state.spend_build_requested || state.spend_save_requested {
+                // redraw first so the screen shows "Building..." / "Saving..."
+                sys::view_port_update(viewport);
+
+                let null_ctx: *mut c_void = core::ptr::null_mut();
+                sys::view_port_draw_callback_set(viewport, None, null_ctx);
+                sys::view_port_input_callback_set(viewport, None, null_ctx);
+
+                if state.spend_build_requested {
+                    build_spend(&mut state);
+                    state.spend_build_requested = false;
+                } else {
+                    save_spend(&mut state);
+                    state.spend_save_requested = false;
+                }
+
+                sys::view_port_draw_callback_set(viewport, Some(draw_callback), state_ptr);
+                sys::view_port_input_callback_set(viewport, Some(input_callback), state_ptr);
+            }
+
             // advance title scroll tick and update offset if on main menu
             state.title_scroll_tick = state.title_scroll_tick.wrapping_add(1);
             if state.current_screen == Screen::MainMenu {
@@ -576,6 +733,10 @@ extern "C" fn draw_callback(canvas: *mut sys::Canvas, ctx: *mut c_void) {
             Screen::ShowMnemonic => draw_show_mnemonic(canvas, state),
             Screen::About => draw_about(canvas, state),
             Screen::ConfirmAction => draw_confirm_dialog(canvas, state),
+            Screen::SignPsbt => draw_sign_psbt(canvas, state),
+            Screen::Spend => draw_spend(canvas, state),
+            Screen::SpendReview => draw_spend_review(canvas, state),
+            Screen::SpendQr => draw_spend_qr(canvas, state),
         }
     }
 }
@@ -598,6 +759,8 @@ unsafe fn draw_main_menu(canvas: *mut sys::Canvas, state: &AppState) {
         b"Create Wallet\0",
         b"Import Wallet\0",
         b"View Wallets \0",
+        b"Sign PSBT    \0",
+        b"Send Bitcoin \0",
         b"Settings     \0",
         b"About        \0",
     ];
@@ -607,7 +770,7 @@ unsafe fn draw_main_menu(canvas: *mut sys::Canvas, state: &AppState) {
         canvas,
         state,
         items_refs,
-        5,
+        items.len(),
         MAIN_MENU_VISIBLE,
         3,
         title_height + 6,
@@ -838,6 +1001,11 @@ unsafe fn draw_import_wallet(canvas: *mut sys::Canvas, state: &AppState) {
         return;
     }
 
+    if state.input_mode == InputMode::TextInput && state.editing_wif {
+        draw_wif_input(canvas, state, title_height);
+        return;
+    }
+
     // If user is typing a word (TextInput), show input field and suggestions
     if state.input_mode == InputMode::TextInput {
         {
@@ -1084,6 +1252,7 @@ unsafe fn draw_import_wallet(canvas: *mut sys::Canvas, state: &AppState) {
         b"Enter Mnemonic  \0",
         b"Enter Passphrase\0",
         b"Confirm Import  \0",
+        b"Import WIF Key  \0",
     ];
 
     let items_refs: &[&[u8]] = &items.map(|s| s.as_slice());
@@ -1091,7 +1260,7 @@ unsafe fn draw_import_wallet(canvas: *mut sys::Canvas, state: &AppState) {
         canvas,
         state,
         items_refs,
-        3,
+        4,
         MAIN_MENU_VISIBLE,
         3,
         title_height + 6,
@@ -1106,6 +1275,44 @@ unsafe fn draw_import_wallet(canvas: *mut sys::Canvas, state: &AppState) {
     }
 }
 
+// Draw the WIF entry field: the tail of the typed key on two lines, the selected
+// base58 character and key hints.
+unsafe fn draw_wif_input(canvas: *mut sys::Canvas, state: &AppState, title_height: i32) {
+    sys::canvas_set_color(canvas, sys::ColorBlack);
+    sys::canvas_draw_str(
+        canvas,
+        8,
+        title_height + 12,
+        b"Enter WIF key\0".as_ptr() as *const u8,
+    );
+
+    // show at most two lines, scrolled so the latest characters stay visible
+    let per_line = 19usize;
+    let typed = &state.wif_buffer[..state.wif_len];
+    let start = typed.len().saturating_sub(per_line * 2) / per_line * per_line;
+    for (i, line) in typed[start..].chunks(per_line).enumerate() {
+        let mut buf: [u8; 24] = [0u8; 24];
+        buf[..line.len()].copy_from_slice(line);
+        let y = title_height + 24 + (i as i32) * 10;
+        sys::canvas_draw_str(canvas, 8, y, buf.as_ptr() as *const u8);
+    }
+
+    let y = 64 - 10;
+    if state.wif_error {
+        sys::canvas_draw_str(canvas, 8, y, b"Invalid WIF key\0".as_ptr() as *const u8);
+    } else {
+        let chars = base58::BITCOIN.chars();
+        let cb = [b'C', b'h', b'a', b'r', b':', b' ', chars[state.char_index % chars.len()], 0];
+        sys::canvas_draw_str(canvas, 8, y, cb.as_ptr() as *const u8);
+    }
+    sys::canvas_draw_str(
+        canvas,
+        8,
+        y + 9,
+        b"OK:Ins L:Del R:Import\0".as_ptr() as *const u8,
+    );
+}
+
 unsafe fn draw_view_wallets(canvas: *mut sys::Canvas, state: &AppState) {
     sys::canvas_set_font(canvas, sys::FontSecondary);
     sys::canvas_set_color(canvas, sys::ColorBlack);
@@ -1137,11 +1344,18 @@ unsafe fn draw_view_wallets(canvas: *mut sys::Canvas, state: &AppState) {
     sys::canvas_draw_box(canvas, 6, title_box_top, header_w, title_box_h as usize);
     sys::canvas_set_color(canvas, sys::ColorWhite);
     // header text baseline positioned inside header box
+    // single-key wallets hold addresses for every coin, not just BTC
+    let label = if wallet.is_single_key() { "Key" } else { "BTC" };
+    let header = if wallet.network().is_mainnet() {
+        alloc::format!("View {} wallet\0", label)
+    } else {
+        alloc::format!("{} {} wallet\0", label, wallet.network().display_name())
+    };
     sys::canvas_draw_str(
         canvas,
         8,
         title_box_top + (title_box_h as i32) - 4,
-        b"View BTC wallet\0".as_ptr() as *const u8,
+        header.as_ptr() as *const u8,
     );
 
     // list starts below header
@@ -1190,7 +1404,7 @@ unsafe fn draw_settings(canvas: *mut sys::Canvas, state: &AppState) {
     sys::canvas_set_font(canvas, sys::FontSecondary);
     sys::canvas_set_color(canvas, sys::ColorBlack);
 
-    let title = b"Mnemonic Phrase Settings\0";
+    let title = b"Wallet Settings\0";
     let title_box_top: i32 = 6;
     let title_box_h: usize = 12;
 
@@ -1202,7 +1416,8 @@ unsafe fn draw_settings(canvas: *mut sys::Canvas, state: &AppState) {
     );
 
     let word_counts = [12usize, 15, 18, 21, 24];
-    let total = 5;
+    // word count options first, then one entry per network
+    let total = word_counts.len() + Network::ALL.len();
     let visible = SETTINGS_VISIBLE;
     let start_y = title_box_top + title_box_h as i32;
     let line_height = 12i32;
@@ -1238,20 +1453,25 @@ unsafe fn draw_settings(canvas: *mut sys::Canvas, state: &AppState) {
             sys::canvas_set_color(canvas, sys::ColorBlack);
         }
 
-        let checkbox = if state.bip39_word_count == word_counts[idx] {
-            b"[X] \0"
+        let (selected, label) = if idx < word_counts.len() {
+            let label = match word_counts[idx] {
+                12 => "12-word mnemonic",
+                15 => "15-word mnemonic",
+                18 => "18-word mnemonic",
+                21 => "21-word mnemonic",
+                24 => "24-word mnemonic",
+                _ => "Unknown",
+            };
+            (state.bip39_word_count == word_counts[idx], alloc::string::String::from(label))
         } else {
-            b"[ ] \0"
-        };
-
-        let label = match word_counts[idx] {
-            12 => b"12-word mnemonic\0",
-            15 => b"15-word mnemonic\0",
-            18 => b"18-word mnemonic\0",
-            21 => b"21-word mnemonic\0",
-            24 => b"24-word mnemonic\0",
-            _ => b"Unknown         \0",
+            let network = Network::ALL[idx - word_counts.len()];
+            (
+                state.network == network,
+                alloc::format!("Net: {}", network.display_name()),
+            )
         };
+        let checkbox = if selected { b"[X] \0" } else { b"[ ] \0" };
+        let label = alloc::format!("{}\0", label);
 
         let text_y = box_top + (box_h as i32) - 6;
         sys::canvas_draw_str(canvas, 8, text_y, checkbox.as_ptr() as *const u8);
@@ -1342,10 +1562,20 @@ unsafe fn draw_show_mnemonic(canvas: *mut sys::Canvas, state: &AppState) {
 
         // Toggle between hex view and QR view using showing_qr
         if state.showing_qr {
-            // Encode private key as hex string for QR compatibility, then encode bytes of that hex string
-            let hex_str = hex::encode(&state.private_key[..state.private_key_len]);
-            let data = hex_str.as_bytes();
-            let ecc_order = [QrCodeEcc::Quartile, QrCodeEcc::Medium, QrCodeEcc::Low];
+            // Encode private key as a compressed Bitcoin WIF for the selected network so
+            // other wallets can sweep it directly
+            let mut key_bytes = [0u8; 32];
+            key_bytes[..state.private_key_len].copy_from_slice(&state.private_key[..state.private_key_len]);
+            let wif_str = SecretKey::from_bytes(&key_bytes)
+                .and_then(|sk| wif::encode(&sk, Cryptocurrency::Bitcoin, state.network, true))
+                .unwrap_or_default();
+            let data = wif_str.as_str().as_bytes();
+            // an empty string means the key could not be encoded; skip straight to the error dialog
+            let ecc_order: &[QrCodeEcc] = if data.is_empty() {
+                &[]
+            } else {
+                &[QrCodeEcc::Quartile, QrCodeEcc::Medium, QrCodeEcc::Low]
+            };
             let mut qr_opt: Option<QrCode> = None;
             for &ecc in ecc_order.iter() {
                 match QrCode::encode_binary(data, ecc) {
@@ -1808,8 +2038,12 @@ unsafe fn draw_show_mnemonic(canvas: *mut sys::Canvas, state: &AppState) {
     }
 
     if !state.wallets.is_empty() {
-        if let Some(wallet) = state.wallets.get(state.current_wallet) {
-            let secret_bytes = wallet.master_key.secret_key().as_bytes();
+        if let Some(master_key) = state
+            .wallets
+            .get(state.current_wallet)
+            .and_then(|wallet| wallet.master_key())
+        {
+            let secret_bytes = master_key.secret_key().as_bytes();
             let hex_str = hex::encode(secret_bytes);
             let hex_bytes = hex_str.as_str().as_bytes();
             let label_y = start_y + (visible as i32) * line_h + 4;
@@ -1904,6 +2138,159 @@ unsafe fn draw_pseudo_qr(
     }
 }
 
+unsafe fn draw_sign_psbt(canvas: *mut sys::Canvas, state: &AppState) {
+    let hint: &[u8] = if state.psbt_sign_requested {
+        b"Signing...\0"
+    } else if state.psbt.is_some() {
+        b"OK:Sign Back:Cancel\0"
+    } else {
+        b"Back: Done\0"
+    };
+    draw_review(canvas, state, b"Review PSBT\0", hint);
+}
+
+unsafe fn draw_spend(canvas: *mut sys::Canvas, state: &AppState) {
+    sys::canvas_set_font(canvas, sys::FontSecondary);
+    sys::canvas_set_color(canvas, sys::ColorBlack);
+
+    let title_height = 8;
+    let title = alloc::format!("Send ({} UTXOs)\0", state.utxos.len());
+    sys::canvas_draw_str(canvas, 8, title_height, title.as_ptr() as *const u8);
+
+    let labels = ["To", "Amount", "Fee rate", "Change #"];
+    let placeholders = ["address", "sat", "sat/vB", "auto"];
+    let start_y = title_height + 12;
+    let line_h = 10i32;
+    // values longer than the column show their end, so the last typed characters stay visible
+    let value_chars = 12usize;
+    for field in 0..SPEND_FIELD_COUNT {
+        let y = start_y + (field as i32) * line_h;
+        if state.spend_field == field {
+            sys::canvas_set_color(canvas, sys::ColorBlack);
+            sys::canvas_draw_box(canvas, 4, y - 8, 118, line_h as usize);
+            sys::canvas_set_color(canvas, sys::ColorWhite);
+        } else {
+            sys::canvas_set_color(canvas, sys::ColorBlack);
+        }
+        let label = alloc::format!("{}\0", labels[field]);
+        sys::canvas_draw_str(canvas, 8, y, label.as_ptr() as *const u8);
+
+        let value = spend_field(state, field);
+        let value = if value.is_empty() {
+            alloc::format!("{}\0", placeholders[field])
+        } else if value.len() > value_chars {
+            alloc::format!("..{}\0", &value[value.len() - (value_chars - 2)..])
+        } else {
+            alloc::format!("{}\0", value)
+        };
+        sys::canvas_draw_str(canvas, 56, y, value.as_ptr() as *const u8);
+    }
+
+    sys::canvas_set_color(canvas, sys::ColorBlack);
+    let hint = if state.spend_pick_requested {
+        alloc::string::String::from("Loading...\0")
+    } else if state.spend_build_requested {
+        alloc::string::String::from("Building...\0")
+    } else if state.editing_spend {
+        let charset = spend_charset(state.spend_field);
+        let ch = charset[state.char_index % charset.len()] as char;
+        alloc::format!("Char:{} OK:Add L:Del\0", ch)
+    } else {
+        alloc::string::String::from("OK:Edit R:Build\0")
+    };
+    sys::canvas_draw_str(canvas, 8, 62, hint.as_ptr() as *const u8);
+}
+
+unsafe fn draw_spend_review(canvas: *mut sys::Canvas, state: &AppState) {
+    let hint: &[u8] = if state.spend_save_requested {
+        b"Saving...\0"
+    } else if state.spend.is_some() {
+        b"OK:Save Back:Edit\0"
+    } else {
+        b"Back: Edit\0"
+    };
+    draw_review(canvas, state, b"Review transaction\0", hint);
+}
+
+// Raw signed transaction as a QR for broadcasting from another device,
+// or the saved file path (and any error) when the QR is hidden or too large.
+unsafe fn draw_spend_qr(canvas: *mut sys::Canvas, state: &AppState) {
+    let qr = match &state.spend_qr {
+        Some(qr) if !state.spend_show_text => qr,
+        _ => {
+            let hint: &[u8] = if state.spend_qr.is_some() {
+                b"R:QR Back:Done\0"
+            } else {
+                b"Back: Done\0"
+            };
+            draw_review(canvas, state, b"Signed transaction\0", hint);
+            return;
+        }
+    };
+
+    sys::canvas_set_font(canvas, sys::FontSecondary);
+    sys::canvas_set_color(canvas, sys::ColorBlack);
+    let size = qr.size();
+    let cell = core::cmp::max(1, 64 / size);
+    let total_px = size * cell;
+    // QR on the right, hints in the space to its left
+    let qr_x = 128 - total_px - 1;
+    let qr_y = (64 - total_px) / 2;
+    for r in 0..size {
+        for c in 0..size {
+            if qr.get_module(c, r) {
+                sys::canvas_draw_box(
+                    canvas,
+                    qr_x + c * cell,
+                    qr_y + r * cell,
+                    cell as usize,
+                    cell as usize,
+                );
+            }
+        }
+    }
+    sys::canvas_draw_str(canvas, 2, 10, b"Raw tx\0".as_ptr() as *const u8);
+    sys::canvas_draw_str(canvas, 2, 50, b"L:Info\0".as_ptr() as *const u8);
+    sys::canvas_draw_str(canvas, 2, 60, b"Back:Done\0".as_ptr() as *const u8);
+}
+
+// Scrollable list of review_lines under a title, with a key hint on the last line
+unsafe fn draw_review(canvas: *mut sys::Canvas, state: &AppState, title: &[u8], hint: &[u8]) {
+    sys::canvas_set_font(canvas, sys::FontSecondary);
+    sys::canvas_set_color(canvas, sys::ColorBlack);
+
+    let title_height = 8;
+    sys::canvas_draw_str(canvas, 8, title_height, title.as_ptr() as *const u8);
+
+    let start_y = title_height + 12;
+    let line_h = 10i32;
+    let total = state.review_lines.len();
+    for (i, line) in state
+        .review_lines
+        .iter()
+        .skip(state.review_scroll)
+        .take(REVIEW_VISIBLE)
+        .enumerate()
+    {
+        let buf = alloc::format!("{}\0", line);
+        let y = start_y + (i as i32) * line_h;
+        sys::canvas_draw_str(canvas, 8, y, buf.as_ptr() as *const u8);
+    }
+    if total > REVIEW_VISIBLE {
+        draw_scrollbar(
+            canvas,
+            state.review_scroll,
+            REVIEW_VISIBLE,
+            total,
+            122,
+            start_y - 8,
+            line_h,
+        );
+    }
+
+    sys::canvas_draw_str(canvas, 8, 62, hint.as_ptr() as *const u8);
+}
+
 unsafe fn draw_about(canvas: *mut sys::Canvas, state: &AppState) {
     sys::canvas_set_font(canvas, sys::FontSecondary);
     sys::canvas_set_color(canvas, sys::ColorBlack);
@@ -1946,6 +2333,8 @@ unsafe fn draw_confirm_dialog(canvas: *mut sys::Canvas, state: &AppState) {
         ConfirmAction::SaveWallet => b"Save wallet to SD? \0",
         ConfirmAction::ClearPassphrase => b"Clear passphrase   \0",
         ConfirmAction::RevealPrivate => b"Reveal Private Key?\0",
+        ConfirmAction::SignPsbt => b"Sign transaction?  \0",
+        ConfirmAction::SaveTransaction => b"Save signed tx?    \0",
         ConfirmAction::None => b"Confirm?           \0",
     };
 
@@ -2077,6 +2466,320 @@ unsafe fn draw_menu_list(
     }
 }
 
+// Put the wallet on its network, then add the first Bitcoin receive address of
+// every supported address type (legacy, nested SegWit, native SegWit, Taproot).
+// Single-key wallets also get the legacy address of every other coin, since the
+// same key is valid on all of them; coins without this network are skipped.
+fn add_default_accounts(wallet: &mut Wallet, network: Network) {
+    let _ = wallet.set_network(network);
+    for address_type in [
+        AddressType::P2pkh,
+        AddressType::P2shP2wpkh,
+        AddressType::P2wpkh,
+        AddressType::P2tr,
+    ] {
+        let _ = wallet.add_account(Cryptocurrency::Bitcoin, address_type, 0, 0);
+    }
+    if wallet.is_single_key() {
+        for crypto in Cryptocurrency::ALL {
+            if crypto != Cryptocurrency::Bitcoin && crypto.supports_network(network) {
+                let _ = wallet.add_account(crypto, AddressType::P2pkh, 0, 0);
+            }
+        }
+    }
+}
+
+// The freshly generated private key, if the create screen is showing one.
+fn generated_secret_key(state: &AppState) -> Option<SecretKey> {
+    if !state.show_private || state.private_key_len != 32 {
+        return None;
+    }
+    let mut key_bytes = [0u8; 32];
+    key_bytes.copy_from_slice(&state.private_key[..32]);
+    SecretKey::from_bytes(&key_bytes).ok()
+}
+
+// Rebuild an in-memory wallet (with its default accounts) from stored wallet data.
+fn wallet_from_data(wdata: &crate::storage::WalletData) -> Option<Wallet> {
+    let mut wallet = if wdata.wif.is_empty() {
+        Wallet::from_mnemonic(wdata.mnemonic.as_str(), wdata.passphrase.as_str()).ok()?
+    } else {
+        Wallet::from_wif(wdata.wif.as_str()).ok()?
+    };
+    add_default_accounts(&mut wallet, wdata.network);
+    Some(wallet)
+}
+
+// Let the user pick a file in the app data folder with the system file browser.
+// Blocks until a file is picked or the browser is closed, so call it from the main loop.
+fn pick_file(extension: &CStr) -> Option<alloc::string::String> {
+    let mut dialogs = DialogsApp::open();
+    // SAFETY: the extension and base path are ASCII
+    let options =
+        unsafe { DialogFileBrowserOptions::with_extension(extension).set_base_path(APP_DATA_DIR) }
+            .set_hide_dot_files(true);
+    let mut start = FuriString::from(APP_DATA_DIR);
+    let picked = dialogs.show_file_browser(Some(&mut start), Some(&options))?;
+    core::str::from_utf8(picked.to_bytes())
+        .ok()
+        .map(alloc::string::String::from)
+}
+
+// Amounts on the review screens are shown in BTC with all 8 decimals
+fn format_btc(sats: u64) -> alloc::string::String {
+    alloc::format!("{}.{:08} BTC", sats / 100_000_000, sats % 100_000_000)
+}
+
+// Split text that does not fit on one line (addresses, paths, errors) over several lines
+fn push_wrapped(lines: &mut Vec<alloc::string::String>, text: &str) {
+    let bytes = text.as_bytes();
+    for chunk in bytes.chunks(REVIEW_LINE_CHARS) {
+        lines.push(alloc::string::String::from_utf8_lossy(chunk).into_owned());
+    }
+}
+
+// Parse the picked PSBT and list what it spends to: every output with its
+// amount and address (our own change is labelled), then the fee.
+fn load_psbt(state: &mut AppState, path: alloc::string::String) {
+    state.psbt = None;
+    state.review_lines.clear();
+    state.review_scroll = 0;
+
+    let wallet = match state.wallets.get(state.current_wallet) {
+        Some(wallet) => wallet,
+        None => {
+            state.review_lines.push("No wallet loaded".into());
+            return;
+        }
+    };
+    let (psbt, binary) = match psbt::load(&path) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            state.review_lines.push("Cannot read PSBT:".into());
+            push_wrapped(&mut state.review_lines, e);
+            return;
+        }
+    };
+
+    for index in 0..psbt.outputs.len() {
+        let review = match psbt.review_output(index, wallet) {
+            Ok(review) => review,
+            Err(e) => {
+                push_wrapped(&mut state.review_lines, e);
+                continue;
+            }
+        };
+        let label = if review.is_change { "Change" } else { "Send" };
+        state
+            .review_lines
+            .push(alloc::format!("{} {}", label, format_btc(review.value)));
+        match review.address {
+            Some(address) => push_wrapped(
+                &mut state.review_lines,
+                &alloc::string::String::from_utf8_lossy(&address),
+            ),
+            None => state.review_lines.push(review.kind.display_name().into()),
+        }
+    }
+    match psbt.fee() {
+        Ok(fee) => {
+            let line = alloc::format!("Fee {}", format_btc(fee));
+            state.review_lines.push(line);
+        }
+        Err(e) => {
+            // without a trustworthy fee the transaction must not be signed
+            state.review_lines.push("Fee unknown:".into());
+            push_wrapped(&mut state.review_lines, e);
+            return;
+        }
+    }
+
+    state.psbt = Some(psbt);
+    state.psbt_binary = binary;
+    state.psbt_path = path;
+}
+
+// Sign the reviewed PSBT with the current wallet and write it next to the
+// original file; the review lines are replaced by the result.
+fn sign_loaded_psbt(state: &mut AppState) {
+    let result = match (state.psbt.as_mut(), state.wallets.get(state.current_wallet)) {
+        (Some(loaded), Some(wallet)) => loaded.sign(wallet).and_then(|signed| {
+            if signed == 0 {
+                return Err("No inputs could be signed");
+            }
+            psbt::save_signed(&state.psbt_path, loaded, state.psbt_binary)
+                .map(|path| (path, signed))
+        }),
+        _ => Err("No PSBT loaded"),
+    };
+
+    state.psbt = None;
+    state.review_lines.clear();
+    state.review_scroll = 0;
+    match result {
+        Ok((path, signed)) => {
+            state
+                .review_lines
+                .push(alloc::format!("Signed {} input(s)", signed));
+            state.review_lines.push("Saved to:".into());
+            push_wrapped(&mut state.review_lines, &path);
+        }
+        Err(e) => {
+            state.review_lines.push("Signing failed:".into());
+            push_wrapped(&mut state.review_lines, e);
+        }
+    }
+}
+
+// Text typed into one of the send form fields
+fn spend_field(state: &AppState, field: usize) -> &str {
+    core::str::from_utf8(&state.spend_fields[field][..state.spend_field_lens[field]]).unwrap_or("")
+}
+
+// Characters offered for a send form field: any address character, or digits only
+fn spend_charset(field: usize) -> &'static [u8] {
+    if field == SPEND_TO {
+        ADDRESS_CHARSET
+    } else {
+        DIGIT_CHARSET
+    }
+}
+
+// Parse the picked UTXO list (JSON or CSV) and open the send form for it
+fn load_utxos(state: &mut AppState, path: alloc::string::String) {
+    state.spend = None;
+    state.review_lines.clear();
+    state.review_scroll = 0;
+    match utxo::load(&path) {
+        Ok(utxos) => {
+            state.utxos = utxos;
+            state.utxo_path = path;
+            state.spend_field = SPEND_TO;
+            state.current_screen = Screen::Spend;
+        }
+        Err(e) => {
+            state.utxos.clear();
+            state.review_lines.push("Cannot read UTXOs:".into());
+            push_wrapped(&mut state.review_lines, e);
+            state.current_screen = Screen::SpendReview;
+        }
+    }
+}
+
+// Select coins, build and sign the transaction from the send form. Nothing
+// leaves the device yet: the review screen shows the result, and the
+// transaction is only written and shown as a QR after the user confirms.
+fn build_spend(state: &mut AppState) {
+    let amount = spend_field(state, SPEND_AMOUNT).parse::<u64>();
+    let fee_rate = spend_field(state, SPEND_FEE_RATE).parse::<u64>();
+    let change_index = match spend_field(state, SPEND_CHANGE) {
+        "" => Ok(None),
+        index => index.parse::<u32>().map(Some),
+    };
+    let destination = spend_field(state, SPEND_TO);
+
+    let wallet = state.wallets.get(state.current_wallet);
+    let result = match (wallet, amount, fee_rate, change_index) {
+        (None, _, _, _) => Err("No wallet loaded"),
+        (_, Err(_), _, _) => Err("Enter the amount in sat"),
+        (_, _, Err(_), _) => Err("Enter the fee rate in sat/vB"),
+        (_, _, _, Err(_)) => Err("Invalid change index"),
+        (Some(wallet), Ok(amount), Ok(fee_rate), Ok(change_index)) => {
+            let request = SpendRequest {
+                destination,
+                amount,
+                fee_rate,
+                strategy: Strategy::BranchAndBound,
+                change_index,
+            };
+            spend::build(wallet, &state.utxos, &request)
+        }
+    };
+
+    let mut lines = Vec::new();
+    match &result {
+        Ok(built) => {
+            // the recipient is always the first output
+            let sent = built.tx.outputs[0].value;
+            lines.push(alloc::format!("Send {}", format_btc(sent)));
+            push_wrapped(&mut lines, destination);
+            if let Some(index) = built.change_output {
+                let change = built.tx.outputs[index].value;
+                lines.push(alloc::format!("Change {}", format_btc(change)));
+                match &built.change_path {
+                    Some(path) => push_wrapped(&mut lines, &alloc::format!("{}", path)),
+                    // a single-key wallet has one address, so change goes back to
+                    // the address being spent and links the payment to it
+                    None => lines.push("Reuses input address".into()),
+                }
+            }
+            lines.push(alloc::format!("Fee {}", format_btc(built.fee)));
+            lines.push(alloc::format!(
+                "{} input(s), {} vB",
+                built.tx.inputs.len(),
+                built.tx.vsize()
+            ));
+        }
+        Err(e) => {
+            lines.push("Cannot build:".into());
+            push_wrapped(&mut lines, e);
+        }
+    }
+
+    state.review_lines = lines;
+    state.review_scroll = 0;
+    state.spend = result.ok();
+    state.current_screen = Screen::SpendReview;
+}
+
+// Write the confirmed transaction next to the UTXO file and prepare its QR
+fn save_spend(state: &mut AppState) {
+    let built = match state.spend.take() {
+        Some(built) => built,
+        None => return,
+    };
+    // encoded once here instead of on every redraw
+    state.spend_qr = built
+        .qr_code()
+        .ok()
+        .filter(|qr| qr.size() <= QR_MAX_MODULES);
+    state.spend_show_text = state.spend_qr.is_none();
+
+    state.review_lines.clear();
+    state.review_scroll = 0;
+    if state.spend_qr.is_none() {
+        state.review_lines.push("Too large for QR".into());
+    }
+    match spend::save(&state.utxo_path, &built) {
+        Ok(path) => {
+            state.review_lines.push("Saved to:".into());
+            push_wrapped(&mut state.review_lines, &path);
+        }
+        Err(e) => {
+            state.review_lines.push("Save failed:".into());
+            push_wrapped(&mut state.review_lines, e);
+        }
+    }
+    state.current_screen = Screen::SpendQr;
+}
+
+// Forget the UTXO list, the form and any transaction when leaving the send screens
+fn clear_spend(state: &mut AppState) {
+    state.utxos.clear();
+    state.utxo_path.clear();
+    state.spend_fields = [[0u8; MAX_SPEND_FIELD_LEN]; SPEND_FIELD_COUNT];
+    state.spend_field_lens = [0usize; SPEND_FIELD_COUNT];
+    state.spend_field = SPEND_TO;
+    state.editing_spend = false;
+    state.spend = None;
+    state.spend_qr = None;
+    state.spend_show_text = false;
+    state.review_lines.clear();
+    state.review_scroll = 0;
+    state.current_screen = Screen::MainMenu;
+    state.menu_index = 4;
+}
+
 // Compute prefix suggestions into state (no heap) based on current fragment.
 fn compute_suggestions_for_prefix(state: &mut AppState, prefix: &str) {
     let pbytes = prefix.as_bytes();
@@ -2142,6 +2845,10 @@ extern "C" fn input_callback(event: *mut sys::InputEvent, ctx: *mut c_void) {
             Screen::ShowMnemonic => handle_show_mnemonic(state, evt),
             Screen::About => handle_about(state, evt),
             Screen::ConfirmAction => handle_confirm(state, evt),
+            Screen::SignPsbt => handle_sign_psbt(state, evt),
+            Screen::Spend => handle_spend(state, evt),
+            Screen::SpendReview => handle_spend_review(state, evt),
+            Screen::SpendQr => handle_spend_qr(state, evt),
         }
     }
 }
@@ -2154,7 +2861,7 @@ fn handle_main_menu(state: &mut AppState, evt: &sys::InputEvent) {
             }
         }
         sys::InputKeyDown => {
-            if state.menu_index < 4 {
+            if state.menu_index < 6 {
                 state.menu_index += 1;
             }
         }
@@ -2169,10 +2876,22 @@ fn handle_main_menu(state: &mut AppState, evt: &sys::InputEvent) {
                 state.menu_index = 0;
             }
             3 => {
+                // the file browser is opened from the main loop
+                state.review_lines.clear();
+                state.review_scroll = 0;
+                state.current_screen = Screen::SignPsbt;
+                state.psbt_pick_requested = true;
+            }
+            4 => {
+                // the file browser is opened from the main loop
+                state.current_screen = Screen::Spend;
+                state.spend_pick_requested = true;
+            }
+            5 => {
                 state.current_screen = Screen::Settings;
                 state.settings_index = 0;
             }
-            4 => state.current_screen = Screen::About,
+            6 => state.current_screen = Screen::About,
             _ => {}
         },
         sys::InputKeyBack => state.exit_requested = true,
@@ -2274,6 +2993,11 @@ fn handle_import_wallet(state: &mut AppState, evt: &sys::InputEvent) {
         return;
     }
 
+    if state.input_mode == InputMode::TextInput && state.editing_wif {
+        handle_wif_input(state, evt);
+        return;
+    }
+
     // If we're in text input mode for entering words, handle navigation/accept/backspace here.
     if state.input_mode == InputMode::TextInput {
         // recompute suggestions based on current fragment so navigation works, but skip for passphrase
@@ -2470,7 +3194,7 @@ fn handle_import_wallet(state: &mut AppState, evt: &sys::InputEvent) {
             }
         }
         sys::InputKeyDown => {
-            if state.menu_index < 2 {
+            if state.menu_index < 3 {
                 state.menu_index += 1;
             }
         }
@@ -2523,7 +3247,7 @@ fn handle_import_wallet(state: &mut AppState, evt: &sys::InputEvent) {
                             .unwrap_or(""),
                     ) {
                         // add default first account for display
-                        let _ = wallet.add_account(crate::address::Cryptocurrency::Bitcoin, 0, 0);
+                        add_default_accounts(&mut wallet, state.network);
                         state.wallets.push(wallet);
                         state.current_wallet = state.wallets.len().saturating_sub(1);
                         state.current_screen = Screen::ViewWallets;
@@ -2540,6 +3264,15 @@ fn handle_import_wallet(state: &mut AppState, evt: &sys::InputEvent) {
                     state.current_screen = Screen::ConfirmAction;
                 }
             }
+            3 => {
+                // type a WIF private key one base58 character at a time
+                state.input_mode = InputMode::TextInput;
+                state.editing_wif = true;
+                state.wif_error = false;
+                state.wif_buffer = [0u8; MAX_WIF_LEN];
+                state.wif_len = 0;
+                state.char_index = 0;
+            }
             _ => {}
         },
         sys::InputKeyBack => {
@@ -2554,6 +3287,62 @@ fn handle_import_wallet(state: &mut AppState, evt: &sys::InputEvent) {
     }
 }
 
+// Keys while typing a WIF: Up/Down pick a base58 character, OK inserts it,
+// Left deletes, Right imports the key as a single-key wallet, Back cancels.
+fn handle_wif_input(state: &mut AppState, evt: &sys::InputEvent) {
+    let chars = base58::BITCOIN.chars();
+    state.wif_error = false;
+    match evt.key {
+        sys::InputKeyUp => {
+            state.char_index = (state.char_index + chars.len() - 1) % chars.len();
+        }
+        sys::InputKeyDown => {
+            state.char_index = (state.char_index + 1) % chars.len();
+        }
+        sys::InputKeyOk => {
+            if state.wif_len < MAX_WIF_LEN - 1 {
+                state.wif_buffer[state.wif_len] = chars[state.char_index % chars.len()];
+                state.wif_len += 1;
+            }
+        }
+        sys::InputKeyLeft => {
+            if state.wif_len > 0 {
+                state.wif_len -= 1;
+                state.wif_buffer[state.wif_len] = 0;
+            }
+        }
+        sys::InputKeyRight => {
+            let wif_str = core::str::from_utf8(&state.wif_buffer[..state.wif_len]).unwrap_or("");
+            match Wallet::from_wif(wif_str) {
+                Ok(mut wallet) => {
+                    let network = wallet.network();
+                    add_default_accounts(&mut wallet, network);
+                    state.wallets.push(wallet);
+                    state.current_wallet = state.wallets.len().saturating_sub(1);
+                    state.wif_buffer = [0u8; MAX_WIF_LEN];
+                    state.wif_len = 0;
+                    state.editing_wif = false;
+                    state.input_mode = InputMode::Navigation;
+                    state.char_index = 0;
+                    state.current_screen = Screen::ViewWallets;
+                    state.menu_index = 0;
+                }
+                Err(_) => {
+                    state.wif_error = true;
+                }
+            }
+        }
+        sys::InputKeyBack => {
+            state.wif_buffer = [0u8; MAX_WIF_LEN];
+            state.wif_len = 0;
+            state.editing_wif = false;
+            state.input_mode = InputMode::Navigation;
+            state.char_index = 0;
+        }
+        _ => {}
+    }
+}
+
 fn handle_view_wallets(state: &mut AppState, evt: &sys::InputEvent) {
     match evt.key {
         sys::InputKeyUp => {
@@ -2585,17 +3374,21 @@ fn handle_settings(state: &mut AppState, evt: &sys::InputEvent) {
             }
         }
         sys::InputKeyDown => {
-            if state.settings_index < 4 {
+            if state.settings_index < 4 + Network::ALL.len() {
                 state.settings_index += 1;
             }
         }
         sys::InputKeyOk => {
             let counts = [12, 15, 18, 21, 24];
-            state.bip39_word_count = counts[state.settings_index];
+            if state.settings_index < counts.len() {
+                state.bip39_word_count = counts[state.settings_index];
+            } else {
+                state.network = Network::ALL[state.settings_index - counts.len()];
+            }
         }
         sys::InputKeyBack => {
             state.current_screen = Screen::MainMenu;
-            state.menu_index = 3;
+            state.menu_index = 5;
         }
         _ => {}
     }
@@ -2697,11 +3490,155 @@ fn handle_show_mnemonic(state: &mut AppState, evt: &sys::InputEvent) {
     }
 }
 
+// Review screen keys: Up/Down scroll, OK asks to sign, Back leaves without signing
+fn handle_sign_psbt(state: &mut AppState, evt: &sys::InputEvent) {
+    if state.psbt_sign_requested {
+        return;
+    }
+    match evt.key {
+        sys::InputKeyUp => {
+            if state.review_scroll > 0 {
+                state.review_scroll -= 1;
+            }
+        }
+        sys::InputKeyDown => {
+            if state.review_scroll + REVIEW_VISIBLE < state.review_lines.len() {
+                state.review_scroll += 1;
+            }
+        }
+        sys::InputKeyOk => {
+            if state.psbt.is_some() {
+                state.confirm_action = ConfirmAction::SignPsbt;
+                state.confirm_index = 1;
+                state.current_screen = Screen::ConfirmAction;
+            }
+        }
+        sys::InputKeyBack | sys::InputKeyLeft => {
+            state.psbt = None;
+            state.review_lines.clear();
+            state.review_scroll = 0;
+            state.current_screen = Screen::MainMenu;
+            state.menu_index = 3;
+        }
+        _ => {}
+    }
+}
+
+// Send form keys: Up/Down pick a field, OK edits it, Right builds the transaction,
+// Back leaves. While editing, Up/Down pick a character, OK adds it, Left deletes,
+// Back or Right finish the field.
+fn handle_spend(state: &mut AppState, evt: &sys::InputEvent) {
+    if state.spend_pick_requested || state.spend_build_requested {
+        return;
+    }
+    if state.editing_spend {
+        let charset = spend_charset(state.spend_field);
+        let field = state.spend_field;
+        match evt.key {
+            sys::InputKeyUp => {
+                state.char_index = (state.char_index + charset.len() - 1) % charset.len();
+            }
+            sys::InputKeyDown => state.char_index = (state.char_index + 1) % charset.len(),
+            sys::InputKeyOk => {
+                let len = state.spend_field_lens[field];
+                if len < MAX_SPEND_FIELD_LEN {
+                    state.spend_fields[field][len] = charset[state.char_index % charset.len()];
+                    state.spend_field_lens[field] += 1;
+                }
+            }
+            sys::InputKeyLeft => {
+                if state.spend_field_lens[field] > 0 {
+                    state.spend_field_lens[field] -= 1;
+                    state.spend_fields[field][state.spend_field_lens[field]] = 0;
+                }
+            }
+            sys::InputKeyBack | sys::InputKeyRight => state.editing_spend = false,
+            _ => {}
+        }
+        return;
+    }
+    match evt.key {
+        sys::InputKeyUp => {
+            if state.spend_field > 0 {
+                state.spend_field -= 1;
+            }
+        }
+        sys::InputKeyDown => {
+            if state.spend_field + 1 < SPEND_FIELD_COUNT {
+                state.spend_field += 1;
+            }
+        }
+        sys::InputKeyOk => {
+            state.editing_spend = true;
+            state.char_index = 0;
+        }
+        sys::InputKeyRight => state.spend_build_requested = true,
+        sys::InputKeyBack => clear_spend(state),
+        _ => {}
+    }
+}
+
+// Review keys: Up/Down scroll, OK asks to save the signed transaction, Back edits
+fn handle_spend_review(state: &mut AppState, evt: &sys::InputEvent) {
+    if state.spend_save_requested {
+        return;
+    }
+    match evt.key {
+        sys::InputKeyUp => {
+            if state.review_scroll > 0 {
+                state.review_scroll -= 1;
+            }
+        }
+        sys::InputKeyDown => {
+            if state.review_scroll + REVIEW_VISIBLE < state.review_lines.len() {
+                state.review_scroll += 1;
+            }
+        }
+        sys::InputKeyOk => {
+            if state.spend.is_some() {
+                state.confirm_action = ConfirmAction::SaveTransaction;
+                state.confirm_index = 1;
+                state.current_screen = Screen::ConfirmAction;
+            }
+        }
+        sys::InputKeyBack | sys::InputKeyLeft => {
+            // the signed transaction is discarded; nothing was written
+            state.spend = None;
+            if state.utxos.is_empty() {
+                clear_spend(state);
+            } else {
+                state.current_screen = Screen::Spend;
+            }
+        }
+        _ => {}
+    }
+}
+
+// QR keys: Left/Right switch between the QR and the saved path, Back/OK finish
+fn handle_spend_qr(state: &mut AppState, evt: &sys::InputEvent) {
+    match evt.key {
+        sys::InputKeyLeft => state.spend_show_text = true,
+        sys::InputKeyRight => state.spend_show_text = state.spend_qr.is_none(),
+        sys::InputKeyUp => {
+            if state.review_scroll > 0 {
+                state.review_scroll -= 1;
+            }
+        }
+        sys::InputKeyDown => {
+            if state.review_scroll + REVIEW_VISIBLE < state.review_lines.len() {
+                state.review_scroll += 1;
+            }
+        }
+        sys::InputKeyBack | sys::InputKeyOk => clear_spend(state),
+        _ => {}
+    }
+}
+
 fn handle_about(state: &mut AppState, evt: &sys::InputEvent) {
     match evt.key {
         sys::InputKeyBack => {
             state.current_screen = Screen::MainMenu;
-            state.menu_index = 4;
+            state.menu_index = 6;
         }
         _ => {}
     }
@@ -2726,6 +3663,8 @@ fn handle_confirm(state: &mut AppState, evt: &sys::InputEvent) {
                     ConfirmAction::ExportMnemonic => {
                         // legacy action used elsewhere as an export/validation marker; no-op here
                     }
+                    ConfirmAction::SignPsbt => state.psbt_sign_requested = true,
+                    ConfirmAction::SaveTransaction => state.spend_save_requested = true,
                     ConfirmAction::SaveWallet => {
                         // For stability, perform an immediate in-memory save (no heavy crypto/IO here).
                         // Build wallet from current mnemonic/passphrase and add to memory.
@@ -2733,17 +3672,21 @@ fn handle_confirm(state: &mut AppState, evt: &sys::InputEvent) {
                             Ok(s) => s.trim(),
                             Err(_) => "",
                         };
-                        if !mnemonic_str.is_empty() {
+                        if state.show_private {
+                            // Generated private key: keep it as a single-key wallet
+                            if let Some(sk) = generated_secret_key(state) {
+                                let mut wallet = Wallet::from_secret_key(sk, true);
+                                add_default_accounts(&mut wallet, state.network);
+                                state.wallets.push(wallet);
+                                state.current_wallet = state.wallets.len().saturating_sub(1);
+                            }
+                        } else if !mnemonic_str.is_empty() {
                             if let Ok(mut wallet) = Wallet::from_mnemonic(
                                 mnemonic_str,
                                 core::str::from_utf8(&state.passphrase_buffer[..state.passphrase_len])
                                     .unwrap_or(""),
                             ) {
-                                let _ = wallet.add_account(
-                                    crate::address::Cryptocurrency::Bitcoin,
-                                    0,
-                                    0,
-                                );
+                                add_default_accounts(&mut wallet, state.network);
                                 state.wallets.push(wallet);
                                 state.current_wallet = state.wallets.len().saturating_sub(1);
                             }
@@ -2757,8 +3700,13 @@ fn handle_confirm(state: &mut AppState, evt: &sys::InputEvent) {
                 }
             }
             // If the confirm action was RevealPrivate and user confirmed, keep ShowMnemonic.
+            // Transaction review returns to its screen either way (signing or still reviewing).
             // Otherwise return to main menu (unless we already switched screen).
-            if state.confirm_action != ConfirmAction::RevealPrivate
+            if state.confirm_action == ConfirmAction::SignPsbt {
+                state.current_screen = Screen::SignPsbt;
+            } else if state.confirm_action == ConfirmAction::SaveTransaction {
+                state.current_screen = Screen::SpendReview;
+            } else if state.confirm_action != ConfirmAction::RevealPrivate
                 && state.confirm_action != ConfirmAction::ExportMnemonic
             {
                 state.current_screen = Screen::MainMenu;
@@ -2766,7 +3714,11 @@ fn handle_confirm(state: &mut AppState, evt: &sys::InputEvent) {
             state.confirm_action = ConfirmAction::None;
         }
         sys::InputKeyBack => {
-            state.current_screen = Screen::MainMenu;
+            state.current_screen = match state.confirm_action {
+                ConfirmAction::SignPsbt => Screen::SignPsbt,
+                ConfirmAction::SaveTransaction => Screen::SpendReview,
+                _ => Screen::MainMenu,
+            };
             state.confirm_action = ConfirmAction::None;
         }
         _ => {}
@@ -1,11 +1,16 @@
 extern crate alloc;
 
-use crate::address::{generate_address, Cryptocurrency};
-use crate::bip32::MasterKey;
+use crate::address::{
+    generate_address_for_network, generate_uncompressed_address, parse_address, AddressType,
+    Cryptocurrency,
+};
+use crate::bip32::{DerivationPath, ExtendedPubKey, MasterKey, HARDENED};
 use crate::bip39::{entropy_to_mnemonic, mnemonic_to_entropy, validate_mnemonic, MnemonicType};
 use crate::fixed_string::FixedString;
+use crate::network::Network;
 use crate::pbkdf2::mnemonic_to_seed;
-use crate::secp256k1::PublicKey;
+use crate::secp256k1::{PublicKey, SecretKey};
+use crate::wif;
 use alloc::vec::Vec;pub type WalletResult<T> = Result<T, WalletError>;
 
 #[derive(Clone, Copy)]
@@ -55,42 +60,60 @@ impl core::fmt::Debug for WalletError {
     }
 }
 
-fn u32_to_string(mut n: u32) -> FixedString<16> {
-    if n == 0 {
-        let mut result = FixedString::new();
-        let _ = result.push('0');
-        return result;
-    }
-
-    let mut digits = [0u8; 10];
-    let mut i = 0;
-    while n > 0 {
-        digits[i] = (n % 10) as u8;
-        n /= 10;
-        i += 1;
-    }
-
-    let mut result = FixedString::new();
-    for j in (0..i).rev() {
-        let _ = result.push((b'0' + digits[j]) as char);
-    }
-
-    result
+/// 账户地址的标准派生路径：m/purpose'/coin_type'/account'/change/address_index
+///
+/// 测试网络统一使用币种索引 1（SLIP-44）。
+fn account_path(
+    network: Network,
+    cryptocurrency: Cryptocurrency,
+    address_type: AddressType,
+    account_index: u32,
+    change: u32,
+    address_index: u32,
+) -> DerivationPath {
+    DerivationPath::from_purpose(
+        address_type.purpose(),
+        cryptocurrency.coin_type_for(network),
+        account_index,
+        change,
+        address_index,
+    )
 }
 
 #[derive(Debug, Clone)]
 pub struct AccountInfo {
     pub cryptocurrency: Cryptocurrency,
+    pub address_type: AddressType,
     pub address: FixedString<128>,
-    pub derivation_path: FixedString<64>,
+    pub derivation_path: DerivationPath,
     pub account_index: u32,
 }
 
+/// 钱包的密钥来源
+#[derive(Clone, Copy)]
+pub enum WalletKind {
+    /// 由助记词派生，持有主私钥
+    Mnemonic(MasterKey),
+    /// 仅观察：只持有某个币种的账户级扩展公钥（m/purpose'/coin_type'/account'），无法签名
+    WatchOnly {
+        cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
+        xpub: ExtendedPubKey,
+    },
+    /// 单个私钥（例如导入的 WIF 或随机生成的私钥），没有派生路径
+    SingleKey {
+        secret_key: SecretKey,
+        /// 地址是否基于压缩公钥（未压缩私钥只能生成 P2PKH 地址）
+        compressed: bool,
+    },
+}
+
 pub struct Wallet {
     pub mnemonic: FixedString<256>,
     pub seed: [u8; 64],
-    pub master_key: MasterKey,
+    pub kind: WalletKind,
     pub accounts: Vec<AccountInfo>,
+    network: Network,
 }
 
 impl Wallet {
@@ -125,8 +148,9 @@ impl Wallet {
         Ok(Wallet {
             mnemonic: mnemonic_str,
             seed,
-            master_key,
+            kind: WalletKind::Mnemonic(master_key),
             accounts: Vec::new(),
+            network: Network::Mainnet,
         })
     }
 
@@ -137,89 +161,266 @@ impl Wallet {
         }
 
         let _entropy = mnemonic_to_entropy(&words).map_err(|_| WalletError::InvalidMnemonic)?;
-        let seed = mnemonic_to_seed(mnemonic_phrase, passphrase);
+
+        // BIP39 种子基于规范化的助记词（单词之间仅一个空格）
+        let mut mnemonic = FixedString::<256>::new();
+        for (i, word) in words.iter().enumerate() {
+            if i > 0 {
+                mnemonic
+                    .push_str(" ")
+                    .map_err(|_| WalletError::AddressTooLong)?;
+            }
+            mnemonic
+                .push_str(word)
+                .map_err(|_| WalletError::AddressTooLong)?;
+        }
+        let seed = mnemonic_to_seed(mnemonic.as_str(), passphrase);
 
         let master_key = MasterKey::from_seed(&seed).map_err(|_| WalletError::InvalidSeed)?;
-        let mnemonic =
-            FixedString::from_str(mnemonic_phrase).map_err(|_| WalletError::AddressTooLong)?;
 
         Ok(Wallet {
             mnemonic,
             seed,
-            master_key,
+            kind: WalletKind::Mnemonic(master_key),
             accounts: Vec::new(),
+            network: Network::Mainnet,
         })
     }
 
-    /// 添加一个新账户（指定币种、账户索引和地址索引）
-    pub fn add_account(
-        &mut self,
+    /// 从账户级扩展公钥创建仅观察钱包（不持有任何私钥）
+    ///
+    /// 地址类型决定该账户的 purpose（例如 P2WPKH 对应 m/84'）。
+    pub fn from_xpub(
+        xpub: &str,
         cryptocurrency: Cryptocurrency,
-        account_index: u32,
-        address_index: u32,
-    ) -> WalletResult<()> {
-        let coin_type = cryptocurrency.coin_type();
+        address_type: AddressType,
+    ) -> WalletResult<Self> {
+        if !cryptocurrency.supports_address_type(address_type) {
+            return Err(WalletError::InvalidPath);
+        }
 
-        // 检查账户是否已存在
-        for account in &self.accounts {
-            if account.cryptocurrency == cryptocurrency && account.account_index == address_index {
-                return Ok(()); // 已存在，无需添加
-            }
+        let xpub = ExtendedPubKey::from_base58(xpub.trim()).map_err(|_| WalletError::InvalidKey)?;
+
+        // 只接受账户级（深度 3、硬化索引）的扩展公钥，才能给出正确的派生路径
+        if xpub.depth() != 3 || xpub.child_number() & HARDENED == 0 {
+            return Err(WalletError::InvalidPath);
+        }
+        if !cryptocurrency.supports_network(xpub.network()) {
+            return Err(WalletError::InvalidKey);
         }
 
-        let secret_key = self
-            .master_key
-            .derive_bip44(coin_type, account_index, 0, address_index)
-            .map_err(|_| WalletError::DerivationFailed)?;
+        Ok(Wallet {
+            mnemonic: FixedString::new(),
+            seed: [0u8; 64],
+            kind: WalletKind::WatchOnly {
+                cryptocurrency,
+                address_type,
+                xpub,
+            },
+            accounts: Vec::new(),
+            network: xpub.network(),
+        })
+    }
+
+    /// 从单个私钥创建钱包
+    pub fn from_secret_key(secret_key: SecretKey, compressed: bool) -> Self {
+        Wallet {
+            mnemonic: FixedString::new(),
+            seed: [0u8; 64],
+            kind: WalletKind::SingleKey {
+                secret_key,
+                compressed,
+            },
+            accounts: Vec::new(),
+            network: Network::Mainnet,
+        }
+    }
 
-        let public_key =
-            PublicKey::from_secret_key(&secret_key).map_err(|_| WalletError::InvalidKey)?;
+    /// 导入 WIF 私钥，钱包网络取自 WIF 版本字节
+    pub fn from_wif(wif_str: &str) -> WalletResult<Self> {
+        let key = wif::decode(wif_str).map_err(|_| WalletError::InvalidKey)?;
+        let mut wallet = Self::from_secret_key(key.secret_key, key.compressed);
+        wallet.network = key.network;
+        Ok(wallet)
+    }
 
-        let address_bytes = generate_address(&public_key, cryptocurrency)
-            .map_err(|_| WalletError::AddressTooLong)?;
+    /// 钱包所在网络
+    pub fn network(&self) -> Network {
+        self.network
+    }
 
-        let address_str =
-            core::str::from_utf8(&address_bytes).map_err(|_| WalletError::AddressTooLong)?;
+    /// 切换钱包所在网络，并清空按旧网络生成的账户
+    ///
+    /// 助记词钱包按新网络重建主密钥（xprv/tprv）；仅观察钱包只能在与导入的
+    /// 扩展公钥版本（xpub/tpub）相符的网络之间切换。
+    pub fn set_network(&mut self, network: Network) -> WalletResult<()> {
+        match &mut self.kind {
+            WalletKind::Mnemonic(master_key) => {
+                *master_key = MasterKey::new_master(network, &self.seed)
+                    .map_err(|_| WalletError::InvalidSeed)?;
+            }
+            WalletKind::WatchOnly { xpub, .. } => {
+                if !network.shares_extended_keys_with(xpub.network()) {
+                    return Err(WalletError::InvalidKey);
+                }
+            }
+            WalletKind::SingleKey { .. } => {}
+        }
 
-        let address =
-            FixedString::from_str(address_str).map_err(|_| WalletError::AddressTooLong)?;
+        self.network = network;
+        self.accounts.clear();
+        Ok(())
+    }
+
+    /// 是否为仅观察钱包
+    pub fn is_watch_only(&self) -> bool {
+        matches!(self.kind, WalletKind::WatchOnly { .. })
+    }
+
+    /// 是否为单私钥钱包
+    pub fn is_single_key(&self) -> bool {
+        matches!(self.kind, WalletKind::SingleKey { .. })
+    }
 
-        // 构建派生路径字符串
-        let mut derivation_path_str = FixedString::<64>::new();
-        derivation_path_str
-            .push_str("m/44'/")
-            .map_err(|_| WalletError::AddressTooLong)?;
+    /// 主私钥（仅观察钱包和单私钥钱包返回 None）
+    pub fn master_key(&self) -> Option<&MasterKey> {
+        match &self.kind {
+            WalletKind::Mnemonic(master_key) => Some(master_key),
+            WalletKind::WatchOnly { .. } | WalletKind::SingleKey { .. } => None,
+        }
+    }
 
-        let coin_type_str = u32_to_string(coin_type);
-        derivation_path_str
-            .push_str(coin_type_str.as_str())
-            .map_err(|_| WalletError::AddressTooLong)?;
+    /// 以 WIF 导出私钥（仅单私钥钱包）
+    pub fn export_wif(&self, cryptocurrency: Cryptocurrency) -> WalletResult<FixedString<64>> {
+        match &self.kind {
+            WalletKind::SingleKey {
+                secret_key,
+                compressed,
+            } => wif::encode(secret_key, cryptocurrency, self.network, *compressed)
+                .map_err(|_| WalletError::InvalidKey),
+            _ => Err(WalletError::InvalidKey),
+        }
+    }
 
-        derivation_path_str
-            .push_str("/")
-            .map_err(|_| WalletError::AddressTooLong)?;
+    /// 地址的派生路径：单私钥钱包没有路径（空路径），其他钱包使用标准账户路径
+    fn address_path(
+        &self,
+        cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
+        account_index: u32,
+        change: u32,
+        address_index: u32,
+    ) -> DerivationPath {
+        if self.is_single_key() {
+            return DerivationPath::master();
+        }
+        account_path(
+            self.network,
+            cryptocurrency,
+            address_type,
+            account_index,
+            change,
+            address_index,
+        )
+    }
 
-        let account_str = u32_to_string(account_index);
-        derivation_path_str
-            .push_str(account_str.as_str())
-            .map_err(|_| WalletError::AddressTooLong)?;
+    /// 派生指定路径的公钥
+    ///
+    /// 仅观察钱包只能派生导入账户之下的非硬化路径
+    /// （m/purpose'/coin_type'/account'/...，且币种与地址类型与导入时一致）。
+    fn derive_public_key(
+        &self,
+        cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
+        path: &DerivationPath,
+    ) -> WalletResult<PublicKey> {
+        match &self.kind {
+            WalletKind::Mnemonic(master_key) => master_key
+                .derive_path(path)
+                .and_then(|key| key.public_key())
+                .map_err(|_| WalletError::DerivationFailed),
+            WalletKind::WatchOnly {
+                cryptocurrency: watched,
+                address_type: watched_type,
+                xpub,
+            } => {
+                let indices = path.indices();
+                let account_prefix = [
+                    watched_type.purpose() | HARDENED,
+                    watched.coin_type_for(self.network) | HARDENED,
+                    xpub.child_number(),
+                ];
+                if *watched != cryptocurrency
+                    || *watched_type != address_type
+                    || indices.len() < 3
+                    || indices[..3] != account_prefix
+                {
+                    return Err(WalletError::InvalidPath);
+                }
+
+                let mut key = *xpub;
+                for &index in &indices[3..] {
+                    key = key
+                        .derive_child(index)
+                        .map_err(|_| WalletError::DerivationFailed)?;
+                }
+
+                Ok(*key.public_key())
+            }
+            WalletKind::SingleKey { secret_key, .. } => {
+                if !path.is_empty() {
+                    return Err(WalletError::InvalidPath);
+                }
+                PublicKey::from_secret_key(secret_key).map_err(|_| WalletError::InvalidKey)
+            }
+        }
+    }
 
-        derivation_path_str
-            .push_str("'/0/")
-            .map_err(|_| WalletError::AddressTooLong)?;
+    /// 添加一个新账户（指定币种、地址类型、账户索引和地址索引）
+    ///
+    /// 派生路径的 purpose 由地址类型决定，例如 P2WPKH 使用 m/84'。
+    pub fn add_account(
+        &mut self,
+        cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
+        account_index: u32,
+        address_index: u32,
+    ) -> WalletResult<()> {
+        let path = self.address_path(
+            cryptocurrency,
+            address_type,
+            account_index,
+            0,
+            address_index,
+        );
+        self.add_account_at_path(cryptocurrency, address_type, path)
+    }
 
-        let address_str = u32_to_string(address_index);
-        derivation_path_str
-            .push_str(address_str.as_str())
-            .map_err(|_| WalletError::AddressTooLong)?;
+    /// 按自定义派生路径添加账户（用于路径不标准的钱包）
+    ///
+    /// 币种、地址类型和派生路径都相同的账户只添加一次。
+    pub fn add_account_at_path(
+        &mut self,
+        cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
+        derivation_path: DerivationPath,
+    ) -> WalletResult<()> {
+        if self
+            .find_account(cryptocurrency, address_type, &derivation_path)
+            .is_some()
+        {
+            return Ok(()); // 已存在，无需添加
+        }
 
-        let derivation_path = derivation_path_str;
+        let address = self.derive_address(cryptocurrency, address_type, &derivation_path)?;
 
         let account = AccountInfo {
             cryptocurrency,
+            address_type,
             address,
             derivation_path,
-            account_index: address_index,
+            // 与 BIP44 账户一致，记录路径最后一级（地址索引）
+            account_index: derivation_path.indices().last().map_or(0, |&i| i & !HARDENED),
         };
 
         self.accounts.push(account);
@@ -229,29 +430,81 @@ impl Wallet {
     pub fn get_address(
         &self,
         cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
         account_index: u32,
         address_index: u32,
     ) -> WalletResult<FixedString<128>> {
-        if let Some(account) = self
-            .accounts
-            .iter()
-            .find(|a| a.cryptocurrency == cryptocurrency && a.account_index == address_index)
-        {
+        let path = self.address_path(
+            cryptocurrency,
+            address_type,
+            account_index,
+            0,
+            address_index,
+        );
+        if let Some(account) = self.find_account(cryptocurrency, address_type, &path) {
             return Ok(account.address);
         }
+        self.derive_address(cryptocurrency, address_type, &path)
+    }
 
-        let coin_type = cryptocurrency.coin_type();
+    /// 按币种、地址类型和完整派生路径查找已添加的账户
+    fn find_account(
+        &self,
+        cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
+        path: &DerivationPath,
+    ) -> Option<&AccountInfo> {
+        self.accounts.iter().find(|account| {
+            account.cryptocurrency == cryptocurrency
+                && account.address_type == address_type
+                && account.derivation_path == *path
+        })
+    }
+
+    /// 获取找零地址：m/purpose'/coin_type'/account'/1/address_index
+    pub fn get_change_address(
+        &self,
+        cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
+        account_index: u32,
+        address_index: u32,
+    ) -> WalletResult<FixedString<128>> {
+        let path = self.address_path(
+            cryptocurrency,
+            address_type,
+            account_index,
+            1,
+            address_index,
+        );
+        self.derive_address(cryptocurrency, address_type, &path)
+    }
 
-        let secret_key = self
-            .master_key
-            .derive_bip44(coin_type, account_index, 0, address_index)
-            .map_err(|_| WalletError::DerivationFailed)?;
+    fn derive_address(
+        &self,
+        cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
+        path: &DerivationPath,
+    ) -> WalletResult<FixedString<128>> {
+        if !cryptocurrency.supports_address_type(address_type)
+            || !cryptocurrency.supports_network(self.network)
+        {
+            return Err(WalletError::InvalidPath);
+        }
 
-        let public_key =
-            PublicKey::from_secret_key(&secret_key).map_err(|_| WalletError::InvalidKey)?;
+        let public_key = self.derive_public_key(cryptocurrency, address_type, path)?;
 
-        let address_bytes = generate_address(&public_key, cryptocurrency)
-            .map_err(|_| WalletError::AddressTooLong)?;
+        let address_bytes = match self.kind {
+            WalletKind::SingleKey {
+                compressed: false, ..
+            } => {
+                if address_type != AddressType::P2pkh {
+                    return Err(WalletError::InvalidKey);
+                }
+                generate_uncompressed_address(&public_key, cryptocurrency, self.network)
+            }
+            _ => generate_address_for_network(&public_key, cryptocurrency, address_type, self.network),
+        }
+        .map_err(|_| WalletError::AddressTooLong)?;
 
         let address_str =
             core::str::from_utf8(&address_bytes).map_err(|_| WalletError::AddressTooLong)?;
@@ -259,6 +512,55 @@ impl Wallet {
         FixedString::from_str(address_str).map_err(|_| WalletError::AddressTooLong)
     }
 
+    /// 查找与给定地址相同的已添加账户（按解码后的内容比较，忽略大小写差异）
+    pub fn find_account_by_address(&self, address: &str) -> Option<&AccountInfo> {
+        let target = parse_address(address).ok()?;
+        self.accounts.iter().find(|account| {
+            parse_address(account.address.as_str()).is_ok_and(|parsed| parsed == target)
+        })
+    }
+
+    /// 主密钥指纹（用于与其他签名设备核对）
+    pub fn master_fingerprint(&self) -> WalletResult<[u8; 4]> {
+        self.master_key()
+            .ok_or(WalletError::InvalidKey)?
+            .fingerprint()
+            .map_err(|_| WalletError::InvalidKey)
+    }
+
+    /// 导出账户级扩展公钥：m/purpose'/coin_type'/account'
+    pub fn account_xpub(
+        &self,
+        cryptocurrency: Cryptocurrency,
+        address_type: AddressType,
+        account_index: u32,
+    ) -> WalletResult<ExtendedPubKey> {
+        match &self.kind {
+            WalletKind::Mnemonic(master_key) => master_key
+                .derive_account(
+                    address_type.purpose(),
+                    cryptocurrency.coin_type_for(self.network),
+                    account_index,
+                )
+                .and_then(|key| key.to_extended_pub_key())
+                .map_err(|_| WalletError::DerivationFailed),
+            WalletKind::WatchOnly {
+                cryptocurrency: watched,
+                address_type: watched_type,
+                xpub,
+            } => {
+                if *watched != cryptocurrency
+                    || *watched_type != address_type
+                    || xpub.child_number() != (account_index | HARDENED)
+                {
+                    return Err(WalletError::InvalidPath);
+                }
+                Ok(*xpub)
+            }
+            WalletKind::SingleKey { .. } => Err(WalletError::InvalidKey),
+        }
+    }
+
     pub fn get_mnemonic(&self) -> &str {
         self.mnemonic.as_str()
     }
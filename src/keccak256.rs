@@ -36,8 +36,8 @@ impl Keccak256 {
     /// 完成哈希计算并返回结果
     pub fn finalize(mut self) -> [u8; 32] {
         if !self.finalized {
-            // Padding (SHA-3/Keccak)
-            self.buffer[self.buffer_len] = 0x06;
+            // Padding（原始 Keccak 使用 0x01，而不是 SHA-3 的 0x06）
+            self.buffer[self.buffer_len] = 0x01;
             self.buffer_len += 1;
 
             // 填充直到缓冲区满
@@ -55,8 +55,8 @@ impl Keccak256 {
 
         // 挤出 32 字节的哈希
         let mut result = [0u8; 32];
-        for i in 0..32 {
-            result[i] = ((self.state[i / 8] >> ((i % 8) * 8)) & 0xff) as u8;
+        for (i, byte) in result.iter_mut().enumerate() {
+            *byte = ((self.state[i / 8] >> ((i % 8) * 8)) & 0xff) as u8;
         }
         result
     }
@@ -86,17 +86,20 @@ impl Keccak256 {
     }
 
     /// Keccak-f[1600] 排列函数
+    ///
+    /// 按规范的 (x, y) 坐标写下标，不改写为迭代器。
+    #[allow(clippy::needless_range_loop)]
     fn keccak_f(&mut self) {
         const ROUNDS: usize = 24;
         const RC: [u64; 24] = [
             0x0000000000000001, 0x0000000000008082, 0x800000000000808a,
-            0x8000000080008000, 0x0000000080000001, 0x8000000080008081,
-            0x8000000000008009, 0x000000000000008a, 0x0000000000000088,
-            0x0000000080000001, 0x000000008000008b, 0x000000008000008a,
-            0x00000000000000ff, 0x800000008000008f, 0x8000000000008081,
-            0x8000000080000001, 0x8000000080008008, 0x000000000000008a,
-            0x0000000080008001, 0x8000000080008081, 0x8000000000000001,
-            0x8000000080008009, 0x000000000000008a, 0x0000000000008009,
+            0x8000000080008000, 0x000000000000808b, 0x0000000080000001,
+            0x8000000080008081, 0x8000000000008009, 0x000000000000008a,
+            0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+            0x000000008000808b, 0x800000000000008b, 0x8000000000008089,
+            0x8000000000008003, 0x8000000000008002, 0x8000000000000080,
+            0x000000000000800a, 0x800000008000000a, 0x8000000080008081,
+            0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
         ];
 
         for round in 0..ROUNDS {
@@ -123,7 +126,7 @@ impl Keccak256 {
             for x in 0..5 {
                 for y in 0..5 {
                     let rot = self.rotation_offset(x, y);
-                    b[5 * y + ((x + 3 * y) % 5)] = self.state[5 * y + x].rotate_left(rot as u32);
+                    b[5 * ((2 * x + 3 * y) % 5) + y] = self.state[5 * y + x].rotate_left(rot as u32);
                 }
             }
 
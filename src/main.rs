@@ -7,20 +7,32 @@ extern crate flipperzero_rt;
 
 pub mod address;
 pub mod aes;
+pub mod base58;
+pub mod bech32;
 pub mod bip32;
 pub mod bip39;
+pub mod coin_selection;
 pub mod fixed_string;
 pub mod flipper_app;
 pub mod flipper_wallet_core;
 pub mod hex;
 pub mod keccak256;
+pub mod network;
 pub mod pbkdf2;
+pub mod psbt;
 pub mod qrcodegen;
 pub mod ripemd160;
+pub mod script;
 pub mod secp256k1;
 pub mod sha256;
+pub mod sha512;
+pub mod sighash;
+pub mod spend;
 pub mod storage;
+pub mod transaction;
 pub mod trng;
+pub mod utxo;
+pub mod wif;
 pub mod word_list;
 
 use core::ffi::CStr;
@@ -0,0 +1,87 @@
+/// 比特币网络类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet3,
+    Testnet4,
+    Signet,
+    Regtest,
+}
+
+impl Network {
+    /// 所有网络（设置界面的显示顺序）
+    pub const ALL: [Network; 5] = [
+        Network::Mainnet,
+        Network::Testnet3,
+        Network::Testnet4,
+        Network::Signet,
+        Network::Regtest,
+    ];
+
+    /// 是否为主网
+    pub fn is_mainnet(&self) -> bool {
+        *self == Network::Mainnet
+    }
+
+    /// BIP32 扩展私钥版本字节（xprv / tprv）
+    pub fn xprv_version(&self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0x04, 0x88, 0xAD, 0xE4],
+            _ => [0x04, 0x35, 0x83, 0x94],
+        }
+    }
+
+    /// BIP32 扩展公钥版本字节（xpub / tpub）
+    pub fn xpub_version(&self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0x04, 0x88, 0xB2, 0x1E],
+            _ => [0x04, 0x35, 0x87, 0xCF],
+        }
+    }
+
+    /// 根据扩展私钥版本字节识别网络
+    ///
+    /// 所有测试网络共用 tprv，无法区分，统一返回 Testnet3。
+    pub fn from_xprv_version(version: &[u8; 4]) -> Option<Self> {
+        Self::ALL.into_iter().find(|n| n.xprv_version() == *version)
+    }
+
+    /// 根据扩展公钥版本字节识别网络（测试网络统一返回 Testnet3）
+    pub fn from_xpub_version(version: &[u8; 4]) -> Option<Self> {
+        Self::ALL.into_iter().find(|n| n.xpub_version() == *version)
+    }
+
+    /// 两个网络的扩展密钥是否可以互换（主网与测试网络不能混用）
+    pub fn shares_extended_keys_with(&self, other: Network) -> bool {
+        self.is_mainnet() == other.is_mainnet()
+    }
+
+    /// 存储用的网络编号
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Network::Mainnet => 0,
+            Network::Testnet3 => 1,
+            Network::Testnet4 => 2,
+            Network::Signet => 3,
+            Network::Regtest => 4,
+        }
+    }
+
+    /// 从存储的网络编号恢复
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|n| n.to_u8() == value)
+    }
+
+    /// 获取网络的显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "Mainnet",
+            Network::Testnet3 => "Testnet3",
+            Network::Testnet4 => "Testnet4",
+            Network::Signet => "Signet",
+            Network::Regtest => "Regtest",
+        }
+    }
+}
+
@@ -2,6 +2,7 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 use crate::sha256::Sha256;
+use crate::sha512::HmacSha512;
 
 /// HMAC-SHA256 实现
 fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
@@ -70,7 +71,42 @@ pub fn pbkdf2_hmac_sha256(password: &[u8], salt: &[u8], iterations: u32, output:
     }
 }
 
+/// PBKDF2-HMAC-SHA512 实现
+pub fn pbkdf2_hmac_sha512(password: &[u8], salt: &[u8], iterations: u32, output: &mut [u8]) {
+    let block_count = (output.len() + 63) / 64;
+
+    // 只处理一次密钥填充，之后每次迭代克隆该上下文
+    let keyed = HmacSha512::new(password);
+
+    for i in 0..block_count {
+        // U1 = HMAC(password, salt || block_number (big-endian))
+        let block_num = (i + 1) as u32;
+        let mut mac = keyed.clone();
+        mac.update(salt);
+        mac.update(&block_num.to_be_bytes());
+        let mut u = mac.finalize();
+        let mut t = u;
+
+        // U2, U3, ..., U_iterations
+        for _ in 1..iterations {
+            let mut mac = keyed.clone();
+            mac.update(&u);
+            u = mac.finalize();
+            // T = U1 XOR U2 XOR ... XOR U_iterations
+            for (tj, uj) in t.iter_mut().zip(u.iter()) {
+                *tj ^= uj;
+            }
+        }
+
+        // 复制到输出
+        let start = i * 64;
+        let end = core::cmp::min(start + 64, output.len());
+        output[start..end].copy_from_slice(&t[..end - start]);
+    }
+}
+
 /// 从助记词和密码短语生成种子（BIP39）
+/// seed = PBKDF2-HMAC-SHA512(mnemonic, "mnemonic" || passphrase, 2048 轮, 64 字节)
 pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> [u8; 64] {
     let mut seed = [0u8; 64];
     
@@ -79,7 +115,7 @@ pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> [u8; 64] {
     salt.extend_from_slice(b"mnemonic");
     salt.extend_from_slice(passphrase.as_bytes());
     
-    pbkdf2_hmac_sha256(mnemonic.as_bytes(), &salt, 2048, &mut seed);
+    pbkdf2_hmac_sha512(mnemonic.as_bytes(), &salt, 2048, &mut seed);
     seed
 }
 
@@ -0,0 +1,789 @@
+extern crate alloc;
+
+use crate::address::{hash160, Cryptocurrency};
+use crate::bip32::{DerivationPath, MasterKey, HARDENED};
+use crate::flipper_wallet_core::Wallet;
+use crate::script::{self, ScriptKind};
+use crate::secp256k1::XOnlyPublicKey;
+use crate::sighash::{self, SIGHASH_ALL, SIGHASH_DEFAULT};
+use crate::storage;
+use crate::transaction::{
+    write_var_bytes, Decoder, OutPoint, Transaction, TxIn, TxOut, SEQUENCE_FINAL,
+};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// PSBT 魔数："psbt" || 0xff
+const MAGIC: &[u8; 5] = b"psbt\xff";
+
+/// PSBT 文件所在目录
+pub const PSBT_DIR: &str = "/ext/apps_data/flipperwallet/";
+
+// 全局字段（BIP174 / BIP370）
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_GLOBAL_TX_VERSION: u8 = 0x02;
+const PSBT_GLOBAL_FALLBACK_LOCKTIME: u8 = 0x03;
+const PSBT_GLOBAL_INPUT_COUNT: u8 = 0x04;
+const PSBT_GLOBAL_OUTPUT_COUNT: u8 = 0x05;
+const PSBT_GLOBAL_VERSION: u8 = 0xFB;
+
+// 输入字段
+const PSBT_IN_NON_WITNESS_UTXO: u8 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+const PSBT_IN_PARTIAL_SIG: u8 = 0x02;
+const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+const PSBT_IN_REDEEM_SCRIPT: u8 = 0x04;
+const PSBT_IN_BIP32_DERIVATION: u8 = 0x06;
+const PSBT_IN_FINAL_SCRIPTSIG: u8 = 0x07;
+const PSBT_IN_FINAL_SCRIPTWITNESS: u8 = 0x08;
+const PSBT_IN_PREVIOUS_TXID: u8 = 0x0E;
+const PSBT_IN_OUTPUT_INDEX: u8 = 0x0F;
+const PSBT_IN_SEQUENCE: u8 = 0x10;
+const PSBT_IN_REQUIRED_TIME_LOCKTIME: u8 = 0x11;
+const PSBT_IN_REQUIRED_HEIGHT_LOCKTIME: u8 = 0x12;
+const PSBT_IN_TAP_KEY_SIG: u8 = 0x13;
+const PSBT_IN_TAP_BIP32_DERIVATION: u8 = 0x16;
+const PSBT_IN_TAP_INTERNAL_KEY: u8 = 0x17;
+const PSBT_IN_TAP_MERKLE_ROOT: u8 = 0x18;
+
+// 输出字段
+const PSBT_OUT_BIP32_DERIVATION: u8 = 0x02;
+const PSBT_OUT_AMOUNT: u8 = 0x03;
+const PSBT_OUT_SCRIPT: u8 = 0x04;
+const PSBT_OUT_TAP_BIP32_DERIVATION: u8 = 0x07;
+
+/// PSBT 键值对（key 含类型字节）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pair {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+impl Pair {
+    /// 字段类型
+    pub fn key_type(&self) -> u8 {
+        self.key[0]
+    }
+
+    /// 类型字节之后的键数据
+    pub fn key_data(&self) -> &[u8] {
+        &self.key[1..]
+    }
+}
+
+/// PSBT 映射（全局、单个输入或单个输出），按原顺序保留全部字段
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Map {
+    pub pairs: Vec<Pair>,
+}
+
+impl Map {
+    /// 查找指定类型与键数据的字段值
+    pub fn get(&self, key_type: u8, key_data: &[u8]) -> Option<&[u8]> {
+        self.pairs
+            .iter()
+            .find(|pair| pair.key_type() == key_type && pair.key_data() == key_data)
+            .map(|pair| pair.value.as_slice())
+    }
+
+    /// 指定类型的所有字段
+    pub fn all(&self, key_type: u8) -> impl Iterator<Item = &Pair> {
+        self.pairs.iter().filter(move |pair| pair.key_type() == key_type)
+    }
+
+    /// 是否包含指定类型的字段
+    pub fn contains(&self, key_type: u8) -> bool {
+        self.all(key_type).next().is_some()
+    }
+
+    /// 添加字段（已存在同名键时不覆盖）
+    fn insert(&mut self, key_type: u8, key_data: &[u8], value: Vec<u8>) -> bool {
+        if self.get(key_type, key_data).is_some() {
+            return false;
+        }
+        let mut key = Vec::with_capacity(1 + key_data.len());
+        key.push(key_type);
+        key.extend_from_slice(key_data);
+        self.pairs.push(Pair { key, value });
+        true
+    }
+
+    fn parse(decoder: &mut Decoder) -> Result<Self, &'static str> {
+        let mut map = Map::default();
+        loop {
+            let key = decoder.var_bytes()?.to_vec();
+            if key.is_empty() {
+                return Ok(map);
+            }
+            let value = decoder.var_bytes()?.to_vec();
+
+            if map.pairs.iter().any(|pair| pair.key == key) {
+                return Err("Duplicate PSBT key");
+            }
+            map.pairs.push(Pair { key, value });
+        }
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        for pair in &self.pairs {
+            write_var_bytes(out, &pair.key);
+            write_var_bytes(out, &pair.value);
+        }
+        out.push(0x00);
+    }
+
+    /// 读取定长字段值（不存在返回 None，长度不符返回错误）
+    fn get_array<const N: usize>(&self, key_type: u8) -> Result<Option<[u8; N]>, &'static str> {
+        match self.get(key_type, &[]) {
+            Some(value) => value
+                .try_into()
+                .map(Some)
+                .map_err(|_| "Invalid PSBT field length"),
+            None => Ok(None),
+        }
+    }
+
+    fn get_u32(&self, key_type: u8) -> Result<Option<u32>, &'static str> {
+        Ok(self.get_array::<4>(key_type)?.map(u32::from_le_bytes))
+    }
+}
+
+/// 一个输出的审核信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputReview {
+    pub value: u64,
+    /// 脚本种类；OP_RETURN 输出带有数据，非标准脚本需要明确提示用户
+    pub kind: ScriptKind,
+    /// 当前网络下的比特币地址（脚本没有地址时为 None）
+    pub address: Option<Vec<u8>>,
+    /// 是否为本钱包的找零
+    pub is_change: bool,
+}
+
+/// 部分签名交易（PSBT v0 或 v2）
+#[derive(Debug, Clone)]
+pub struct Psbt {
+    pub version: u32,
+    pub global: Map,
+    pub inputs: Vec<Map>,
+    pub outputs: Vec<Map>,
+    /// 待签名交易（v0 取自全局字段，v2 由各输入输出字段组装）
+    tx: Transaction,
+}
+
+impl Psbt {
+    /// 解析二进制 PSBT
+    pub fn parse(data: &[u8]) -> Result<Self, &'static str> {
+        let mut decoder = Decoder::new(data);
+        if decoder.bytes(MAGIC.len())? != MAGIC {
+            return Err("Invalid PSBT magic");
+        }
+
+        let global = Map::parse(&mut decoder)?;
+        let version = global.get_u32(PSBT_GLOBAL_VERSION)?.unwrap_or(0);
+
+        let (input_count, output_count) = match version {
+            0 => {
+                let tx = global
+                    .get(PSBT_GLOBAL_UNSIGNED_TX, &[])
+                    .ok_or("Missing unsigned transaction")?;
+                let tx = Transaction::deserialize(tx)?;
+                (tx.inputs.len(), tx.outputs.len())
+            }
+            2 => {
+                if global.contains(PSBT_GLOBAL_UNSIGNED_TX) {
+                    return Err("PSBT v2 must not contain an unsigned transaction");
+                }
+                let input_count = global
+                    .get(PSBT_GLOBAL_INPUT_COUNT, &[])
+                    .ok_or("Missing input count")?;
+                let output_count = global
+                    .get(PSBT_GLOBAL_OUTPUT_COUNT, &[])
+                    .ok_or("Missing output count")?;
+                (read_count(input_count)?, read_count(output_count)?)
+            }
+            _ => return Err("Unsupported PSBT version"),
+        };
+
+        // 每个映射至少占 1 字节分隔符，先检查数量避免按伪造的计数分配内存
+        if input_count.saturating_add(output_count) > decoder.remaining() {
+            return Err("Truncated PSBT");
+        }
+        let mut inputs = Vec::with_capacity(input_count);
+        for _ in 0..input_count {
+            inputs.push(Map::parse(&mut decoder)?);
+        }
+        let mut outputs = Vec::with_capacity(output_count);
+        for _ in 0..output_count {
+            outputs.push(Map::parse(&mut decoder)?);
+        }
+        decoder.finish()?;
+
+        let tx = match version {
+            0 => Self::v0_transaction(&global, &inputs, &outputs)?,
+            _ => Self::v2_transaction(&global, &inputs, &outputs)?,
+        };
+
+        Ok(Psbt { version, global, inputs, outputs, tx })
+    }
+
+    /// v0：取全局未签名交易，并确认没有混入 v2 专用字段
+    fn v0_transaction(global: &Map, inputs: &[Map], outputs: &[Map]) -> Result<Transaction, &'static str> {
+        let tx = Transaction::deserialize(global.get(PSBT_GLOBAL_UNSIGNED_TX, &[]).unwrap_or(&[]))?;
+        if tx.inputs.iter().any(|input| !input.script_sig.is_empty() || !input.witness.is_empty()) {
+            return Err("Unsigned transaction has scriptSigs or witnesses");
+        }
+
+        let v2_global = [
+            PSBT_GLOBAL_TX_VERSION,
+            PSBT_GLOBAL_FALLBACK_LOCKTIME,
+            PSBT_GLOBAL_INPUT_COUNT,
+            PSBT_GLOBAL_OUTPUT_COUNT,
+        ];
+        let v2_input = [
+            PSBT_IN_PREVIOUS_TXID,
+            PSBT_IN_OUTPUT_INDEX,
+            PSBT_IN_SEQUENCE,
+            PSBT_IN_REQUIRED_TIME_LOCKTIME,
+            PSBT_IN_REQUIRED_HEIGHT_LOCKTIME,
+        ];
+        let v2_output = [PSBT_OUT_AMOUNT, PSBT_OUT_SCRIPT];
+        if v2_global.iter().any(|&t| global.contains(t))
+            || inputs.iter().any(|map| v2_input.iter().any(|&t| map.contains(t)))
+            || outputs.iter().any(|map| v2_output.iter().any(|&t| map.contains(t)))
+        {
+            return Err("PSBT v0 contains v2 fields");
+        }
+
+        Ok(tx)
+    }
+
+    /// v2：由全局、输入与输出字段组装待签名交易
+    fn v2_transaction(global: &Map, inputs: &[Map], outputs: &[Map]) -> Result<Transaction, &'static str> {
+        let version = global
+            .get_u32(PSBT_GLOBAL_TX_VERSION)?
+            .ok_or("Missing transaction version")?;
+
+        let mut tx_inputs = Vec::with_capacity(inputs.len());
+        for map in inputs {
+            let txid = map
+                .get_array(PSBT_IN_PREVIOUS_TXID)?
+                .ok_or("Missing previous txid")?;
+            let vout = map
+                .get_u32(PSBT_IN_OUTPUT_INDEX)?
+                .ok_or("Missing output index")?;
+            let sequence = map.get_u32(PSBT_IN_SEQUENCE)?.unwrap_or(SEQUENCE_FINAL);
+            tx_inputs.push(TxIn::new(OutPoint::new(txid, vout), sequence));
+        }
+
+        let mut tx_outputs = Vec::with_capacity(outputs.len());
+        for map in outputs {
+            let value = map
+                .get_array(PSBT_OUT_AMOUNT)?
+                .map(u64::from_le_bytes)
+                .ok_or("Missing output amount")?;
+            let script_pubkey = map
+                .get(PSBT_OUT_SCRIPT, &[])
+                .ok_or("Missing output script")?
+                .to_vec();
+            tx_outputs.push(TxOut { value, script_pubkey });
+        }
+
+        Ok(Transaction {
+            version,
+            inputs: tx_inputs,
+            outputs: tx_outputs,
+            lock_time: Self::v2_lock_time(global, inputs)?,
+        })
+    }
+
+    /// BIP370 锁定时间：所有带要求的输入都支持的类型取最大值（都支持时优先区块高度），
+    /// 没有输入提出要求时使用后备值
+    fn v2_lock_time(global: &Map, inputs: &[Map]) -> Result<u32, &'static str> {
+        let mut any_required = false;
+        let mut height_ok = true;
+        let mut time_ok = true;
+        let mut max_height = 0;
+        let mut max_time = 0;
+
+        for map in inputs {
+            let time = map.get_u32(PSBT_IN_REQUIRED_TIME_LOCKTIME)?;
+            let height = map.get_u32(PSBT_IN_REQUIRED_HEIGHT_LOCKTIME)?;
+            if time.is_none() && height.is_none() {
+                continue;
+            }
+            any_required = true;
+            match height {
+                Some(h) => max_height = max_height.max(h),
+                None => height_ok = false,
+            }
+            match time {
+                Some(t) => max_time = max_time.max(t),
+                None => time_ok = false,
+            }
+        }
+
+        if !any_required {
+            return Ok(global.get_u32(PSBT_GLOBAL_FALLBACK_LOCKTIME)?.unwrap_or(0));
+        }
+        if height_ok {
+            Ok(max_height)
+        } else if time_ok {
+            Ok(max_time)
+        } else {
+            Err("Inputs have conflicting lock time requirements")
+        }
+    }
+
+    /// 序列化为二进制 PSBT
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        self.global.write(&mut out);
+        for map in self.inputs.iter().chain(self.outputs.iter()) {
+            map.write(&mut out);
+        }
+        out
+    }
+
+    /// 交易版本
+    pub fn tx_version(&self) -> u32 {
+        self.tx.version
+    }
+
+    /// 交易锁定时间
+    pub fn lock_time(&self) -> u32 {
+        self.tx.lock_time
+    }
+
+    /// 第 index 个输出
+    pub fn output(&self, index: usize) -> Option<&TxOut> {
+        self.tx.outputs.get(index)
+    }
+
+    /// 第 index 个输入花费的输出（来自见证 UTXO 或完整的前序交易）
+    ///
+    /// 两者都存在时必须一致：见证 UTXO 中的金额不受前序交易哈希保护，
+    /// 单独信任它会让恶意主机虚报金额（CVE-2020-14199）。
+    pub fn spent_output(&self, index: usize) -> Result<TxOut, &'static str> {
+        let map = self.inputs.get(index).ok_or("Input index out of range")?;
+        let input = &self.tx.inputs[index];
+
+        let from_prev_tx = match map.get(PSBT_IN_NON_WITNESS_UTXO, &[]) {
+            Some(prev_tx) => {
+                let prev_tx = Transaction::deserialize(prev_tx)?;
+                if prev_tx.txid() != input.previous_output.txid {
+                    return Err("Previous transaction does not match input");
+                }
+                let output = prev_tx
+                    .outputs
+                    .get(input.previous_output.vout as usize)
+                    .cloned()
+                    .ok_or("Previous output index out of range")?;
+                Some(output)
+            }
+            None => None,
+        };
+
+        match (map.get(PSBT_IN_WITNESS_UTXO, &[]), from_prev_tx) {
+            (Some(value), from_prev_tx) => {
+                let mut decoder = Decoder::new(value);
+                let output = TxOut::consensus_decode(&mut decoder)?;
+                decoder.finish()?;
+                if from_prev_tx.is_some_and(|prev| prev != output) {
+                    return Err("Witness UTXO does not match previous transaction");
+                }
+                Ok(output)
+            }
+            (None, Some(output)) => Ok(output),
+            (None, None) => Err("Missing UTXO information"),
+        }
+    }
+
+    /// 手续费：输入金额之和减去输出金额之和
+    pub fn fee(&self) -> Result<u64, &'static str> {
+        let mut input_total: u64 = 0;
+        for index in 0..self.inputs.len() {
+            input_total = input_total
+                .checked_add(self.spent_output(index)?.value)
+                .ok_or("Input amount overflow")?;
+        }
+        let mut output_total: u64 = 0;
+        for output in &self.tx.outputs {
+            output_total = output_total
+                .checked_add(output.value)
+                .ok_or("Output amount overflow")?;
+        }
+        input_total
+            .checked_sub(output_total)
+            .ok_or("Outputs exceed inputs")
+    }
+
+    /// 输出是否为钱包自己的找零
+    ///
+    /// 需要带有与本钱包指纹匹配的 BIP32 派生信息，且输出脚本确实支付给派生出的公钥，
+    /// 否则恶意 PSBT 可以给攻击者的输出附上本钱包的派生路径冒充找零。
+    pub fn is_change_output(&self, index: usize, wallet: &Wallet) -> bool {
+        let (master_key, fingerprint) = match signing_key(wallet) {
+            Ok(key) => key,
+            Err(_) => return false,
+        };
+        let (map, output) = match (self.outputs.get(index), self.output(index)) {
+            (Some(map), Some(output)) => (map, output),
+            _ => return false,
+        };
+
+        let ecdsa = map.all(PSBT_OUT_BIP32_DERIVATION).any(|pair| {
+            parse_key_origin(&pair.value).is_ok_and(|(fp, path)| {
+                fp == fingerprint
+                    && master_key
+                        .derive_path(&path)
+                        .and_then(|key| key.public_key())
+                        .is_ok_and(|pk| {
+                            pk.serialize_compressed()[..] == *pair.key_data()
+                                && script::pays_to_public_key(&output.script_pubkey, &pk)
+                        })
+            })
+        });
+        let taproot = map.all(PSBT_OUT_TAP_BIP32_DERIVATION).any(|pair| {
+            parse_tap_key_origin(&pair.value).is_ok_and(|(fp, path)| {
+                fp == fingerprint
+                    && master_key
+                        .derive_path(&path)
+                        .and_then(|key| key.public_key())
+                        .is_ok_and(|pk| {
+                            pk.x_only_public_key().0.as_bytes()[..] == *pair.key_data()
+                                && script::pays_to_public_key(&output.script_pubkey, &pk)
+                        })
+            })
+        });
+        ecdsa || taproot
+    }
+
+    /// 审核界面展示一个输出所需的信息
+    pub fn review_output(&self, index: usize, wallet: &Wallet) -> Result<OutputReview, &'static str> {
+        let output = self.output(index).ok_or("Output index out of range")?;
+        let address = script::to_address(&output.script_pubkey, Cryptocurrency::Bitcoin, wallet.network()).ok();
+        Ok(OutputReview {
+            value: output.value,
+            kind: script::classify(&output.script_pubkey),
+            address,
+            is_change: self.is_change_output(index, wallet),
+        })
+    }
+
+    /// 用钱包为所有可签名的输入添加部分签名，返回新增的签名数
+    ///
+    /// 只签名 BIP32 派生信息中的主密钥指纹与本钱包一致、且派生出的公钥与记录相符的输入，
+    /// 支持 P2PKH、P2WPKH、P2SH-P2WPKH 与 Taproot 密钥路径；已最终化的输入会被跳过。
+    /// 哈希类型只接受 SIGHASH_ALL（Taproot 另可 SIGHASH_DEFAULT）。
+    /// 不满足这些条件的输入被跳过，其余输入照常签名；只有 PSBT 本身的结构错误
+    /// （无法解析的字段、与前序交易矛盾的 UTXO）才返回错误。
+    pub fn sign(&mut self, wallet: &Wallet) -> Result<usize, &'static str> {
+        let (master_key, fingerprint) = signing_key(wallet)?;
+
+        let mut signed = 0;
+        for index in 0..self.inputs.len() {
+            let map = &self.inputs[index];
+            if map.contains(PSBT_IN_FINAL_SCRIPTSIG) || map.contains(PSBT_IN_FINAL_SCRIPTWITNESS) {
+                continue;
+            }
+            signed += self.sign_ecdsa_input(index, &master_key, fingerprint)?;
+            signed += self.sign_taproot_input(index, &master_key, fingerprint)?;
+        }
+        Ok(signed)
+    }
+
+    /// 为 P2PKH / P2WPKH / P2SH-P2WPKH 输入添加 ECDSA 部分签名
+    fn sign_ecdsa_input(
+        &mut self,
+        index: usize,
+        master_key: &MasterKey,
+        fingerprint: [u8; 4],
+    ) -> Result<usize, &'static str> {
+        let map = &self.inputs[index];
+        let mut keys = Vec::new();
+        for pair in map.all(PSBT_IN_BIP32_DERIVATION) {
+            // 格式错误或与派生结果不符的记录不属于本钱包，跳过它，继续签名其他输入
+            let (fp, path) = match parse_key_origin(&pair.value) {
+                Ok(origin) => origin,
+                Err(_) => continue,
+            };
+            if fp != fingerprint || map.get(PSBT_IN_PARTIAL_SIG, pair.key_data()).is_some() {
+                continue;
+            }
+            let key = match master_key.derive_path(&path) {
+                Ok(key) => key,
+                Err(_) => continue,
+            };
+            if key.public_key()?.serialize_compressed()[..] != *pair.key_data() {
+                continue;
+            }
+            keys.push(key);
+        }
+        if keys.is_empty() {
+            return Ok(0);
+        }
+        // 传统输入的金额不受签名保护；BIP143 虽然签名金额，但同一交易的多个输入可以被
+        // 分别欺骗（CVE-2020-14199），因此都必须用完整的前序交易核对，缺少时不签这个输入
+        if !map.contains(PSBT_IN_NON_WITNESS_UTXO) {
+            return Ok(0);
+        }
+        // 审核界面展示的输出与手续费只有在签名覆盖全部输入输出时才成立；
+        // NONE / SINGLE / ANYONECANPAY 允许签名后改写交易，不签这个输入
+        let sighash_type = map.get_u32(PSBT_IN_SIGHASH_TYPE)?.unwrap_or(SIGHASH_ALL);
+        if sighash_type != SIGHASH_ALL {
+            return Ok(0);
+        }
+
+        let spent = self.spent_output(index)?;
+        let redeem_script = map.get(PSBT_IN_REDEEM_SCRIPT, &[]).map(|script| script.to_vec());
+
+        let mut added = 0;
+        for key in keys {
+            let public_key = key.public_key()?;
+            let pubkey = public_key.serialize_compressed();
+            let pubkey_hash = hash160(&pubkey);
+            let witness_program = script::p2wpkh(&pubkey_hash);
+
+            let legacy = spent.script_pubkey == script::p2pkh(&pubkey_hash);
+            let segwit_v0 = spent.script_pubkey == witness_program
+                || (spent.script_pubkey == script::p2sh_p2wpkh(&pubkey_hash)
+                    && redeem_script.as_deref() == Some(&witness_program[..]));
+            if !legacy && !segwit_v0 {
+                // 不支持的脚本（例如多签）
+                continue;
+            }
+
+            let hash = if legacy {
+                sighash::legacy(&self.tx, index, &spent.script_pubkey, sighash_type)?
+            } else {
+                let script_code = script::p2pkh(&pubkey_hash);
+                sighash::segwit_v0(&self.tx, index, &script_code, spent.value, sighash_type)?
+            };
+
+            // 发布前自检，防止故障注入导致密钥泄露
+            let signature = key.secret_key().sign_ecdsa(&hash);
+            if !public_key.verify_ecdsa(&hash, &signature) {
+                return Err("ECDSA signature self-check failed");
+            }
+            let mut sig = signature.serialize_der();
+            sig.push(sighash_type as u8);
+            if self.inputs[index].insert(PSBT_IN_PARTIAL_SIG, &pubkey, sig) {
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// 为 Taproot 输入添加密钥路径 Schnorr 签名（脚本路径暂不支持）
+    fn sign_taproot_input(
+        &mut self,
+        index: usize,
+        master_key: &MasterKey,
+        fingerprint: [u8; 4],
+    ) -> Result<usize, &'static str> {
+        let map = &self.inputs[index];
+        if map.contains(PSBT_IN_TAP_KEY_SIG) {
+            return Ok(0);
+        }
+        let internal_key: [u8; 32] = match map.get_array(PSBT_IN_TAP_INTERNAL_KEY)? {
+            Some(key) => key,
+            None => return Ok(0),
+        };
+
+        let mut signing_key = None;
+        for pair in map.all(PSBT_IN_TAP_BIP32_DERIVATION) {
+            // 只有内部密钥可以走密钥路径
+            if pair.key_data() != internal_key {
+                continue;
+            }
+            let (fp, path) = match parse_tap_key_origin(&pair.value) {
+                Ok(origin) => origin,
+                Err(_) => continue,
+            };
+            if fp != fingerprint {
+                continue;
+            }
+            let key = match master_key.derive_path(&path) {
+                Ok(key) => key,
+                Err(_) => continue,
+            };
+            if *key.public_key()?.x_only_public_key().0.as_bytes() != internal_key {
+                continue;
+            }
+            signing_key = Some(key);
+        }
+        let key = match signing_key {
+            Some(key) => key,
+            None => return Ok(0),
+        };
+
+        let merkle_root: Option<[u8; 32]> = map.get_array(PSBT_IN_TAP_MERKLE_ROOT)?;
+        // 与 ECDSA 相同，只接受覆盖全部输入输出的 DEFAULT / ALL
+        let sighash_type = map.get_u32(PSBT_IN_SIGHASH_TYPE)?.unwrap_or(SIGHASH_DEFAULT);
+        if sighash_type != SIGHASH_DEFAULT && sighash_type != SIGHASH_ALL {
+            return Ok(0);
+        }
+
+        let (output_key, _) = XOnlyPublicKey::from_bytes(&internal_key)?.tap_tweak(merkle_root.as_ref())?;
+        let spent = self.spent_output(index)?;
+        if spent.script_pubkey != script::p2tr(output_key.as_bytes()) {
+            // 输出密钥与内部密钥、默克尔根不符：不是本钱包的输出，不签这个输入
+            return Ok(0);
+        }
+
+        // BIP341 签名覆盖所有输入花费的金额与脚本，缺少任何一个都无法签名
+        let mut spent_outputs = Vec::with_capacity(self.inputs.len());
+        for (i, input) in self.inputs.iter().enumerate() {
+            if !input.contains(PSBT_IN_WITNESS_UTXO) && !input.contains(PSBT_IN_NON_WITNESS_UTXO) {
+                return Ok(0);
+            }
+            spent_outputs.push(self.spent_output(i)?);
+        }
+        let hash = sighash::taproot(&self.tx, index, &spent_outputs, sighash_type, None, None)?;
+
+        let tweaked = key.secret_key().tap_tweak(merkle_root.as_ref())?;
+        let mut sig = tweaked.sign_schnorr(&hash, None)?.serialize().to_vec();
+        if sighash_type != SIGHASH_DEFAULT {
+            sig.push(sighash_type as u8);
+        }
+        self.inputs[index].insert(PSBT_IN_TAP_KEY_SIG, &[], sig);
+        Ok(1)
+    }
+}
+
+/// 读取 PSBT 文件、签名，并把结果写到同一目录下的 "-signed" 文件
+///
+/// 文件可以是二进制 PSBT 或 Base64 文本，输出保持与输入相同的格式。
+/// 返回签名文件路径与新增的签名数。
+pub fn sign_file(wallet: &Wallet, path: &str) -> Result<(String, usize), &'static str> {
+    let (mut psbt, binary) = load(path)?;
+    let signed = psbt.sign(wallet)?;
+    if signed == 0 {
+        return Err("No inputs could be signed");
+    }
+    Ok((save_signed(path, &psbt, binary)?, signed))
+}
+
+/// 读取 PSBT 文件（二进制或 Base64 文本），同时返回文件是否为二进制格式
+pub fn load(path: &str) -> Result<(Psbt, bool), &'static str> {
+    let data = storage::load_file(&alloc::format!("{}\0", path))?;
+    let binary = data.starts_with(MAGIC);
+    let raw = if binary { data } else { base64_decode(trim_ascii(&data))? };
+    Ok((Psbt::parse(&raw)?, binary))
+}
+
+/// 把签名后的 PSBT 按原文件格式写到 "-signed" 文件，返回写入的路径
+pub fn save_signed(path: &str, psbt: &Psbt, binary: bool) -> Result<String, &'static str> {
+    let out = psbt.serialize();
+    let out = if binary { out } else { base64_encode(&out) };
+    let out_path = signed_path(path);
+    storage::write_file(&alloc::format!("{}\0", out_path), &out)?;
+    Ok(out_path)
+}
+
+/// 签名文件路径：foo.psbt → foo-signed.psbt
+pub fn signed_path(path: &str) -> String {
+    let stem = path.strip_suffix(".psbt").unwrap_or(path);
+    alloc::format!("{}-signed.psbt", stem)
+}
+
+/// 可以签名的钱包主私钥及其指纹
+fn signing_key(wallet: &Wallet) -> Result<(MasterKey, [u8; 4]), &'static str> {
+    let master_key = *wallet.master_key().ok_or("Wallet cannot sign")?;
+    let fingerprint = master_key.fingerprint()?;
+    Ok((master_key, fingerprint))
+}
+
+/// 解析密钥来源：4 字节主密钥指纹 || 若干 32 位小端序索引
+fn parse_key_origin(value: &[u8]) -> Result<([u8; 4], DerivationPath), &'static str> {
+    if value.len() < 4 || (value.len() - 4) % 4 != 0 {
+        return Err("Invalid key origin");
+    }
+
+    let mut fingerprint = [0u8; 4];
+    fingerprint.copy_from_slice(&value[..4]);
+    let mut path = DerivationPath::master();
+    for chunk in value[4..].chunks(4) {
+        let index = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        path.push(index & !HARDENED, index & HARDENED != 0)?;
+    }
+    Ok((fingerprint, path))
+}
+
+/// 解析 Taproot 密钥来源：叶子哈希列表 || 密钥来源
+fn parse_tap_key_origin(value: &[u8]) -> Result<([u8; 4], DerivationPath), &'static str> {
+    let mut decoder = Decoder::new(value);
+    let leaf_count = decoder.length()?;
+    decoder.bytes(leaf_count.checked_mul(32).ok_or("Invalid key origin")?)?;
+    parse_key_origin(decoder.bytes(decoder.remaining())?)
+}
+
+/// 整个字段恰好是一个 CompactSize 计数（PSBT v2 的输入、输出个数）
+fn read_count(value: &[u8]) -> Result<usize, &'static str> {
+    let mut decoder = Decoder::new(value);
+    let count = decoder.compact_size()?;
+    decoder.finish()?;
+    usize::try_from(count).map_err(|_| "Count too large")
+}
+
+/// 去掉首尾 ASCII 空白
+fn trim_ascii(data: &[u8]) -> &[u8] {
+    let start = data
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    let end = data
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(start, |pos| pos + 1);
+    &data[start..end]
+}
+
+/// Base64 字母表（RFC 4648，Bitcoin Core 与 Sparrow 导出 PSBT 文本时使用）
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let mut block = [0u8; 3];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let n = (block[0] as u32) << 16 | (block[1] as u32) << 8 | block[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_CHARS[(n >> (18 - 6 * i)) as usize & 0x3F]);
+            } else {
+                out.push(b'=');
+            }
+        }
+    }
+    out
+}
+
+fn base64_decode(input: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if input.len() % 4 != 0 {
+        return Err("Invalid base64 length");
+    }
+
+    let block_count = input.len() / 4;
+    let mut out = Vec::with_capacity(block_count * 3);
+    for (i, chunk) in input.chunks(4).enumerate() {
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && i + 1 != block_count) {
+            return Err("Invalid base64 padding");
+        }
+
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            let digit = BASE64_CHARS
+                .iter()
+                .position(|&b| b == c)
+                .ok_or("Invalid base64 character")?;
+            n = n << 6 | digit as u32;
+        }
+        n <<= 6 * padding as u32;
+        out.extend_from_slice(&[(n >> 16) as u8, (n >> 8) as u8, n as u8][..3 - padding]);
+    }
+    Ok(out)
+}
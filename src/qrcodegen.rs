@@ -98,7 +98,7 @@ impl QrCode {
             bb.append_bits(padbyte, 8);
         }
 
-        let mut datacodewords = Vec::from(vec![0u8; bb.0.len() / 8]);
+        let mut datacodewords = vec![0u8; bb.0.len() / 8];
         for (i, &bit) in bb.0.iter().enumerate() {
             datacodewords[i >> 3] |= u8::from(bit) << (7 - (i & 7));
         }
@@ -118,8 +118,8 @@ impl QrCode {
             size: size as i32,
             mask: Mask::new(0),
             errorcorrectionlevel: ecl,
-            modules: Vec::from(vec![false; size * size]),
-            isfunction: Vec::from(vec![false; size * size]),
+            modules: vec![false; size * size],
+            isfunction: vec![false; size * size],
         };
 
         // Compute ECC, draw modules
@@ -197,8 +197,8 @@ impl QrCode {
         for i in 0..numalign {
             for j in 0..numalign {
                 // Don't draw on the three finder corners
-                if !(i == 0 && j == 0 || i == 0 && j == numalign - 1 || i == numalign - 1 && j == 0)
-                {
+                let corner = i == 0 && (j == 0 || j == numalign - 1) || i == numalign - 1 && j == 0;
+                if !corner {
                     self.draw_alignment_pattern(alignpatpos[i], alignpatpos[j]);
                 }
             }
@@ -388,6 +388,8 @@ impl QrCode {
         }
     }
 
+    // Run-length checks read clearer as an if chain than as a match on Ordering
+    #[allow(clippy::comparison_chain)]
     fn get_penalty_score(&self) -> i32 {
         let mut result: i32 = 0;
         let size: i32 = self.size;
@@ -458,9 +460,9 @@ impl QrCode {
         let dark: i32 = self.modules.iter().copied().map(i32::from).sum();
         let total: i32 = size * size; // Note that size is odd, so dark/total != 1/2
         let k: i32 = ((dark * 20 - total * 10).abs() + total - 1) / total - 1;
-        debug_assert!(0 <= k && k <= 9);
+        debug_assert!((0..=9).contains(&k));
         result += k * PENALTY_N4;
-        debug_assert!(0 <= result && result <= 2568888);
+        debug_assert!((0..=2568888).contains(&result));
         result
     }
 
@@ -693,10 +695,7 @@ impl QrSegment {
         let mut accumdata: u32 = 0;
         let mut accumcount: u8 = 0;
         for b in text.bytes() {
-            assert!(
-                (b'0'..=b'9').contains(&b),
-                "String contains non-numeric characters"
-            );
+            assert!(b.is_ascii_digit(), "String contains non-numeric characters");
             accumdata = accumdata * 10 + u32::from(b - b'0');
             accumcount += 1;
             if accumcount == 3 {
@@ -800,7 +799,7 @@ impl QrSegment {
     }
 
     pub fn is_numeric(text: &str) -> bool {
-        text.chars().all(|c| ('0'..='9').contains(&c))
+        text.chars().all(|c| c.is_ascii_digit())
     }
 
     pub fn is_alphanumeric(text: &str) -> bool {
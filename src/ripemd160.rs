@@ -85,146 +85,89 @@ impl Ripemd160 {
 
     /// 处理 64 字节的数据块
     fn process_block(&mut self) {
+        // 每一步选用的消息字索引（左线 / 右线）
+        const R_LEFT: [usize; 80] = [
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+            7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8,
+            3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12,
+            1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2,
+            4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+        ];
+        const R_RIGHT: [usize; 80] = [
+            5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12,
+            6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2,
+            15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13,
+            8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14,
+            12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+        ];
+        // 每一步的循环左移位数（左线 / 右线）
+        const S_LEFT: [u32; 80] = [
+            11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8,
+            7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12,
+            11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5,
+            11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12,
+            9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+        ];
+        const S_RIGHT: [u32; 80] = [
+            8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6,
+            9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11,
+            9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5,
+            15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8,
+            8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+        ];
+        // 每轮（16 步）的加法常量
+        const K_LEFT: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+        const K_RIGHT: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+        // 第 round 轮的非线性函数
+        fn f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+            match round {
+                0 => x ^ y ^ z,
+                1 => (x & y) | (!x & z),
+                2 => (x | !y) ^ z,
+                3 => (x & z) | (y & !z),
+                _ => x ^ (y | !z),
+            }
+        }
+
         // 将块转换为 16 个 32 位字（小端序）
         let mut x = [0u32; 16];
-        for i in 0..16 {
-            x[i] = (self.buffer[i * 4] as u32)
-                | ((self.buffer[i * 4 + 1] as u32) << 8)
-                | ((self.buffer[i * 4 + 2] as u32) << 16)
-                | ((self.buffer[i * 4 + 3] as u32) << 24);
+        for (word, chunk) in x.iter_mut().zip(self.buffer.chunks_exact(4)) {
+            *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
         }
 
         // 初始化工作变量
-        let mut al = self.state[0];
-        let mut bl = self.state[1];
-        let mut cl = self.state[2];
-        let mut dl = self.state[3];
-        let mut el = self.state[4];
-
-        let mut ar = self.state[0];
-        let mut br = self.state[1];
-        let mut cr = self.state[2];
-        let mut dr = self.state[3];
-        let mut er = self.state[4];
-
-        // 左线
+        let [mut al, mut bl, mut cl, mut dl, mut el] = self.state;
+        let [mut ar, mut br, mut cr, mut dr, mut er] = self.state;
+
         for j in 0..80 {
-            let (k, _rol_amount, _r_idx) = if j < 16 {
-                (0x00000000, 0, 0)
-            } else if j < 32 {
-                (0x5a827999, 0, 1)
-            } else if j < 48 {
-                (0x6ed9eba1, 0, 2)
-            } else if j < 64 {
-                (0x8f1bbcdc, 0, 3)
-            } else {
-                (0xa953fd4e, 0, 4)
-            };
-
-            let f_val = if j < 16 {
-                al ^ bl ^ cl
-            } else if j < 32 {
-                (al & bl) | (!al & cl)
-            } else if j < 48 {
-                (al | !bl) ^ cl
-            } else if j < 64 {
-                (al & cl) | (bl & !cl)
-            } else {
-                al ^ (bl | !cl)
-            };
-
-            let rol_amount = if j < 16 {
-                [11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8][j % 16]
-            } else if j < 32 {
-                [7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 4][j % 16]
-            } else if j < 48 {
-                [3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12][j % 16]
-            } else if j < 64 {
-                [1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2][j % 16]
-            } else {
-                [4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13][j % 16]
-            };
-
-            let r_idx = if j < 16 {
-                [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15][j]
-            } else if j < 32 {
-                [7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8][j % 16]
-            } else if j < 48 {
-                [3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12][j % 16]
-            } else if j < 64 {
-                [1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2][j % 16]
-            } else {
-                [4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13][j % 16]
-            };
-
-            let t = al.wrapping_add(f_val)
-                .wrapping_add(x[r_idx])
-                .wrapping_add(k);
+            let round = j / 16;
+
+            // 左线：轮函数顺序为 f1..f5
+            let t = al
+                .wrapping_add(f(round, bl, cl, dl))
+                .wrapping_add(x[R_LEFT[j]])
+                .wrapping_add(K_LEFT[round])
+                .rotate_left(S_LEFT[j])
+                .wrapping_add(el);
             al = el;
             el = dl;
             dl = cl.rotate_left(10);
             cl = bl;
-            bl = t.rotate_left(rol_amount);
-        }
-
-        // 右线
-        for j in 0..80 {
-            let f_val = if j < 16 {
-                ar ^ (br | !cr)
-            } else if j < 32 {
-                (ar & cr) | (br & !cr)
-            } else if j < 48 {
-                (ar | !br) ^ cr
-            } else if j < 64 {
-                (ar & br) | (!ar & cr)
-            } else {
-                ar ^ br ^ cr
-            };
-
-            let k = if j < 16 {
-                0x50a28be6
-            } else if j < 32 {
-                0x5c4dd124
-            } else if j < 48 {
-                0x6d703ef3
-            } else if j < 64 {
-                0x7a6d76e9
-            } else {
-                0x00000000
-            };
-
-            let rol_amount = if j < 16 {
-                [8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6][j % 16]
-            } else if j < 32 {
-                [9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11][j % 16]
-            } else if j < 48 {
-                [9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5][j % 16]
-            } else if j < 64 {
-                [15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8][j % 16]
-            } else {
-                [8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11][j % 16]
-            };
-
-            let r_idx = if j < 16 {
-                [5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12][j % 16]
-            } else if j < 32 {
-                [6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2][j % 16]
-            } else if j < 48 {
-                [15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13][j % 16]
-            } else if j < 64 {
-                [8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14][j % 16]
-            } else {
-                [12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11][j % 16]
-            };
-
-            let t = ar.wrapping_add(f_val)
-                .wrapping_add(x[r_idx])
-                .wrapping_add(k);
+            bl = t;
+
+            // 右线：轮函数顺序为 f5..f1
+            let t = ar
+                .wrapping_add(f(4 - round, br, cr, dr))
+                .wrapping_add(x[R_RIGHT[j]])
+                .wrapping_add(K_RIGHT[round])
+                .rotate_left(S_RIGHT[j])
+                .wrapping_add(er);
             ar = er;
             er = dr;
             dr = cr.rotate_left(10);
             cr = br;
-            br = t.rotate_left(rol_amount);
+            br = t;
         }
 
         // 更新状态
@@ -0,0 +1,455 @@
+extern crate alloc;
+
+use crate::address::{hash160, parse_address, AddressKind, AddressType, Cryptocurrency, ParsedAddress};
+use crate::base58;
+use crate::bech32;
+use crate::network::Network;
+use crate::secp256k1::{PublicKey, XOnlyPublicKey};
+use alloc::vec::Vec;
+
+/// 操作码（只列出钱包用到的部分）
+pub const OP_0: u8 = 0x00;
+pub const OP_PUSHDATA1: u8 = 0x4C;
+pub const OP_PUSHDATA2: u8 = 0x4D;
+pub const OP_PUSHDATA4: u8 = 0x4E;
+pub const OP_1NEGATE: u8 = 0x4F;
+pub const OP_1: u8 = 0x51;
+pub const OP_16: u8 = 0x60;
+pub const OP_VERIFY: u8 = 0x69;
+pub const OP_RETURN: u8 = 0x6A;
+pub const OP_DROP: u8 = 0x75;
+pub const OP_DUP: u8 = 0x76;
+pub const OP_EQUAL: u8 = 0x87;
+pub const OP_EQUALVERIFY: u8 = 0x88;
+pub const OP_SHA256: u8 = 0xA8;
+pub const OP_HASH160: u8 = 0xA9;
+pub const OP_CODESEPARATOR: u8 = 0xAB;
+pub const OP_CHECKSIG: u8 = 0xAC;
+pub const OP_CHECKSIGVERIFY: u8 = 0xAD;
+pub const OP_CHECKMULTISIG: u8 = 0xAE;
+pub const OP_CHECKLOCKTIMEVERIFY: u8 = 0xB1;
+pub const OP_CHECKSEQUENCEVERIFY: u8 = 0xB2;
+pub const OP_CHECKSIGADD: u8 = 0xBA;
+
+/// 标准中继策略允许的 OP_RETURN 数据长度
+pub const MAX_OP_RETURN_DATA: usize = 80;
+
+/// 脚本构建器
+///
+/// 按值链式调用，最后用 into_script 取出字节。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Builder {
+    script: Vec<u8>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self { script: Vec::new() }
+    }
+
+    /// 追加一个操作码
+    pub fn push_opcode(mut self, opcode: u8) -> Self {
+        self.script.push(opcode);
+        self
+    }
+
+    /// 推入数据，按长度选择直接推入或 OP_PUSHDATA1/2/4
+    ///
+    /// 空数据编码为 OP_0；单字节 1 到 16 不会改写为 OP_1..OP_16，
+    /// 需要最小编码的数字请用 push_int。
+    pub fn push_slice(mut self, data: &[u8]) -> Self {
+        let len = data.len();
+        if len < OP_PUSHDATA1 as usize {
+            self.script.push(len as u8);
+        } else if len <= 0xFF {
+            self.script.push(OP_PUSHDATA1);
+            self.script.push(len as u8);
+        } else if len <= 0xFFFF {
+            self.script.push(OP_PUSHDATA2);
+            self.script.extend_from_slice(&(len as u16).to_le_bytes());
+        } else {
+            self.script.push(OP_PUSHDATA4);
+            self.script.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+        self.script.extend_from_slice(data);
+        self
+    }
+
+    /// 推入整数（-1 与 0 到 16 使用专用操作码，其他按脚本数字最小编码推入）
+    pub fn push_int(self, value: i64) -> Self {
+        match value {
+            0 => self.push_opcode(OP_0),
+            -1 => self.push_opcode(OP_1NEGATE),
+            1..=16 => self.push_opcode(OP_1 + (value as u8 - 1)),
+            _ => {
+                let mut bytes = Vec::with_capacity(9);
+                let negative = value < 0;
+                let mut magnitude = value.unsigned_abs();
+                while magnitude > 0 {
+                    bytes.push((magnitude & 0xFF) as u8);
+                    magnitude >>= 8;
+                }
+                // 最高字节的最高位是符号位，被占用时补一个字节
+                if bytes[bytes.len() - 1] & 0x80 != 0 {
+                    bytes.push(if negative { 0x80 } else { 0x00 });
+                } else if negative {
+                    let last = bytes.len() - 1;
+                    bytes[last] |= 0x80;
+                }
+                self.push_slice(&bytes)
+            }
+        }
+    }
+
+    /// 推入 33 字节压缩公钥
+    pub fn push_key(self, public_key: &PublicKey) -> Self {
+        self.push_slice(&public_key.serialize_compressed())
+    }
+
+    /// 推入 32 字节 x-only 公钥（Tapscript）
+    pub fn push_x_only_key(self, public_key: &XOnlyPublicKey) -> Self {
+        self.push_slice(public_key.as_bytes())
+    }
+
+    pub fn len(&self) -> usize {
+        self.script.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.script.is_empty()
+    }
+
+    pub fn into_script(self) -> Vec<u8> {
+        self.script
+    }
+}
+
+/// 脚本中的一条指令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction<'a> {
+    /// 数据推入（OP_0 视为推入空数据）
+    Push(&'a [u8]),
+    /// 其他操作码（包括 OP_1NEGATE 与 OP_1..OP_16）
+    Op(u8),
+}
+
+/// 逐条解析脚本指令的迭代器
+///
+/// 推入数据超出脚本末尾时返回一次错误，之后结束。
+#[derive(Debug, Clone)]
+pub struct Instructions<'a> {
+    script: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Instructions<'a> {
+    /// 下一条指令在脚本中的字节偏移
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    fn push_length(&self, opcode: u8) -> Option<(usize, usize)> {
+        let rest = &self.script[self.pos + 1..];
+        match opcode {
+            0x00..=0x4B => Some((1, opcode as usize)),
+            OP_PUSHDATA1 => rest.first().map(|&len| (2, len as usize)),
+            OP_PUSHDATA2 => rest
+                .get(..2)
+                .map(|len| (3, u16::from_le_bytes([len[0], len[1]]) as usize)),
+            OP_PUSHDATA4 => rest
+                .get(..4)
+                .map(|len| (5, u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Iterator for Instructions<'a> {
+    type Item = Result<Instruction<'a>, &'static str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let opcode = *self.script.get(self.pos)?;
+        if opcode > OP_PUSHDATA4 {
+            self.pos += 1;
+            return Some(Ok(Instruction::Op(opcode)));
+        }
+
+        let end = self.push_length(opcode).and_then(|(header, len)| {
+            let start = self.pos + header;
+            start.checked_add(len).filter(|&end| end <= self.script.len()).map(|end| (start, end))
+        });
+        match end {
+            Some((start, end)) => {
+                self.pos = end;
+                Some(Ok(Instruction::Push(&self.script[start..end])))
+            }
+            None => {
+                self.pos = self.script.len();
+                Some(Err("Push past end of script"))
+            }
+        }
+    }
+}
+
+/// 遍历脚本指令
+pub fn instructions(script: &[u8]) -> Instructions<'_> {
+    Instructions { script, pos: 0 }
+}
+
+/// P2PKH：OP_DUP OP_HASH160 <20 字节公钥哈希> OP_EQUALVERIFY OP_CHECKSIG
+pub fn p2pkh(pubkey_hash: &[u8; 20]) -> Vec<u8> {
+    Builder::new()
+        .push_opcode(OP_DUP)
+        .push_opcode(OP_HASH160)
+        .push_slice(pubkey_hash)
+        .push_opcode(OP_EQUALVERIFY)
+        .push_opcode(OP_CHECKSIG)
+        .into_script()
+}
+
+/// P2SH：OP_HASH160 <20 字节脚本哈希> OP_EQUAL
+pub fn p2sh(script_hash: &[u8; 20]) -> Vec<u8> {
+    Builder::new()
+        .push_opcode(OP_HASH160)
+        .push_slice(script_hash)
+        .push_opcode(OP_EQUAL)
+        .into_script()
+}
+
+/// P2WPKH：OP_0 <20 字节公钥哈希>（也是 P2SH-P2WPKH 的赎回脚本）
+pub fn p2wpkh(pubkey_hash: &[u8; 20]) -> Vec<u8> {
+    Builder::new().push_opcode(OP_0).push_slice(pubkey_hash).into_script()
+}
+
+/// 嵌套隔离见证 P2SH-P2WPKH：赎回脚本 OP_0 <20> 的 P2SH
+pub fn p2sh_p2wpkh(pubkey_hash: &[u8; 20]) -> Vec<u8> {
+    p2sh(&hash160(&p2wpkh(pubkey_hash)))
+}
+
+/// P2WSH：OP_0 <32 字节见证脚本 SHA-256>
+pub fn p2wsh(script_hash: &[u8; 32]) -> Vec<u8> {
+    Builder::new().push_opcode(OP_0).push_slice(script_hash).into_script()
+}
+
+/// P2TR：OP_1 <32 字节输出密钥>
+pub fn p2tr(output_key: &[u8; 32]) -> Vec<u8> {
+    Builder::new().push_opcode(OP_1).push_slice(output_key).into_script()
+}
+
+/// 任意见证版本的输出脚本：OP_n <见证程序>
+pub fn witness_program(version: u8, program: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if version > 16 {
+        return Err("Invalid witness version");
+    }
+    if !(2..=40).contains(&program.len()) || (version == 0 && program.len() != 20 && program.len() != 32) {
+        return Err("Invalid witness program length");
+    }
+    let opcode = if version == 0 { OP_0 } else { OP_1 + version - 1 };
+    Ok(Builder::new().push_opcode(opcode).push_slice(program).into_script())
+}
+
+/// OP_RETURN 数据输出（不可花费），数据不超过 80 字节
+pub fn op_return(data: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if data.len() > MAX_OP_RETURN_DATA {
+        return Err("OP_RETURN data too long");
+    }
+    Ok(Builder::new().push_opcode(OP_RETURN).push_slice(data).into_script())
+}
+
+/// 输出脚本的种类
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptKind {
+    /// 裸公钥：<33 或 65 字节公钥> OP_CHECKSIG（没有地址）
+    P2pk(Vec<u8>),
+    P2pkh([u8; 20]),
+    P2sh([u8; 20]),
+    P2wpkh([u8; 20]),
+    P2wsh([u8; 32]),
+    P2tr([u8; 32]),
+    /// 尚未定义的见证版本或长度
+    WitnessUnknown { version: u8, program: Vec<u8> },
+    /// OP_RETURN 后只有数据推入，payload 为所有推入数据的串联
+    OpReturn(Vec<u8>),
+    /// 其他脚本（包括 OP_RETURN 后跟非推入操作码的脚本）
+    NonStandard,
+}
+
+impl ScriptKind {
+    /// 是否有对应的地址
+    pub fn has_address(&self) -> bool {
+        !matches!(self, Self::P2pk(_) | Self::OpReturn(_) | Self::NonStandard)
+    }
+
+    /// 简短名称，用于审核界面
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::P2pk(_) => "P2PK",
+            Self::P2pkh(_) => "P2PKH",
+            Self::P2sh(_) => "P2SH",
+            Self::P2wpkh(_) => "P2WPKH",
+            Self::P2wsh(_) => "P2WSH",
+            Self::P2tr(_) => "P2TR",
+            Self::WitnessUnknown { .. } => "Witness",
+            Self::OpReturn(_) => "OP_RETURN",
+            Self::NonStandard => "Non-standard",
+        }
+    }
+}
+
+/// 按字节模板识别输出脚本（与 Bitcoin Core 的 Solver 一致）
+pub fn classify(script: &[u8]) -> ScriptKind {
+    match script {
+        [OP_DUP, OP_HASH160, 0x14, hash @ .., OP_EQUALVERIFY, OP_CHECKSIG] if hash.len() == 20 => {
+            ScriptKind::P2pkh(to_array(hash))
+        }
+        [OP_HASH160, 0x14, hash @ .., OP_EQUAL] if hash.len() == 20 => ScriptKind::P2sh(to_array(hash)),
+        [len @ (0x21 | 0x41), key @ .., OP_CHECKSIG] if key.len() == *len as usize => {
+            ScriptKind::P2pk(key.to_vec())
+        }
+        [OP_RETURN, rest @ ..] => match push_only_data(rest) {
+            Some(payload) => ScriptKind::OpReturn(payload),
+            None => ScriptKind::NonStandard,
+        },
+        _ => match witness_program_of(script) {
+            Some((0, program)) if program.len() == 20 => ScriptKind::P2wpkh(to_array(program)),
+            Some((0, program)) if program.len() == 32 => ScriptKind::P2wsh(to_array(program)),
+            Some((1, program)) if program.len() == 32 => ScriptKind::P2tr(to_array(program)),
+            // 长度不对的 v0 程序无法花费，不算标准输出
+            Some((0, _)) | None => ScriptKind::NonStandard,
+            Some((version, program)) => ScriptKind::WitnessUnknown {
+                version,
+                program: program.to_vec(),
+            },
+        },
+    }
+}
+
+/// 脚本是否支付给该公钥的单签名输出（P2PKH、P2WPKH、P2SH-P2WPKH 或 BIP86 P2TR）
+pub fn pays_to_public_key(script: &[u8], public_key: &PublicKey) -> bool {
+    let pubkey_hash = hash160(&public_key.serialize_compressed());
+    match classify(script) {
+        ScriptKind::P2pkh(hash) | ScriptKind::P2wpkh(hash) => hash == pubkey_hash,
+        ScriptKind::P2sh(_) => script == p2sh_p2wpkh(&pubkey_hash).as_slice(),
+        ScriptKind::P2tr(output_key) => public_key
+            .x_only_public_key()
+            .0
+            .tap_tweak(None)
+            .is_ok_and(|(tweaked, _)| *tweaked.as_bytes() == output_key),
+        _ => false,
+    }
+}
+
+/// 钱包地址类型对应的输出脚本（与 address 模块生成的地址一致）
+pub fn for_address_type(address_type: AddressType, public_key: &PublicKey) -> Result<Vec<u8>, &'static str> {
+    let pubkey_hash = hash160(&public_key.serialize_compressed());
+    match address_type {
+        AddressType::P2pkh => Ok(p2pkh(&pubkey_hash)),
+        AddressType::P2shP2wpkh => Ok(p2sh_p2wpkh(&pubkey_hash)),
+        AddressType::P2wpkh => Ok(p2wpkh(&pubkey_hash)),
+        AddressType::P2tr => {
+            let (output_key, _) = public_key.x_only_public_key().0.tap_tweak(None)?;
+            Ok(p2tr(output_key.as_bytes()))
+        }
+    }
+}
+
+/// 由已解析的地址得到输出脚本
+pub fn from_parsed_address(address: &ParsedAddress) -> Result<Vec<u8>, &'static str> {
+    if matches!(address.cryptocurrency, Cryptocurrency::Ethereum | Cryptocurrency::Ripple) {
+        return Err("Address has no output script");
+    }
+    let payload = address.payload.as_slice();
+    let hash20 = || -> Result<[u8; 20], &'static str> {
+        payload.try_into().map_err(|_| "Invalid address payload length")
+    };
+    let hash32 = || -> Result<[u8; 32], &'static str> {
+        payload.try_into().map_err(|_| "Invalid address payload length")
+    };
+    match address.kind {
+        AddressKind::P2pkh => Ok(p2pkh(&hash20()?)),
+        AddressKind::P2sh => Ok(p2sh(&hash20()?)),
+        AddressKind::P2wpkh => Ok(p2wpkh(&hash20()?)),
+        AddressKind::P2wsh => Ok(p2wsh(&hash32()?)),
+        AddressKind::P2tr => Ok(p2tr(&hash32()?)),
+        AddressKind::WitnessUnknown(version) => witness_program(version, payload),
+        AddressKind::Ethereum => Err("Address has no output script"),
+    }
+}
+
+/// 解析地址字符串并得到输出脚本
+pub fn from_address(address: &str) -> Result<Vec<u8>, &'static str> {
+    from_parsed_address(&parse_address(address)?)
+}
+
+/// 输出脚本对应的地址字符串
+///
+/// 只适用于基于 UTXO 的币种；P2PK、OP_RETURN 和非标准脚本没有地址。
+pub fn to_address(
+    script: &[u8],
+    cryptocurrency: Cryptocurrency,
+    network: Network,
+) -> Result<Vec<u8>, &'static str> {
+    if matches!(cryptocurrency, Cryptocurrency::Ethereum | Cryptocurrency::Ripple) {
+        return Err("Cryptocurrency has no output scripts");
+    }
+    if !cryptocurrency.supports_network(network) {
+        return Err("Network not supported for this cryptocurrency");
+    }
+
+    let base58_address = |prefix: u8, hash: &[u8; 20]| {
+        let mut versioned = Vec::with_capacity(21);
+        versioned.push(prefix);
+        versioned.extend_from_slice(hash);
+        Ok(base58::check_encode(&versioned, &base58::BITCOIN))
+    };
+    let segwit_address = |version: u8, program: &[u8]| {
+        let hrp = cryptocurrency
+            .bech32_hrp_for(network)
+            .ok_or("Cryptocurrency has no segwit addresses")?;
+        bech32::encode_segwit_address(hrp, version, program)
+    };
+
+    match classify(script) {
+        ScriptKind::P2pkh(hash) => base58_address(cryptocurrency.address_prefix_for(network), &hash),
+        ScriptKind::P2sh(hash) => base58_address(cryptocurrency.script_hash_prefix_for(network), &hash),
+        ScriptKind::P2wpkh(hash) => segwit_address(0, &hash),
+        ScriptKind::P2wsh(hash) => segwit_address(0, &hash),
+        ScriptKind::P2tr(key) => segwit_address(1, &key),
+        ScriptKind::WitnessUnknown { version, program } => segwit_address(version, &program),
+        ScriptKind::P2pk(_) | ScriptKind::OpReturn(_) | ScriptKind::NonStandard => {
+            Err("Script has no address")
+        }
+    }
+}
+
+/// 见证程序：OP_n 加一个 2 到 40 字节的直接推入，且恰好占满脚本
+fn witness_program_of(script: &[u8]) -> Option<(u8, &[u8])> {
+    if script.len() < 4 || script.len() > 42 || script[1] as usize != script.len() - 2 {
+        return None;
+    }
+    let version = match script[0] {
+        OP_0 => 0,
+        OP_1..=OP_16 => script[0] - OP_1 + 1,
+        _ => return None,
+    };
+    Some((version, &script[2..]))
+}
+
+/// 只含数据推入（或 OP_1NEGATE、OP_1..OP_16）时返回推入数据的串联
+fn push_only_data(script: &[u8]) -> Option<Vec<u8>> {
+    let mut payload = Vec::new();
+    for instruction in instructions(script) {
+        match instruction.ok()? {
+            Instruction::Push(data) => payload.extend_from_slice(data),
+            Instruction::Op(OP_1NEGATE | OP_1..=OP_16) => {}
+            Instruction::Op(_) => return None,
+        }
+    }
+    Some(payload)
+}
+
+fn to_array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut array = [0u8; N];
+    array.copy_from_slice(bytes);
+    array
+}
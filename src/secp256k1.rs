@@ -1,293 +1,757 @@
 extern crate alloc;
 
-use crate::sha256::Sha256;
+use crate::sha256::{HmacSha256, Sha256};
 use alloc::vec::Vec;
 
-/// secp256k1 曲线参数 - 质数（p = 2^256 - 2^32 - 977）
-const P: [u8; 32] = [
-    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-    0xff, 0xff, 0xff, 0xfe, 0xff, 0xff, 0xfc, 0x2f,
+/// secp256k1 曲线参数 - 质数 p = 2^256 - 2^32 - 977（小端序 32 位 limb）
+const P: [u32; 8] = [
+    0xFFFFFC2F, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF,
+    0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
 ];
 
-/// secp256k1 曲线参数 - 秩（曲线上的点数）
-const N: [u8; 32] = [
-    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
-    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b,
-    0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+/// secp256k1 曲线参数 - 秩 n（曲线上的点数）
+const N: [u32; 8] = [
+    0xD0364141, 0xBFD25E8C, 0xAF48A03B, 0xBAAEDCE6,
+    0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
 ];
 
-/// secp256k1 生成点 G 的 x 坐标
-const GX: [u8; 32] = [
-    0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac,
-    0x55, 0xa0, 0x69, 0x24, 0xef, 0x7c, 0xae, 0x82,
-    0x24, 0x6c, 0x61, 0x90, 0xe5, 0x38, 0x45, 0xb0,
-    0xcf, 0x43, 0x19, 0xea, 0x1f, 0xa8, 0xf7, 0x24,
+/// 2^256 mod n = 2^256 - n，用于快速约化
+const N_COMPLEMENT: [u32; 5] = [0x2FC9BEBF, 0x402DA173, 0x50B75FC4, 0x45512319, 0x00000001];
+
+/// n - 2（费马小定理求逆的指数）
+const N_MINUS_2: [u32; 8] = [
+    0xD036413F, 0xBFD25E8C, 0xAF48A03B, 0xBAAEDCE6,
+    0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
 ];
 
-/// secp256k1 生成点 G 的 y 坐标
-const GY: [u8; 32] = [
-    0x48, 0x3a, 0xda, 0x77, 0x26, 0xa3, 0xc4, 0x65,
-    0x5d, 0xa4, 0xfb, 0xfc, 0x0e, 0x11, 0x08, 0xa8,
-    0xfd, 0x17, 0xb4, 0x48, 0xa6, 0x85, 0x54, 0x19,
-    0x9c, 0x47, 0xd0, 0x8f, 0xfb, 0x10, 0xd4, 0xb8,
+/// floor(n / 2)，用于判断低 S 值
+const N_HALF: [u32; 8] = [
+    0x681B20A0, 0xDFE92F46, 0x57A4501D, 0x5D576E73,
+    0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0x7FFFFFFF,
 ];
 
-/// 模逆元计算（使用扩展欧几里得算法）
-/// 计算 a^(-1) mod m
-fn mod_inverse(a: &[u8; 32], m: &[u8; 32]) -> Result<[u8; 32], &'static str> {
-    // 检查 a 是否为零
-    let mut is_zero = true;
-    for &byte in a {
-        if byte != 0 {
-            is_zero = false;
-            break;
+/// secp256k1 生成点 G 的 x 坐标
+const GX: FieldElement = FieldElement([
+    0x16F81798, 0x59F2815B, 0x2DCE28D9, 0x029BFCDB,
+    0xCE870B07, 0x55A06295, 0xF9DCBBAC, 0x79BE667E,
+]);
+
+/// secp256k1 生成点 G 的 y 坐标
+const GY: FieldElement = FieldElement([
+    0xFB10D4B8, 0x9C47D08F, 0xA6855419, 0xFD17B448,
+    0x0E1108A8, 0x5DA4FBFC, 0x26A3C465, 0x483ADA77,
+]);
+
+// ============================================================================
+// 256 位整数运算（小端序 32 位 limb，常量时间）
+// ============================================================================
+
+/// 大端序字节转换为 limb
+const fn limbs_from_be_bytes(bytes: &[u8; 32]) -> [u32; 8] {
+    let mut out = [0u32; 8];
+    let mut i = 0;
+    while i < 8 {
+        let o = 28 - i * 4;
+        out[i] = ((bytes[o] as u32) << 24)
+            | ((bytes[o + 1] as u32) << 16)
+            | ((bytes[o + 2] as u32) << 8)
+            | (bytes[o + 3] as u32);
+        i += 1;
+    }
+    out
+}
+
+/// limb 转换为大端序字节
+const fn limbs_to_be_bytes(limbs: &[u32; 8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 8 {
+        let o = 28 - i * 4;
+        out[o] = (limbs[i] >> 24) as u8;
+        out[o + 1] = (limbs[i] >> 16) as u8;
+        out[o + 2] = (limbs[i] >> 8) as u8;
+        out[o + 3] = limbs[i] as u8;
+        i += 1;
+    }
+    out
+}
+
+/// a + b，返回结果和进位
+const fn limbs_add(a: &[u32; 8], b: &[u32; 8]) -> ([u32; 8], u32) {
+    let mut out = [0u32; 8];
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < 8 {
+        let v = a[i] as u64 + b[i] as u64 + carry;
+        out[i] = v as u32;
+        carry = v >> 32;
+        i += 1;
+    }
+    (out, carry as u32)
+}
+
+/// a - b，返回结果和借位
+const fn limbs_sub(a: &[u32; 8], b: &[u32; 8]) -> ([u32; 8], u32) {
+    let mut out = [0u32; 8];
+    let mut borrow = 0u64;
+    let mut i = 0;
+    while i < 8 {
+        let v = (a[i] as u64).wrapping_sub(b[i] as u64).wrapping_sub(borrow);
+        out[i] = v as u32;
+        borrow = v >> 63;
+        i += 1;
+    }
+    (out, borrow as u32)
+}
+
+/// 常量时间选择：mask 为全 1 时返回 a，为 0 时返回 b
+const fn limbs_select(mask: u32, a: &[u32; 8], b: &[u32; 8]) -> [u32; 8] {
+    let mut out = [0u32; 8];
+    let mut i = 0;
+    while i < 8 {
+        out[i] = (a[i] & mask) | (b[i] & !mask);
+        i += 1;
+    }
+    out
+}
+
+/// 常量时间判断是否为零
+const fn limbs_is_zero(a: &[u32; 8]) -> bool {
+    let mut acc = 0u32;
+    let mut i = 0;
+    while i < 8 {
+        acc |= a[i];
+        i += 1;
+    }
+    acc == 0
+}
+
+/// 常量时间判断是否相等
+const fn limbs_eq(a: &[u32; 8], b: &[u32; 8]) -> bool {
+    let mut acc = 0u32;
+    let mut i = 0;
+    while i < 8 {
+        acc |= a[i] ^ b[i];
+        i += 1;
+    }
+    acc == 0
+}
+
+/// a < b
+const fn limbs_lt(a: &[u32; 8], b: &[u32; 8]) -> bool {
+    limbs_sub(a, b).1 == 1
+}
+
+/// (a + b) mod m，要求 a, b < m
+const fn mod_add(a: &[u32; 8], b: &[u32; 8], m: &[u32; 8]) -> [u32; 8] {
+    let (sum, carry) = limbs_add(a, b);
+    let (diff, borrow) = limbs_sub(&sum, m);
+    // 溢出或 sum >= m 时取差值
+    let use_diff = carry | (borrow ^ 1);
+    limbs_select(0u32.wrapping_sub(use_diff), &diff, &sum)
+}
+
+/// (a - b) mod m，要求 a, b < m
+const fn mod_sub(a: &[u32; 8], b: &[u32; 8], m: &[u32; 8]) -> [u32; 8] {
+    let (diff, borrow) = limbs_sub(a, b);
+    // 借位时加回 m
+    let fix = limbs_select(0u32.wrapping_sub(borrow), m, &[0u32; 8]);
+    limbs_add(&diff, &fix).0
+}
+
+/// 256 × 256 → 512 位乘法
+const fn mul_wide(a: &[u32; 8], b: &[u32; 8]) -> [u32; 16] {
+    let mut t = [0u32; 16];
+    let mut i = 0;
+    while i < 8 {
+        let mut carry = 0u64;
+        let mut j = 0;
+        while j < 8 {
+            let v = t[i + j] as u64 + (a[i] as u64) * (b[j] as u64) + carry;
+            t[i + j] = v as u32;
+            carry = v >> 32;
+            j += 1;
         }
+        t[i + 8] = carry as u32;
+        i += 1;
     }
-    
-    if is_zero {
-        return Err("Cannot invert zero");
+    t
+}
+
+/// 将 512 位整数约化到 [0, p)
+///
+/// 利用 2^256 ≡ 2^32 + 977 (mod p)：hi * 2^256 ≡ hi * 977 + (hi << 32)。
+/// 第一轮后结果不超过 2^300，第二、三轮处理剩余的高位，最后条件减去 p。
+const fn reduce_field(t: &[u32; 16]) -> [u32; 8] {
+    // 第一轮：r = lo + hi * 977 + (hi << 32)，最高位 top 不超过 2^44
+    let mut r = [0u32; 8];
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < 8 {
+        let mut v = t[i] as u64 + (t[8 + i] as u64) * 0x3D1 + carry;
+        if i > 0 {
+            v += t[7 + i] as u64;
+        }
+        r[i] = v as u32;
+        carry = v >> 32;
+        i += 1;
     }
-    
-    // 对于 secp256k1，使用费马小定理的近似：a^(-1) = a^(p-2) mod p
-    // 这里使用二进制幂运算（从高位到低位）
-    
-    // p - 2 的二进制表示
-    // p = 2^256 - 2^32 - 977，所以 p - 2 = 2^256 - 2^32 - 979
-    let p_minus_2 = [
-        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-        0xff, 0xff, 0xff, 0xfe, 0xff, 0xff, 0xfc, 0x2d,
-    ];
-    
-    let mut result = [0u8; 32];
-    result[31] = 1; // result = 1
-    
-    let base = *a;
-    
-    // 使用二进制幂运算计算 a^(p-2)
-    // 从最高位到最低位遍历 p-2
-    for &byte in &p_minus_2 {
-        for bit in (0..8).rev() {
-            // 平方
-            result = mod_mul(&result, &result, m);
-            
-            // 如果当前位是 1，则乘以底数
-            if (byte >> bit) & 1 == 1 {
-                result = mod_mul(&result, &base, m);
-            }
+    let mut top = t[15] as u64 + carry;
+
+    // 第二、三轮：把 top 折叠回低 256 位
+    let mut round = 0;
+    while round < 2 {
+        let mut v = r[0] as u64 + top * 0x3D1;
+        r[0] = v as u32;
+        v = r[1] as u64 + top + (v >> 32);
+        r[1] = v as u32;
+        let mut carry = v >> 32;
+        let mut k = 2;
+        while k < 8 {
+            let v = r[k] as u64 + carry;
+            r[k] = v as u32;
+            carry = v >> 32;
+            k += 1;
         }
+        top = carry;
+        round += 1;
     }
-    
-    // 验证结果（可选）
-    let verification = mod_mul(&result, a, m);
-    let one = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-               0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
-    
-    if verification != one {
-        return Err("Modular inverse calculation failed");
+
+    let (diff, borrow) = limbs_sub(&r, &P);
+    limbs_select(0u32.wrapping_sub(borrow ^ 1), &diff, &r)
+}
+
+/// 将 512 位整数约化到 [0, m)
+///
+/// c = 2^256 mod m。每轮把高 256 位折叠为 lo + hi * c，
+/// 对 n 来说三轮之后结果小于 2^256 + 2^133，再做一次条件减法即可。
+/// 循环次数固定，不依赖输入数据。
+const fn reduce_wide(t: &[u32; 16], c: &[u32], m: &[u32; 8]) -> [u32; 8] {
+    let mut r = *t;
+    let mut round = 0;
+    while round < 3 {
+        let mut next = [0u32; 16];
+        let mut i = 0;
+        while i < 8 {
+            next[i] = r[i];
+            i += 1;
+        }
+
+        i = 0;
+        while i < 8 {
+            let mut carry = 0u64;
+            let mut j = 0;
+            while j < c.len() {
+                let v = next[i + j] as u64 + (r[8 + i] as u64) * (c[j] as u64) + carry;
+                next[i + j] = v as u32;
+                carry = v >> 32;
+                j += 1;
+            }
+            let mut k = i + c.len();
+            while k < 16 {
+                let v = next[k] as u64 + carry;
+                next[k] = v as u32;
+                carry = v >> 32;
+                k += 1;
+            }
+            i += 1;
+        }
+
+        r = next;
+        round += 1;
     }
-    
-    Ok(result)
+
+    let lo = [r[0], r[1], r[2], r[3], r[4], r[5], r[6], r[7]];
+    let (diff, borrow) = limbs_sub(&lo, m);
+    let use_diff = r[8] | (borrow ^ 1);
+    limbs_select(0u32.wrapping_sub(use_diff), &diff, &lo)
 }
 
-/// 大整数模加
-fn mod_add(a: &[u8; 32], b: &[u8; 32], m: &[u8; 32]) -> [u8; 32] {
-    let mut result = [0u8; 32];
-    let mut carry = 0u16;
-    
-    for i in (0..32).rev() {
-        let sum = a[i] as u16 + b[i] as u16 + carry;
-        result[i] = (sum & 0xff) as u8;
-        carry = sum >> 8;
+// ============================================================================
+// 基域元素（模 p）
+// ============================================================================
+
+/// secp256k1 基域元素，始终保持完全约化（< p）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldElement([u32; 8]);
+
+impl FieldElement {
+    pub const ZERO: Self = FieldElement([0; 8]);
+    pub const ONE: Self = FieldElement([1, 0, 0, 0, 0, 0, 0, 0]);
+
+    /// 从大端序字节创建（拒绝 >= p 的值）
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, &'static str> {
+        let limbs = limbs_from_be_bytes(bytes);
+        if !limbs_lt(&limbs, &P) {
+            return Err("Field element is out of range (>= p)");
+        }
+        Ok(FieldElement(limbs))
     }
-    
-    // 如果溢出，减去 m
-    if carry > 0 {
-        let mut borrow = 0i16;
-        for i in (0..32).rev() {
-            let diff = result[i] as i16 - m[i] as i16 - borrow;
-            result[i] = diff as u8;
-            borrow = if diff < 0 { 1 } else { 0 };
+
+    /// 转换为大端序字节
+    pub const fn to_bytes(&self) -> [u8; 32] {
+        limbs_to_be_bytes(&self.0)
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        limbs_is_zero(&self.0)
+    }
+
+    pub const fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    pub const fn ct_eq(&self, other: &Self) -> bool {
+        limbs_eq(&self.0, &other.0)
+    }
+
+    pub const fn add(&self, other: &Self) -> Self {
+        FieldElement(mod_add(&self.0, &other.0, &P))
+    }
+
+    pub const fn sub(&self, other: &Self) -> Self {
+        FieldElement(mod_sub(&self.0, &other.0, &P))
+    }
+
+    pub const fn neg(&self) -> Self {
+        FieldElement(mod_sub(&[0; 8], &self.0, &P))
+    }
+
+    pub const fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    pub const fn mul(&self, other: &Self) -> Self {
+        FieldElement(reduce_field(&mul_wide(&self.0, &other.0)))
+    }
+
+    pub const fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// 连续平方 k 次
+    const fn pow2k(&self, k: usize) -> Self {
+        let mut r = *self;
+        let mut i = 0;
+        while i < k {
+            r = r.square();
+            i += 1;
         }
+        r
     }
-    
-    result
-}
 
-/// 大整数模减
-fn mod_sub(a: &[u8; 32], b: &[u8; 32], _m: &[u8; 32]) -> [u8; 32] {
-    let mut result = [0u8; 32];
-    let mut borrow = 0i16;
-    
-    for i in (0..32).rev() {
-        let diff = a[i] as i16 - b[i] as i16 - borrow;
-        if diff < 0 {
-            result[i] = (256 + diff) as u8;
-            borrow = 1;
+    /// 计算 x^(2^223 - 1) 及链上的中间值 (x2, x22, x223)
+    const fn pow_chain(&self) -> (Self, Self, Self) {
+        let x = *self;
+        let x2 = x.square().mul(&x);
+        let x3 = x2.square().mul(&x);
+        let x6 = x3.pow2k(3).mul(&x3);
+        let x9 = x6.pow2k(3).mul(&x3);
+        let x11 = x9.pow2k(2).mul(&x2);
+        let x22 = x11.pow2k(11).mul(&x11);
+        let x44 = x22.pow2k(22).mul(&x22);
+        let x88 = x44.pow2k(44).mul(&x44);
+        let x176 = x88.pow2k(88).mul(&x88);
+        let x220 = x176.pow2k(44).mul(&x44);
+        let x223 = x220.pow2k(3).mul(&x3);
+        (x2, x22, x223)
+    }
+
+    /// 模逆元：x^(p-2) mod p（费马小定理，固定加法链）
+    /// 零的逆元定义为零
+    pub const fn invert(&self) -> Self {
+        let (x2, x22, x223) = self.pow_chain();
+        x223.pow2k(23)
+            .mul(&x22)
+            .pow2k(5)
+            .mul(self)
+            .pow2k(3)
+            .mul(&x2)
+            .pow2k(2)
+            .mul(self)
+    }
+
+    /// 平方根：x^((p+1)/4) mod p（p ≡ 3 mod 4）
+    /// 若 x 不是二次剩余则返回 None
+    pub fn sqrt(&self) -> Option<Self> {
+        let (x2, x22, x223) = self.pow_chain();
+        let r = x223.pow2k(23).mul(&x22).pow2k(6).mul(&x2).pow2k(2);
+        if r.square().ct_eq(self) {
+            Some(r)
         } else {
-            result[i] = diff as u8;
-            borrow = 0;
+            None
         }
     }
-    
-    result
 }
 
-/// 大整数模乘（使用长乘法和模约化）
-fn mod_mul(a: &[u8; 32], b: &[u8; 32], m: &[u8; 32]) -> [u8; 32] {
-    let mut result = [0u8; 64];
-    
-    // 长乘法
-    for i in 0..32 {
-        let mut carry = 0u16;
-        for j in 0..32 {
-            let mul = (a[i] as u16) * (b[j] as u16) + (result[i + j] as u16) + carry;
-            result[i + j] = (mul & 0xff) as u8;
-            carry = mul >> 8;
-        }
-        if i < 31 {
-            result[i + 32] = (carry & 0xff) as u8;
+// ============================================================================
+// 标量（模 n）
+// ============================================================================
+
+/// secp256k1 标量，始终保持完全约化（< n）
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Scalar([u32; 8]);
+
+impl Scalar {
+    pub const ZERO: Self = Scalar([0; 8]);
+    pub const ONE: Self = Scalar([1, 0, 0, 0, 0, 0, 0, 0]);
+
+    /// 从大端序字节创建（拒绝 >= n 的值）
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, &'static str> {
+        let limbs = limbs_from_be_bytes(bytes);
+        if !limbs_lt(&limbs, &N) {
+            return Err("Scalar is out of range (>= N)");
         }
+        Ok(Scalar(limbs))
     }
-    
-    // 模约化（简化版本：对于 secp256k1 使用特殊约化）
-    let mut reduced = [0u8; 32];
-    reduced.copy_from_slice(&result[..32]);
-    
-    // 检查是否需要减去 m
-    let mut need_sub = false;
-    for i in 0..32 {
-        if result[i] > reduced[i] {
-            need_sub = true;
-            break;
-        } else if result[i] < reduced[i] {
-            break;
+
+    /// 从大端序字节创建并约化到 [0, n)（用于哈希值）
+    pub fn from_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        let limbs = limbs_from_be_bytes(bytes);
+        // 2^256 < 2n，一次条件减法即可
+        let (diff, borrow) = limbs_sub(&limbs, &N);
+        Scalar(limbs_select(0u32.wrapping_sub(borrow ^ 1), &diff, &limbs))
+    }
+
+    /// 转换为大端序字节
+    pub fn to_bytes(&self) -> [u8; 32] {
+        limbs_to_be_bytes(&self.0)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        limbs_is_zero(&self.0)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Scalar(mod_add(&self.0, &other.0, &N))
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Scalar(mod_sub(&self.0, &other.0, &N))
+    }
+
+    pub fn neg(&self) -> Self {
+        Scalar(mod_sub(&[0; 8], &self.0, &N))
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Scalar(reduce_wide(&mul_wide(&self.0, &other.0), &N_COMPLEMENT, &N))
+    }
+
+    /// 模逆元：s^(n-2) mod n（指数公开，运算序列与 s 无关）
+    /// 零的逆元定义为零
+    pub fn invert(&self) -> Self {
+        let mut result = Scalar::ONE;
+        for i in (0..256).rev() {
+            result = result.mul(&result);
+            if (N_MINUS_2[i / 32] >> (i % 32)) & 1 == 1 {
+                result = result.mul(self);
+            }
         }
+        result
     }
-    
-    if need_sub {
-        let _ = mod_sub(&reduced, m, m);
+
+    /// 是否大于 n / 2（高 S 值）
+    pub fn is_high(&self) -> bool {
+        limbs_lt(&N_HALF, &self.0)
     }
-    
-    reduced
+
+    /// 取第 i 个 4 位窗口（从最低位开始计数，共 64 个）
+    const fn nibble(&self, i: usize) -> u32 {
+        (self.0[i / 8] >> ((i % 8) * 4)) & 0xF
+    }
+}
+
+/// 曲线方程 y^2 = x^3 + 7 中的常数 b
+const B: FieldElement = FieldElement([7, 0, 0, 0, 0, 0, 0, 0]);
+
+/// 曲线方程 y^2 = x^3 + 7 中的 3b，用于完备加法公式
+const B3: FieldElement = FieldElement([21, 0, 0, 0, 0, 0, 0, 0]);
+
+/// 仿射坐标点（不表示无穷远点）
+#[derive(Clone, Copy, Debug)]
+struct AffinePoint {
+    x: FieldElement,
+    y: FieldElement,
 }
 
-/// 椭圆曲线点（仿射坐标）
+impl AffinePoint {
+    /// 生成点 G
+    const GENERATOR: Self = AffinePoint { x: GX, y: GY };
+
+    /// 由 x 坐标和 y 的奇偶性恢复曲线上的点；x 不在曲线上时返回 None
+    fn decompress(x: &FieldElement, y_is_odd: bool) -> Option<Self> {
+        // y^2 = x^3 + 7
+        let y = x.square().mul(x).add(&B).sqrt()?;
+        let y = if y.is_odd() == y_is_odd { y } else { y.neg() };
+        Some(AffinePoint { x: *x, y })
+    }
+
+    /// 常量时间选择：mask 为全 1 时返回 a，为 0 时返回 b
+    const fn select(mask: u32, a: &Self, b: &Self) -> Self {
+        AffinePoint {
+            x: FieldElement(limbs_select(mask, &a.x.0, &b.x.0)),
+            y: FieldElement(limbs_select(mask, &a.y.0, &b.y.0)),
+        }
+    }
+}
+
+/// 椭圆曲线点（齐次射影坐标 (X : Y : Z)，对应仿射点 (X/Z, Y/Z)）
+///
+/// 无穷远点表示为 (0 : Y : 0)。加法和倍点使用 Renes–Costello–Batina
+/// 的完备公式（a = 0），对所有输入（包括无穷远点和 P = Q）都成立，
+/// 因此没有依赖数据的分支。
 #[derive(Clone, Copy, Debug)]
 struct ECPoint {
-    x: [u8; 32],
-    y: [u8; 32],
-    is_infinity: bool,
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
 }
 
 impl ECPoint {
     /// 获得无穷远点
-    fn infinity() -> Self {
+    const fn infinity() -> Self {
         ECPoint {
-            x: [0; 32],
-            y: [0; 32],
-            is_infinity: true,
+            x: FieldElement::ZERO,
+            y: FieldElement::ONE,
+            z: FieldElement::ZERO,
         }
     }
-    
-    /// 点加法：P + Q
-    fn add(&self, other: &ECPoint) -> Result<ECPoint, &'static str> {
-        if self.is_infinity {
-            return Ok(*other);
-        }
-        if other.is_infinity {
-            return Ok(*self);
-        }
-        
-        // 检查是否是点倍增
-        let is_same_x = self.x == other.x;
-        let is_same_y = self.y == other.y;
-        
-        if is_same_x && is_same_y {
-            // 点倍增
-            return self.double();
-        }
-        
-        if is_same_x && !is_same_y {
-            // P + (-P) = O（无穷远点）
-            return Ok(ECPoint::infinity());
-        }
-        
-        // 一般情况：(x1 != x2)
-        // s = (y2 - y1) / (x2 - x1)
-        // x3 = s^2 - x1 - x2
-        // y3 = s * (x1 - x3) - y1
-        
-        let dx = mod_sub(&other.x, &self.x, &P);
-        let dy = mod_sub(&other.y, &self.y, &P);
-        
-        let s = mod_mul(&dy, &mod_inverse(&dx, &P)?, &P);
-        
-        let s2 = mod_mul(&s, &s, &P);
-        let x3 = mod_sub(&s2, &mod_add(&self.x, &other.x, &P), &P);
-        let y3 = mod_sub(&mod_mul(&s, &mod_sub(&self.x, &x3, &P), &P), &self.y, &P);
-        
-        Ok(ECPoint {
-            x: x3,
-            y: y3,
-            is_infinity: false,
-        })
+
+    const fn from_affine(p: &AffinePoint) -> Self {
+        ECPoint {
+            x: p.x,
+            y: p.y,
+            z: FieldElement::ONE,
+        }
     }
-    
-    /// 点倍增：2P
-    fn double(&self) -> Result<ECPoint, &'static str> {
-        if self.is_infinity {
-            return Ok(ECPoint::infinity());
-        }
-        
-        // s = (3 * x1^2) / (2 * y1)
-        // x3 = s^2 - 2 * x1
-        // y3 = s * (x1 - x3) - y1
-        
-        let three = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 
-                     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3];
-        
-        let x1_squared = mod_mul(&self.x, &self.x, &P);
-        let three_x1_squared = mod_mul(&three, &x1_squared, &P);
-        let two_y1 = mod_add(&self.y, &self.y, &P);
-        
-        let s = mod_mul(&three_x1_squared, &mod_inverse(&two_y1, &P)?, &P);
-        let s_squared = mod_mul(&s, &s, &P);
-        let two_x1 = mod_add(&self.x, &self.x, &P);
-        
-        let x3 = mod_sub(&s_squared, &two_x1, &P);
-        let y3 = mod_sub(&mod_mul(&s, &mod_sub(&self.x, &x3, &P), &P), &self.y, &P);
-        
-        Ok(ECPoint {
-            x: x3,
-            y: y3,
-            is_infinity: false,
-        })
+
+    fn is_infinity(&self) -> bool {
+        self.z.is_zero()
     }
-    
-    /// 标量乘法：k * P（使用二进制方法）
-    fn scalar_mul(&self, scalar: &[u8; 32]) -> Result<ECPoint, &'static str> {
-        let mut result = ECPoint::infinity();
-        let mut temp = *self;
-        
-        // 从最低位到最高位遍历标量
-        for &byte in scalar {
-            for bit in 0..8 {
-                if (byte >> bit) & 1 == 1 {
-                    result = result.add(&temp)?;
+
+    /// 常量时间选择：mask 为全 1 时返回 a，为 0 时返回 b
+    const fn select(mask: u32, a: &Self, b: &Self) -> Self {
+        ECPoint {
+            x: FieldElement(limbs_select(mask, &a.x.0, &b.x.0)),
+            y: FieldElement(limbs_select(mask, &a.y.0, &b.y.0)),
+            z: FieldElement(limbs_select(mask, &a.z.0, &b.z.0)),
+        }
+    }
+
+    /// 转换为仿射坐标（只需一次求逆）；无穷远点返回 None
+    fn to_affine(self) -> Option<AffinePoint> {
+        if self.is_infinity() {
+            return None;
+        }
+        Some(self.to_affine_unchecked())
+    }
+
+    /// 转换为仿射坐标，调用方保证不是无穷远点
+    const fn to_affine_unchecked(self) -> AffinePoint {
+        let z_inv = self.z.invert();
+        AffinePoint {
+            x: self.x.mul(&z_inv),
+            y: self.y.mul(&z_inv),
+        }
+    }
+
+    /// 点加法：P + Q（RCB 算法 7）
+    const fn add(&self, other: &ECPoint) -> ECPoint {
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&other.x, &other.y, &other.z);
+
+        let t0 = x1.mul(x2);
+        let t1 = y1.mul(y2);
+        let t2 = z1.mul(z2);
+        let t3 = x1.add(y1).mul(&x2.add(y2)).sub(&t0.add(&t1));
+        let t4 = y1.add(z1).mul(&y2.add(z2)).sub(&t1.add(&t2));
+        let y3 = x1.add(z1).mul(&x2.add(z2)).sub(&t0.add(&t2));
+        let t0 = t0.double().add(&t0);
+        let t2 = B3.mul(&t2);
+        let z3 = t1.add(&t2);
+        let t1 = t1.sub(&t2);
+        let y3 = B3.mul(&y3);
+        let x3 = t3.mul(&t1).sub(&t4.mul(&y3));
+        let y3 = t1.mul(&z3).add(&y3.mul(&t0));
+        let z3 = z3.mul(&t4).add(&t0.mul(&t3));
+
+        ECPoint { x: x3, y: y3, z: z3 }
+    }
+
+    /// 混合加法：P + Q，其中 Q 为仿射点（RCB 算法 8）
+    const fn add_affine(&self, other: &AffinePoint) -> ECPoint {
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2) = (&other.x, &other.y);
+
+        let t0 = x1.mul(x2);
+        let t1 = y1.mul(y2);
+        let t3 = x2.add(y2).mul(&x1.add(y1)).sub(&t0.add(&t1));
+        let t4 = y2.mul(z1).add(y1);
+        let y3 = x2.mul(z1).add(x1);
+        let t0 = t0.double().add(&t0);
+        let t2 = B3.mul(z1);
+        let z3 = t1.add(&t2);
+        let t1 = t1.sub(&t2);
+        let y3 = B3.mul(&y3);
+        let x3 = t3.mul(&t1).sub(&t4.mul(&y3));
+        let y3 = t1.mul(&z3).add(&y3.mul(&t0));
+        let z3 = z3.mul(&t4).add(&t0.mul(&t3));
+
+        ECPoint { x: x3, y: y3, z: z3 }
+    }
+
+    /// 点倍增：2P（RCB 算法 9）
+    const fn double(&self) -> ECPoint {
+        let (x, y, z) = (&self.x, &self.y, &self.z);
+
+        let t0 = y.square();
+        let z3 = t0.double().double().double();
+        let t1 = y.mul(z);
+        let t2 = B3.mul(&z.square());
+        let x3 = t2.mul(&z3);
+        let y3 = t0.add(&t2);
+        let z3 = t1.mul(&z3);
+        let t2 = t2.double().add(&t2);
+        let t0 = t0.sub(&t2);
+        let y3 = t0.mul(&y3).add(&x3);
+        let x3 = t0.mul(&x.mul(y)).double();
+
+        ECPoint { x: x3, y: y3, z: z3 }
+    }
+
+    /// 生成点标量乘法：k * G（梳状预计算表，常量时间）
+    ///
+    /// 把 k 的 64 个 4 位窗口 d[8i + m] 按 k = Σ_m 16^m Σ_i d[8i + m] 2^(32i) 分组，
+    /// 只需 28 次倍点和 64 次查表混合加法。起始点使用随机化的射影坐标
+    /// (0 : λ : 0)，使中间值与 k 不直接相关，以抵御功耗分析。
+    fn mul_base(scalar: &Scalar) -> ECPoint {
+        let mut result = ECPoint {
+            x: FieldElement::ZERO,
+            y: random_nonzero_field_element(),
+            z: FieldElement::ZERO,
+        };
+
+        for m in (0..8).rev() {
+            if m != 7 {
+                result = result.double().double().double().double();
+            }
+
+            for (i, row) in G_TABLE.iter().enumerate() {
+                let digit = scalar.nibble(8 * i + m);
+
+                // 扫描整行，digit 为 0 时也执行一次加法，再用掩码丢弃结果
+                let mut selected = row[0];
+                for (j, entry) in row.iter().enumerate() {
+                    selected = AffinePoint::select(ct_eq_mask(j as u32 + 1, digit), entry, &selected);
                 }
-                temp = temp.double()?;
+                let sum = result.add_affine(&selected);
+                result = ECPoint::select(!ct_eq_mask(digit, 0), &sum, &result);
             }
         }
-        
-        Ok(result)
+
+        result
+    }
+
+    /// 任意点标量乘法：k * P（4 位固定窗口，常量时间）
+    ///
+    /// 预计算 0P..15P，每个窗口固定做 4 次倍点、一次整表扫描和一次加法；
+    /// 完备公式保证窗口为 0（加无穷远点）时结果依然正确。
+    fn mul(&self, scalar: &Scalar) -> ECPoint {
+        let mut table = [ECPoint::infinity(); 16];
+        for i in 1..16 {
+            table[i] = table[i - 1].add(self);
+        }
+
+        let mut result = ECPoint::infinity();
+        for i in (0..64).rev() {
+            result = result.double().double().double().double();
+
+            let digit = scalar.nibble(i);
+            let mut selected = table[0];
+            for (j, entry) in table.iter().enumerate() {
+                selected = ECPoint::select(ct_eq_mask(j as u32, digit), entry, &selected);
+            }
+            result = result.add(&selected);
+        }
+
+        result
+    }
+}
+
+/// 常量时间相等比较：相等时返回全 1 掩码，否则返回 0
+const fn ct_eq_mask(a: u32, b: u32) -> u32 {
+    let x = a ^ b;
+    // x == 0 时 (x | -x) 的最高位为 0
+    ((x | x.wrapping_neg()) >> 31).wrapping_sub(1)
+}
+
+/// 从 TRNG 取一个非零域元素，用于射影坐标随机化
+fn random_nonzero_field_element() -> FieldElement {
+    let bytes = crate::trng::get_random_bytes(32);
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(&bytes[..32]);
+    match FieldElement::from_bytes(&buf) {
+        Ok(fe) if !fe.is_zero() => fe,
+        _ => FieldElement::ONE,
     }
 }
 
+/// 生成点预计算表：G_TABLE[i][j] = (j + 1) * 2^(32i) * G
+///
+/// 在编译期计算，8 行 × 15 个仿射点，约 7.5 KB。
+static G_TABLE: [[AffinePoint; 15]; 8] = build_base_table();
+
+const fn build_base_table() -> [[AffinePoint; 15]; 8] {
+    // 先在射影坐标下算出全部 120 个点
+    let mut points = [ECPoint::infinity(); 120];
+    let mut base = ECPoint::from_affine(&AffinePoint::GENERATOR);
+
+    let mut i = 0;
+    while i < 8 {
+        let mut acc = base;
+        let mut j = 0;
+        while j < 15 {
+            points[i * 15 + j] = acc;
+            acc = acc.add(&base);
+            j += 1;
+        }
+
+        // base = 2^32 * base
+        let mut k = 0;
+        while k < 32 {
+            base = base.double();
+            k += 1;
+        }
+        i += 1;
+    }
+
+    // 批量求逆（Montgomery 技巧）：只做一次域求逆
+    let mut prefix = [FieldElement::ONE; 120];
+    let mut acc = FieldElement::ONE;
+    let mut idx = 0;
+    while idx < 120 {
+        prefix[idx] = acc;
+        acc = acc.mul(&points[idx].z);
+        idx += 1;
+    }
+
+    let mut inv = acc.invert();
+    let mut table = [[AffinePoint::GENERATOR; 15]; 8];
+    idx = 120;
+    while idx > 0 {
+        idx -= 1;
+        let z_inv = inv.mul(&prefix[idx]);
+        inv = inv.mul(&points[idx].z);
+        table[idx / 15][idx % 15] = AffinePoint {
+            x: points[idx].x.mul(&z_inv),
+            y: points[idx].y.mul(&z_inv),
+        };
+    }
+
+    table
+}
+
 /// 私钥（32 字节）
 #[derive(Clone, Copy)]
 pub struct SecretKey([u8; 32]);
@@ -295,47 +759,438 @@ pub struct SecretKey([u8; 32]);
 impl SecretKey {
     /// 从字节创建私钥（带验证）
     pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, &'static str> {
+        let scalar = Scalar::from_bytes(bytes).map_err(|_| "Private key is out of range (>= N)")?;
+
         // 检查私钥是否为零
-        let mut is_zero = true;
-        for &byte in bytes {
-            if byte != 0 {
-                is_zero = false;
-                break;
-            }
-        }
-        
-        if is_zero {
+        if scalar.is_zero() {
             return Err("Private key cannot be zero");
         }
-        
-        // 检查私钥是否 >= N（秩）
-        let mut is_ge_n = false;
-        for i in 0..32 {
-            if bytes[i] > N[i] {
-                is_ge_n = true;
-                break;
-            } else if bytes[i] < N[i] {
-                break;
-            }
-        }
-        
-        if is_ge_n {
-            return Err("Private key is out of range (>= N)");
-        }
-        
+
         Ok(SecretKey(*bytes))
     }
-    
+
     /// 从种子派生私钥
     pub fn from_seed(seed: &[u8]) -> Self {
         let hash = Sha256::digest(seed);
         SecretKey(hash)
     }
-    
+
     /// 获取私钥字节
     pub fn as_bytes(&self) -> &[u8; 32] {
         &self.0
     }
+
+    /// 私钥对应的标量
+    pub fn to_scalar(&self) -> Scalar {
+        Scalar::from_bytes_reduced(&self.0)
+    }
+
+    /// 私钥加法调整：(self + tweak) mod n（BIP32 子私钥派生）
+    /// tweak 必须小于 n，且结果不能为零
+    pub fn add_tweak(&self, tweak: &[u8; 32]) -> Result<Self, &'static str> {
+        let tweak = Scalar::from_bytes(tweak).map_err(|_| "Tweak is out of range (>= N)")?;
+        let sum = self.to_scalar().add(&tweak);
+
+        if sum.is_zero() {
+            return Err("Tweaked key is invalid");
+        }
+
+        Ok(SecretKey(sum.to_bytes()))
+    }
+
+    /// BIP341 Taproot 私钥调整，得到与 `XOnlyPublicKey::tap_tweak` 输出密钥对应的私钥
+    ///
+    /// 内部公钥 y 为奇数时先取 d = n - d，再加上调整值 t。
+    pub fn tap_tweak(&self, merkle_root: Option<&[u8; 32]>) -> Result<Self, &'static str> {
+        let point = ECPoint::mul_base(&self.to_scalar())
+            .to_affine()
+            .ok_or("Generated point is at infinity")?;
+        let d = if point.y.is_odd() {
+            self.to_scalar().neg()
+        } else {
+            self.to_scalar()
+        };
+
+        let tweak = tap_tweak_hash(&point.x.to_bytes(), merkle_root);
+        let tweak = Scalar::from_bytes(&tweak).map_err(|_| "Tweak is out of range (>= N)")?;
+        let tweaked = d.add(&tweak);
+        if tweaked.is_zero() {
+            return Err("Tweaked key is invalid");
+        }
+
+        Ok(SecretKey(tweaked.to_bytes()))
+    }
+
+    /// ECDSA 签名（RFC 6979 确定性随机数，输出低 S 值）
+    pub fn sign_ecdsa(&self, msg_hash: &[u8; 32]) -> Signature {
+        self.sign_ecdsa_recoverable(msg_hash).signature
+    }
+
+    /// 可恢复的 ECDSA 签名，附带恢复标识（recovery id）
+    pub fn sign_ecdsa_recoverable(&self, msg_hash: &[u8; 32]) -> RecoverableSignature {
+        let d = self.to_scalar();
+        let z = Scalar::from_bytes_reduced(msg_hash);
+        let mut nonces = Rfc6979::new(&self.0, &z.to_bytes());
+
+        loop {
+            let k = nonces.next_nonce();
+
+            // R = k * G，r = R.x mod n
+            let point = match ECPoint::mul_base(&k).to_affine() {
+                Some(p) => p,
+                None => continue,
+            };
+            let x_bytes = point.x.to_bytes();
+            let r = Scalar::from_bytes_reduced(&x_bytes);
+            if r.is_zero() {
+                continue;
+            }
+
+            // s = k^-1 * (z + r * d) mod n
+            let s = k.invert().mul(&z.add(&r.mul(&d)));
+            if s.is_zero() {
+                continue;
+            }
+
+            // 恢复标识：bit 0 为 R.y 的奇偶性，bit 1 表示 R.x >= n
+            let mut recovery_id = point.y.is_odd() as u8;
+            if Scalar::from_bytes(&x_bytes).is_err() {
+                recovery_id |= 2;
+            }
+
+            // 规范化为低 S 值；s 取反相当于使用 -R，奇偶性随之翻转
+            let s = if s.is_high() {
+                recovery_id ^= 1;
+                s.neg()
+            } else {
+                s
+            };
+
+            return RecoverableSignature {
+                signature: Signature { r, s },
+                recovery_id,
+            };
+        }
+    }
+
+    /// BIP340 Schnorr 签名
+    ///
+    /// `aux_rand` 为 None 时从 TRNG 取 32 字节辅助随机数；
+    /// 传入固定值即为确定性模式（用于测试向量）。
+    pub fn sign_schnorr(
+        &self,
+        msg: &[u8],
+        aux_rand: Option<&[u8; 32]>,
+    ) -> Result<SchnorrSignature, &'static str> {
+        let aux = match aux_rand {
+            Some(aux) => *aux,
+            None => {
+                let bytes = crate::trng::get_random_bytes(32);
+                let mut aux = [0u8; 32];
+                aux.copy_from_slice(&bytes[..32]);
+                aux
+            }
+        };
+
+        // P = d' * G；P 的 y 为奇数时改用 d = n - d'
+        let d = self.to_scalar();
+        let point = ECPoint::mul_base(&d)
+            .to_affine()
+            .ok_or("Generated point is at infinity")?;
+        let d = if point.y.is_odd() { d.neg() } else { d };
+        let px = point.x.to_bytes();
+
+        // t = bytes(d) xor hash_BIP0340/aux(a)
+        let mut t = d.to_bytes();
+        let aux_hash = Sha256::tagged_digest(b"BIP0340/aux", &aux);
+        for (byte, mask) in t.iter_mut().zip(aux_hash.iter()) {
+            *byte ^= mask;
+        }
+
+        // k' = int(hash_BIP0340/nonce(t || bytes(P) || m)) mod n
+        let mut hasher = Sha256::tagged(b"BIP0340/nonce");
+        hasher.update(&t);
+        hasher.update(&px);
+        hasher.update(msg);
+        let k = Scalar::from_bytes_reduced(&hasher.finalize());
+        if k.is_zero() {
+            return Err("Schnorr nonce is zero");
+        }
+
+        // R = k' * G；R 的 y 为奇数时改用 k = n - k'
+        let big_r = ECPoint::mul_base(&k)
+            .to_affine()
+            .ok_or("Nonce point is at infinity")?;
+        let k = if big_r.y.is_odd() { k.neg() } else { k };
+        let rx = big_r.x.to_bytes();
+
+        // sig = bytes(R) || bytes((k + e * d) mod n)
+        let e = schnorr_challenge(&rx, &px, msg);
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&rx);
+        sig[32..].copy_from_slice(&k.add(&e.mul(&d)).to_bytes());
+        let sig = SchnorrSignature(sig);
+
+        // 发布前自检，防止故障注入导致密钥泄露
+        if !XOnlyPublicKey(px).verify_schnorr(msg, &sig) {
+            return Err("Schnorr signature self-check failed");
+        }
+
+        Ok(sig)
+    }
+}
+
+/// BIP340 挑战值 e = int(hash_BIP0340/challenge(R.x || P.x || m)) mod n
+fn schnorr_challenge(rx: &[u8; 32], px: &[u8; 32], msg: &[u8]) -> Scalar {
+    let mut hasher = Sha256::tagged(b"BIP0340/challenge");
+    hasher.update(rx);
+    hasher.update(px);
+    hasher.update(msg);
+    Scalar::from_bytes_reduced(&hasher.finalize())
+}
+
+/// RFC 6979 确定性随机数生成器（HMAC-SHA256，qlen = 256）
+struct Rfc6979 {
+    k: [u8; 32],
+    v: [u8; 32],
+    first: bool,
+}
+
+impl Rfc6979 {
+    /// 用私钥和约化后的消息哈希初始化（RFC 6979 3.2 节 b-g 步）
+    fn new(secret: &[u8; 32], msg: &[u8; 32]) -> Self {
+        let mut k = [0u8; 32];
+        let mut v = [1u8; 32];
+
+        for tag in [0x00u8, 0x01] {
+            let mut mac = HmacSha256::new(&k);
+            mac.update(&v);
+            mac.update(&[tag]);
+            mac.update(secret);
+            mac.update(msg);
+            k = mac.finalize();
+
+            let mut mac = HmacSha256::new(&k);
+            mac.update(&v);
+            v = mac.finalize();
+        }
+
+        Rfc6979 { k, v, first: true }
+    }
+
+    /// 生成下一个候选随机数 k ∈ [1, n-1]（3.2 节 h 步）
+    fn next_nonce(&mut self) -> Scalar {
+        loop {
+            if !self.first {
+                let mut mac = HmacSha256::new(&self.k);
+                mac.update(&self.v);
+                mac.update(&[0x00]);
+                self.k = mac.finalize();
+
+                let mut mac = HmacSha256::new(&self.k);
+                mac.update(&self.v);
+                self.v = mac.finalize();
+            }
+            self.first = false;
+
+            let mut mac = HmacSha256::new(&self.k);
+            mac.update(&self.v);
+            self.v = mac.finalize();
+
+            if let Ok(k) = Scalar::from_bytes(&self.v) {
+                if !k.is_zero() {
+                    return k;
+                }
+            }
+        }
+    }
+}
+
+/// ECDSA 签名 (r, s)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    r: Scalar,
+    s: Scalar,
+}
+
+impl Signature {
+    /// 从 64 字节紧凑格式 r || s 解析
+    pub fn from_compact(bytes: &[u8; 64]) -> Result<Self, &'static str> {
+        let mut r_bytes = [0u8; 32];
+        let mut s_bytes = [0u8; 32];
+        r_bytes.copy_from_slice(&bytes[..32]);
+        s_bytes.copy_from_slice(&bytes[32..]);
+
+        let r = Scalar::from_bytes(&r_bytes).map_err(|_| "Invalid signature r value")?;
+        let s = Scalar::from_bytes(&s_bytes).map_err(|_| "Invalid signature s value")?;
+        if r.is_zero() || s.is_zero() {
+            return Err("Invalid signature (zero r or s)");
+        }
+
+        Ok(Signature { r, s })
+    }
+
+    /// 从 DER 编码解析（严格 DER：最短长度编码、无多余前导零）
+    pub fn from_der(der: &[u8]) -> Result<Self, &'static str> {
+        if der.len() < 8 || der[0] != 0x30 || der[1] as usize != der.len() - 2 {
+            return Err("Invalid DER signature");
+        }
+
+        let (r, rest) = parse_der_integer(&der[2..])?;
+        let (s, rest) = parse_der_integer(rest)?;
+        if !rest.is_empty() {
+            return Err("Invalid DER signature (trailing data)");
+        }
+
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&r);
+        compact[32..].copy_from_slice(&s);
+        Self::from_compact(&compact)
+    }
+
+    /// 64 字节紧凑格式 r || s
+    pub fn serialize_compact(&self) -> [u8; 64] {
+        let mut out = [0u8; 64];
+        out[..32].copy_from_slice(&self.r.to_bytes());
+        out[32..].copy_from_slice(&self.s.to_bytes());
+        out
+    }
+
+    /// DER 编码：30 len 02 len(r) r 02 len(s) s（最长 72 字节）
+    pub fn serialize_der(&self) -> Vec<u8> {
+        let r = der_integer(&self.r.to_bytes());
+        let s = der_integer(&self.s.to_bytes());
+
+        let mut out = Vec::with_capacity(6 + r.len() + s.len());
+        out.push(0x30);
+        out.push((4 + r.len() + s.len()) as u8);
+        out.push(0x02);
+        out.push(r.len() as u8);
+        out.extend_from_slice(&r);
+        out.push(0x02);
+        out.push(s.len() as u8);
+        out.extend_from_slice(&s);
+        out
+    }
+
+    /// r 值（大端序）
+    pub fn r(&self) -> [u8; 32] {
+        self.r.to_bytes()
+    }
+
+    /// s 值（大端序）
+    pub fn s(&self) -> [u8; 32] {
+        self.s.to_bytes()
+    }
+
+    /// s 是否不大于 n / 2（BIP62 低 S 规则）
+    pub fn is_low_s(&self) -> bool {
+        !self.s.is_high()
+    }
+
+    /// 规范化为低 S 值
+    pub fn normalize_s(&mut self) {
+        if self.s.is_high() {
+            self.s = self.s.neg();
+        }
+    }
+}
+
+/// 可恢复的 ECDSA 签名（签名 + 恢复标识 0..=3）
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RecoverableSignature {
+    signature: Signature,
+    recovery_id: u8,
+}
+
+impl RecoverableSignature {
+    /// 从 64 字节紧凑格式和恢复标识创建
+    pub fn from_compact(bytes: &[u8; 64], recovery_id: u8) -> Result<Self, &'static str> {
+        if recovery_id > 3 {
+            return Err("Invalid recovery id");
+        }
+        Ok(RecoverableSignature {
+            signature: Signature::from_compact(bytes)?,
+            recovery_id,
+        })
+    }
+
+    /// 序列化为 (r || s, 恢复标识)
+    pub fn serialize_compact(&self) -> ([u8; 64], u8) {
+        (self.signature.serialize_compact(), self.recovery_id)
+    }
+
+    /// 从以太坊格式 r || s 和 v 创建
+    ///
+    /// v 可以是 0/1（类型化交易的 y_parity）、27/28（传统格式），
+    /// 或 EIP-155 的 chain_id * 2 + 35/36。
+    pub fn from_ethereum(bytes: &[u8; 64], v: u64) -> Result<Self, &'static str> {
+        let recovery_id = match v {
+            0 | 1 => v,
+            27 | 28 => v - 27,
+            35.. => (v - 35) % 2,
+            _ => return Err("Invalid Ethereum v value"),
+        };
+        Self::from_compact(bytes, recovery_id as u8)
+    }
+
+    /// 以太坊 v 值：无 chain_id 时为 27/28，否则为 EIP-155 的 chain_id * 2 + 35/36
+    ///
+    /// 以太坊只接受恢复标识 0/1；低 S 签名中 R.x >= n 的概率可以忽略。
+    /// chain_id 过大导致 v 超出 u64 时返回错误。
+    pub fn ethereum_v(&self, chain_id: Option<u64>) -> Result<u64, &'static str> {
+        let parity = (self.recovery_id & 1) as u64;
+        match chain_id {
+            Some(id) => id
+                .checked_mul(2)
+                .and_then(|v| v.checked_add(35 + parity))
+                .ok_or("Chain id too large for EIP-155 v"),
+            None => Ok(27 + parity),
+        }
+    }
+
+    /// 不可恢复的普通签名
+    pub fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    /// 恢复标识
+    pub fn recovery_id(&self) -> u8 {
+        self.recovery_id
+    }
+}
+
+/// 把 32 字节大端整数编码为 DER INTEGER 的内容（去掉前导零，必要时补 0x00 保持正数）
+fn der_integer(bytes: &[u8; 32]) -> Vec<u8> {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(31);
+    let mut out = Vec::with_capacity(33);
+    if bytes[start] & 0x80 != 0 {
+        out.push(0x00);
+    }
+    out.extend_from_slice(&bytes[start..]);
+    out
+}
+
+/// 解析一个 DER INTEGER，返回 32 字节大端值和剩余数据
+fn parse_der_integer(data: &[u8]) -> Result<([u8; 32], &[u8]), &'static str> {
+    if data.len() < 2 || data[0] != 0x02 {
+        return Err("Invalid DER integer");
+    }
+    let len = data[1] as usize;
+    if len == 0 || len > 33 || data.len() < 2 + len {
+        return Err("Invalid DER integer length");
+    }
+
+    let value = &data[2..2 + len];
+    // 负数，或者不必要的前导零
+    if value[0] & 0x80 != 0 || (len > 1 && value[0] == 0 && value[1] & 0x80 == 0) {
+        return Err("Invalid DER integer encoding");
+    }
+
+    let value = if len == 33 { &value[1..] } else { value };
+    let mut out = [0u8; 32];
+    out[32 - value.len()..].copy_from_slice(value);
+    Ok((out, &data[2 + len..]))
 }
 
 /// 公钥（椭圆曲线点）
@@ -349,24 +1204,127 @@ impl PublicKey {
     /// 从私钥导出公钥
     /// 公钥 = 私钥 * G（G 是生成点）
     pub fn from_secret_key(secret_key: &SecretKey) -> Result<Self, &'static str> {
-        let g = ECPoint {
-            x: GX,
-            y: GY,
-            is_infinity: false,
-        };
-        
-        let point = g.scalar_mul(secret_key.as_bytes())?;
-        
-        if point.is_infinity {
-            return Err("Generated point is at infinity");
-        }
-        
-        Ok(PublicKey {
-            x: point.x,
-            y: point.y,
-        })
+        let point = ECPoint::mul_base(&secret_key.to_scalar())
+            .to_affine()
+            .ok_or("Generated point is at infinity")?;
+
+        Ok(Self::from_affine(&point))
     }
-    
+
+    /// 从 SEC1 编码解析：33 字节压缩格式（0x02/0x03）或 65 字节未压缩格式（0x04）
+    ///
+    /// 压缩格式通过模 p 开平方恢复 y；拒绝不在曲线上的点和无穷远点（单字节 0x00）。
+    pub fn from_slice(data: &[u8]) -> Result<Self, &'static str> {
+        match (data.len(), data.first()) {
+            (33, Some(&prefix @ (0x02 | 0x03))) => {
+                let mut x_bytes = [0u8; 32];
+                x_bytes.copy_from_slice(&data[1..33]);
+                let x = FieldElement::from_bytes(&x_bytes)
+                    .map_err(|_| "Public key x coordinate is out of range")?;
+
+                let point = AffinePoint::decompress(&x, prefix == 0x03)
+                    .ok_or("Public key is not on the curve")?;
+                Ok(Self::from_affine(&point))
+            }
+            (65, Some(0x04)) => {
+                let mut x_bytes = [0u8; 32];
+                let mut y_bytes = [0u8; 32];
+                x_bytes.copy_from_slice(&data[1..33]);
+                y_bytes.copy_from_slice(&data[33..65]);
+                let x = FieldElement::from_bytes(&x_bytes)
+                    .map_err(|_| "Public key x coordinate is out of range")?;
+                let y = FieldElement::from_bytes(&y_bytes)
+                    .map_err(|_| "Public key y coordinate is out of range")?;
+
+                // y^2 = x^3 + 7
+                if y.square() != x.square().mul(&x).add(&B) {
+                    return Err("Public key is not on the curve");
+                }
+                Ok(PublicKey { x: x_bytes, y: y_bytes })
+            }
+            (1, Some(0x00)) => Err("Public key is the point at infinity"),
+            _ => Err("Invalid public key encoding"),
+        }
+    }
+
+    /// 从仿射点创建
+    fn from_affine(point: &AffinePoint) -> Self {
+        PublicKey {
+            x: point.x.to_bytes(),
+            y: point.y.to_bytes(),
+        }
+    }
+
+    /// 公钥调整：tweak * G + P（BIP32 CKDpub）
+    pub fn add_exp_tweak(&self, tweak: &[u8; 32]) -> Result<Self, &'static str> {
+        let t = Scalar::from_bytes(tweak).map_err(|_| "Tweak is out of range (>= N)")?;
+        let point = ECPoint::mul_base(&t)
+            .add_affine(&self.point())
+            .to_affine()
+            .ok_or("Tweaked key is invalid")?;
+        Ok(Self::from_affine(&point))
+    }
+
+    /// 对应的曲线点（坐标在构造时已保证 < p）
+    fn point(&self) -> AffinePoint {
+        AffinePoint {
+            x: FieldElement(limbs_from_be_bytes(&self.x)),
+            y: FieldElement(limbs_from_be_bytes(&self.y)),
+        }
+    }
+
+    /// 验证 ECDSA 签名
+    ///
+    /// 按标准 ECDSA 验证，高 S 值的签名同样接受；
+    /// 需要 BIP62 低 S 规则时由调用方检查 `Signature::is_low_s`。
+    pub fn verify_ecdsa(&self, msg_hash: &[u8; 32], sig: &Signature) -> bool {
+        let z = Scalar::from_bytes_reduced(msg_hash);
+        let s_inv = sig.s.invert();
+
+        // R = (z / s) * G + (r / s) * Q
+        let u1 = z.mul(&s_inv);
+        let u2 = sig.r.mul(&s_inv);
+        let point = ECPoint::mul_base(&u1).add(&ECPoint::from_affine(&self.point()).mul(&u2));
+
+        match point.to_affine() {
+            Some(p) => Scalar::from_bytes_reduced(&p.x.to_bytes()) == sig.r,
+            None => false,
+        }
+    }
+
+    /// 从可恢复签名中恢复签名者公钥（与以太坊 ecrecover 相同）
+    pub fn recover(msg_hash: &[u8; 32], sig: &RecoverableSignature) -> Result<Self, &'static str> {
+        let Signature { r, s } = sig.signature;
+
+        // R.x = r（恢复标识 bit 1 置位时为 r + n，此时必须仍 < p）
+        let mut x_limbs = r.0;
+        if sig.recovery_id & 2 != 0 {
+            let (sum, carry) = limbs_add(&x_limbs, &N);
+            if carry != 0 || !limbs_lt(&sum, &P) {
+                return Err("Invalid recovery id for this signature");
+            }
+            x_limbs = sum;
+        }
+
+        let big_r = AffinePoint::decompress(&FieldElement(x_limbs), sig.recovery_id & 1 != 0)
+            .ok_or("Signature point is not on the curve")?;
+
+        // Q = r^-1 * (s * R - z * G)
+        let z = Scalar::from_bytes_reduced(msg_hash);
+        let r_inv = r.invert();
+        let u1 = z.mul(&r_inv).neg();
+        let u2 = s.mul(&r_inv);
+        let point = ECPoint::mul_base(&u1).add(&ECPoint::from_affine(&big_r).mul(&u2));
+
+        let point = point.to_affine().ok_or("Recovered public key is at infinity")?;
+        Ok(Self::from_affine(&point))
+    }
+
+    /// x-only 公钥（BIP340），以及 y 是否为奇数
+    pub fn x_only_public_key(&self) -> (XOnlyPublicKey, bool) {
+        (XOnlyPublicKey(self.x), self.y[31] & 1 != 0)
+    }
+
     /// 获取 x 坐标
     pub fn x(&self) -> &[u8; 32] {
         &self.x
@@ -408,3 +1366,105 @@ impl PublicKey {
     }
 }
 
+/// BIP341 调整值 hash_TapTweak(P.x || merkle_root)
+fn tap_tweak_hash(internal_key: &[u8; 32], merkle_root: Option<&[u8; 32]>) -> [u8; 32] {
+    let mut hasher = Sha256::tagged(b"TapTweak");
+    hasher.update(internal_key);
+    if let Some(root) = merkle_root {
+        hasher.update(root);
+    }
+    hasher.finalize()
+}
+
+/// BIP340 x-only 公钥（32 字节，隐含 y 为偶数）
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct XOnlyPublicKey([u8; 32]);
+
+impl XOnlyPublicKey {
+    /// 从 32 字节 x 坐标创建（x 必须在曲线上）
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, &'static str> {
+        let x = FieldElement::from_bytes(bytes).map_err(|_| "Invalid x-only public key")?;
+        AffinePoint::decompress(&x, false).ok_or("X-only public key is not on the curve")?;
+        Ok(XOnlyPublicKey(*bytes))
+    }
+
+    /// 序列化为 32 字节
+    pub fn serialize(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// 获取 x 坐标
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// 对应的完整公钥（y 为偶数）
+    pub fn public_key(&self) -> Result<PublicKey, &'static str> {
+        let x = FieldElement::from_bytes(&self.0).map_err(|_| "Invalid x-only public key")?;
+        let point =
+            AffinePoint::decompress(&x, false).ok_or("X-only public key is not on the curve")?;
+        Ok(PublicKey::from_affine(&point))
+    }
+
+    /// BIP341 Taproot 调整：Q = P + int(hash_TapTweak(P || merkle_root)) * G
+    ///
+    /// 无脚本路径时 merkle_root 为 None（BIP86）。返回输出密钥及其 y 是否为奇数。
+    pub fn tap_tweak(&self, merkle_root: Option<&[u8; 32]>) -> Result<(Self, bool), &'static str> {
+        let tweak = tap_tweak_hash(&self.0, merkle_root);
+        let output = self.public_key()?.add_exp_tweak(&tweak)?;
+        Ok(output.x_only_public_key())
+    }
+
+    /// 验证 BIP340 Schnorr 签名
+    pub fn verify_schnorr(&self, msg: &[u8], sig: &SchnorrSignature) -> bool {
+        let point = match self.public_key() {
+            Ok(pk) => pk.point(),
+            Err(_) => return false,
+        };
+
+        let mut r_bytes = [0u8; 32];
+        let mut s_bytes = [0u8; 32];
+        r_bytes.copy_from_slice(&sig.0[..32]);
+        s_bytes.copy_from_slice(&sig.0[32..]);
+
+        // r < p，s < n
+        let r = match FieldElement::from_bytes(&r_bytes) {
+            Ok(r) => r,
+            Err(_) => return false,
+        };
+        let s = match Scalar::from_bytes(&s_bytes) {
+            Ok(s) => s,
+            Err(_) => return false,
+        };
+
+        // R = s * G - e * P，必须不是无穷远点、y 为偶数且 x = r
+        let e = schnorr_challenge(&r_bytes, &self.0, msg);
+        let big_r = ECPoint::mul_base(&s).add(&ECPoint::from_affine(&point).mul(&e.neg()));
+
+        match big_r.to_affine() {
+            Some(p) => !p.y.is_odd() && p.x == r,
+            None => false,
+        }
+    }
+}
+
+/// BIP340 Schnorr 签名（64 字节：R.x || s）
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SchnorrSignature([u8; 64]);
+
+impl SchnorrSignature {
+    /// 从 64 字节创建（取值范围在验证时检查）
+    pub fn from_bytes(bytes: &[u8; 64]) -> Self {
+        SchnorrSignature(*bytes)
+    }
+
+    /// 序列化为 64 字节
+    pub fn serialize(&self) -> [u8; 64] {
+        self.0
+    }
+
+    /// 获取签名字节
+    pub fn as_bytes(&self) -> &[u8; 64] {
+        &self.0
+    }
+}
@@ -1,3 +1,4 @@
+#[derive(Clone)]
 pub struct Sha256 {
     state: [u32; 8],
     length: u64,
@@ -80,6 +81,22 @@ impl Sha256 {
         hasher.finalize()
     }
 
+    /// 创建 BIP340 标签哈希上下文：SHA256(SHA256(tag) || SHA256(tag) || ...)
+    pub fn tagged(tag: &[u8]) -> Self {
+        let tag_hash = Self::digest(tag);
+        let mut hasher = Self::new();
+        hasher.update(&tag_hash);
+        hasher.update(&tag_hash);
+        hasher
+    }
+
+    /// 计算标签哈希 SHA256(SHA256(tag) || SHA256(tag) || data)
+    pub fn tagged_digest(tag: &[u8], data: &[u8]) -> [u8; 32] {
+        let mut hasher = Self::tagged(tag);
+        hasher.update(data);
+        hasher.finalize()
+    }
+
     fn process_block(&mut self) {
         const K: [u32; 64] = [
             0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5,
@@ -103,8 +120,8 @@ impl Sha256 {
         let mut w = [0u32; 64];
         
         // 将块转换为字数组（大端序）
-        for i in 0..16 {
-            w[i] = ((self.buffer[i * 4] as u32) << 24)
+        for (i, word) in w.iter_mut().take(16).enumerate() {
+            *word = ((self.buffer[i * 4] as u32) << 24)
                 | ((self.buffer[i * 4 + 1] as u32) << 16)
                 | ((self.buffer[i * 4 + 2] as u32) << 8)
                 | (self.buffer[i * 4 + 3] as u32);
@@ -170,3 +187,55 @@ impl Default for Sha256 {
     }
 }
 
+
+/// HMAC-SHA256 上下文
+///
+/// 内外层的填充块在创建时就已吸收，可以克隆后重复使用。
+#[derive(Clone)]
+pub struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    const BLOCK_SIZE: usize = 64;
+
+    /// 使用给定密钥创建 HMAC 上下文
+    pub fn new(key: &[u8]) -> Self {
+        let mut k = [0u8; Self::BLOCK_SIZE];
+
+        // 如果密钥太长，先哈希它
+        if key.len() > Self::BLOCK_SIZE {
+            k[..32].copy_from_slice(&Sha256::digest(key));
+        } else {
+            k[..key.len()].copy_from_slice(key);
+        }
+
+        let mut i_key_pad = [0x36u8; Self::BLOCK_SIZE];
+        let mut o_key_pad = [0x5cu8; Self::BLOCK_SIZE];
+        for i in 0..Self::BLOCK_SIZE {
+            i_key_pad[i] ^= k[i];
+            o_key_pad[i] ^= k[i];
+        }
+
+        let mut inner = Sha256::new();
+        inner.update(&i_key_pad);
+        let mut outer = Sha256::new();
+        outer.update(&o_key_pad);
+
+        Self { inner, outer }
+    }
+
+    /// 更新消息
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// 完成计算并返回 32 字节 MAC
+    pub fn finalize(self) -> [u8; 32] {
+        let inner_hash = self.inner.finalize();
+        let mut outer = self.outer;
+        outer.update(&inner_hash);
+        outer.finalize()
+    }
+}
@@ -0,0 +1,229 @@
+#[derive(Clone)]
+pub struct Sha512 {
+    state: [u64; 8],
+    length: u128,
+    buffer: [u8; 128],
+    buffer_len: usize,
+}
+
+impl Sha512 {
+    /// 创建新的 SHA-512 上下文
+    pub fn new() -> Self {
+        Self {
+            state: [
+                0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+                0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+            ],
+            length: 0,
+            buffer: [0; 128],
+            buffer_len: 0,
+        }
+    }
+
+    /// 更新哈希状态
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u128;
+
+        for &byte in data {
+            self.buffer[self.buffer_len] = byte;
+            self.buffer_len += 1;
+
+            if self.buffer_len == 128 {
+                self.process_block();
+                self.buffer_len = 0;
+            }
+        }
+    }
+
+    /// 完成哈希计算并返回结果
+    pub fn finalize(mut self) -> [u8; 64] {
+        // 添加填充
+        let bit_len = self.length * 8;
+        self.buffer[self.buffer_len] = 0x80;
+        self.buffer_len += 1;
+
+        if self.buffer_len > 112 {
+            while self.buffer_len < 128 {
+                self.buffer[self.buffer_len] = 0;
+                self.buffer_len += 1;
+            }
+            self.process_block();
+            self.buffer_len = 0;
+        }
+
+        while self.buffer_len < 112 {
+            self.buffer[self.buffer_len] = 0;
+            self.buffer_len += 1;
+        }
+
+        // 添加长度（大端序 128 位）
+        self.buffer[112..128].copy_from_slice(&bit_len.to_be_bytes());
+        self.process_block();
+
+        // 将状态转换为字节数组（大端序）
+        let mut result = [0u8; 64];
+        for (i, word) in self.state.iter().enumerate() {
+            result[i * 8..i * 8 + 8].copy_from_slice(&word.to_be_bytes());
+        }
+        result
+    }
+
+    /// 计算数据的 SHA-512 哈希
+    pub fn digest(data: &[u8]) -> [u8; 64] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    fn process_block(&mut self) {
+        const K: [u64; 80] = [
+            0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
+            0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+            0xd807aa98a3030242, 0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+            0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235, 0xc19bf174cf692694,
+            0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+            0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+            0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4,
+            0xc6e00bf33da88fc2, 0xd5a79147930aa725, 0x06ca6351e003826f, 0x142929670a0e6e70,
+            0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+            0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+            0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30,
+            0xd192e819d6ef5218, 0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+            0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8,
+            0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+            0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+            0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+            0xca273eceea26619c, 0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178,
+            0x06f067aa72176fba, 0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+            0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
+            0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+        ];
+
+        let mut w = [0u64; 80];
+
+        // 将块转换为字数组（大端序）
+        for (i, chunk) in self.buffer.chunks_exact(8).enumerate() {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(chunk);
+            w[i] = u64::from_be_bytes(bytes);
+        }
+
+        // 扩展消息
+        for i in 16..80 {
+            let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+            let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let mut a = self.state[0];
+        let mut b = self.state[1];
+        let mut c = self.state[2];
+        let mut d = self.state[3];
+        let mut e = self.state[4];
+        let mut f = self.state[5];
+        let mut g = self.state[6];
+        let mut h = self.state[7];
+
+        // 主循环
+        for (&k, &wi) in K.iter().zip(w.iter()) {
+            let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(k)
+                .wrapping_add(wi);
+            let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        // 更新状态
+        self.state[0] = self.state[0].wrapping_add(a);
+        self.state[1] = self.state[1].wrapping_add(b);
+        self.state[2] = self.state[2].wrapping_add(c);
+        self.state[3] = self.state[3].wrapping_add(d);
+        self.state[4] = self.state[4].wrapping_add(e);
+        self.state[5] = self.state[5].wrapping_add(f);
+        self.state[6] = self.state[6].wrapping_add(g);
+        self.state[7] = self.state[7].wrapping_add(h);
+    }
+}
+
+impl Default for Sha512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// HMAC-SHA512 上下文
+///
+/// 内外层的填充块在创建时就已吸收，克隆一个已设置密钥的上下文
+/// 可以避免在 PBKDF2 等循环中重复处理密钥。
+#[derive(Clone)]
+pub struct HmacSha512 {
+    inner: Sha512,
+    outer: Sha512,
+}
+
+impl HmacSha512 {
+    const BLOCK_SIZE: usize = 128;
+
+    /// 使用给定密钥创建 HMAC 上下文
+    pub fn new(key: &[u8]) -> Self {
+        let mut k = [0u8; Self::BLOCK_SIZE];
+
+        // 如果密钥太长，先哈希它
+        if key.len() > Self::BLOCK_SIZE {
+            k[..64].copy_from_slice(&Sha512::digest(key));
+        } else {
+            k[..key.len()].copy_from_slice(key);
+        }
+
+        let mut i_key_pad = [0x36u8; Self::BLOCK_SIZE];
+        let mut o_key_pad = [0x5cu8; Self::BLOCK_SIZE];
+        for i in 0..Self::BLOCK_SIZE {
+            i_key_pad[i] ^= k[i];
+            o_key_pad[i] ^= k[i];
+        }
+
+        let mut inner = Sha512::new();
+        inner.update(&i_key_pad);
+        let mut outer = Sha512::new();
+        outer.update(&o_key_pad);
+
+        Self { inner, outer }
+    }
+
+    /// 更新消息
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// 完成计算并返回 64 字节 MAC
+    pub fn finalize(self) -> [u8; 64] {
+        let inner_hash = self.inner.finalize();
+        let mut outer = self.outer;
+        outer.update(&inner_hash);
+        outer.finalize()
+    }
+}
+
+/// 计算 HMAC-SHA512(key, message)
+pub fn hmac_sha512(key: &[u8], message: &[u8]) -> [u8; 64] {
+    let mut mac = HmacSha512::new(key);
+    mac.update(message);
+    mac.finalize()
+}
@@ -0,0 +1,278 @@
+extern crate alloc;
+
+use crate::script::{instructions, Instruction, OP_CODESEPARATOR};
+use crate::sha256::Sha256;
+use crate::transaction::{double_sha256, write_compact_size, write_var_bytes, Transaction, TxOut};
+use alloc::vec::Vec;
+
+/// 签名哈希类型
+pub const SIGHASH_DEFAULT: u32 = 0x00;
+pub const SIGHASH_ALL: u32 = 0x01;
+pub const SIGHASH_NONE: u32 = 0x02;
+pub const SIGHASH_SINGLE: u32 = 0x03;
+pub const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+/// 传统 SIGHASH_SINGLE 没有对应输出时的"哈希"值：整数 1（小端序）
+const UINT256_ONE: [u8; 32] = {
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    one
+};
+
+/// BIP342 脚本路径花费的扩展字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TapLeafSpend {
+    /// 被执行叶子的 tapleaf 哈希
+    pub leaf_hash: [u8; 32],
+    /// 最后执行的 OP_CODESEPARATOR 位置（没有时为 0xFFFFFFFF）
+    pub codesep_pos: u32,
+}
+
+/// 基本类型（低 5 位，NONE / SINGLE 以外均按 ALL 处理）与是否 ANYONECANPAY
+fn split_type(sighash_type: u32) -> (u32, bool) {
+    (sighash_type & 0x1F, sighash_type & SIGHASH_ANYONECANPAY != 0)
+}
+
+/// 传统签名哈希（隔离见证之前的原始算法，与共识规则逐字节一致）
+///
+/// 接受任意哈希类型；script_code 中的 OP_CODESEPARATOR 会被删除。
+/// SIGHASH_SINGLE 在没有对应输出时按共识规则返回整数 1，
+/// 对这样的值签名等于授权花费该输入，签名方必须自行拒绝这种情况。
+pub fn legacy(
+    tx: &Transaction,
+    input_index: usize,
+    script_code: &[u8],
+    sighash_type: u32,
+) -> Result<[u8; 32], &'static str> {
+    if input_index >= tx.inputs.len() {
+        return Err("Input index out of range");
+    }
+    let (base_type, anyone_can_pay) = split_type(sighash_type);
+    if base_type == SIGHASH_SINGLE && input_index >= tx.outputs.len() {
+        return Ok(UINT256_ONE);
+    }
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&tx.version.to_le_bytes());
+
+    // 输入：只有被签名的输入带脚本；NONE / SINGLE 下其他输入的 sequence 置零
+    let signed_inputs = if anyone_can_pay {
+        input_index..input_index + 1
+    } else {
+        0..tx.inputs.len()
+    };
+    write_compact_size(&mut out, signed_inputs.len() as u64);
+    for i in signed_inputs {
+        let input = &tx.inputs[i];
+        input.previous_output.consensus_encode(&mut out);
+        if i == input_index {
+            write_var_bytes(&mut out, &remove_codeseparators(script_code));
+        } else {
+            write_compact_size(&mut out, 0);
+        }
+        let sequence = if i != input_index && (base_type == SIGHASH_NONE || base_type == SIGHASH_SINGLE) {
+            0
+        } else {
+            input.sequence
+        };
+        out.extend_from_slice(&sequence.to_le_bytes());
+    }
+
+    // 输出：NONE 不含输出；SINGLE 只保留对应输出，之前的输出置为空（金额 -1）
+    match base_type {
+        SIGHASH_NONE => write_compact_size(&mut out, 0),
+        SIGHASH_SINGLE => {
+            write_compact_size(&mut out, input_index as u64 + 1);
+            for _ in 0..input_index {
+                out.extend_from_slice(&u64::MAX.to_le_bytes());
+                write_compact_size(&mut out, 0);
+            }
+            tx.outputs[input_index].consensus_encode(&mut out);
+        }
+        _ => {
+            write_compact_size(&mut out, tx.outputs.len() as u64);
+            for output in &tx.outputs {
+                output.consensus_encode(&mut out);
+            }
+        }
+    }
+
+    out.extend_from_slice(&tx.lock_time.to_le_bytes());
+    out.extend_from_slice(&sighash_type.to_le_bytes());
+    Ok(double_sha256(&out))
+}
+
+/// BIP143 隔离见证 v0 签名哈希
+///
+/// script_code 对 P2WPKH 为 OP_DUP OP_HASH160 <20> OP_EQUALVERIFY OP_CHECKSIG，
+/// 对 P2WSH 为见证脚本（从最后执行的 OP_CODESEPARATOR 之后开始）。
+pub fn segwit_v0(
+    tx: &Transaction,
+    input_index: usize,
+    script_code: &[u8],
+    value: u64,
+    sighash_type: u32,
+) -> Result<[u8; 32], &'static str> {
+    let input = tx.inputs.get(input_index).ok_or("Input index out of range")?;
+    let (base_type, anyone_can_pay) = split_type(sighash_type);
+    let commits_all_outputs = base_type != SIGHASH_NONE && base_type != SIGHASH_SINGLE;
+
+    let mut hash_prevouts = [0u8; 32];
+    let mut hash_sequence = [0u8; 32];
+    let mut hash_outputs = [0u8; 32];
+
+    if !anyone_can_pay {
+        hash_prevouts = double_sha256(&prevouts(tx));
+        if commits_all_outputs {
+            hash_sequence = double_sha256(&sequences(tx));
+        }
+    }
+    if commits_all_outputs {
+        hash_outputs = double_sha256(&outputs(tx));
+    } else if base_type == SIGHASH_SINGLE && input_index < tx.outputs.len() {
+        let mut output = Vec::new();
+        tx.outputs[input_index].consensus_encode(&mut output);
+        hash_outputs = double_sha256(&output);
+    }
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&tx.version.to_le_bytes());
+    out.extend_from_slice(&hash_prevouts);
+    out.extend_from_slice(&hash_sequence);
+    input.previous_output.consensus_encode(&mut out);
+    write_var_bytes(&mut out, script_code);
+    out.extend_from_slice(&value.to_le_bytes());
+    out.extend_from_slice(&input.sequence.to_le_bytes());
+    out.extend_from_slice(&hash_outputs);
+    out.extend_from_slice(&tx.lock_time.to_le_bytes());
+    out.extend_from_slice(&sighash_type.to_le_bytes());
+    Ok(double_sha256(&out))
+}
+
+/// BIP341 Taproot 签名哈希
+///
+/// spent_outputs 为所有输入花费的输出（按输入顺序）。annex 为见证中以 0x50 开头的附加项；
+/// leaf 为 None 时是密钥路径花费，否则按 BIP342 计算脚本路径花费。
+pub fn taproot(
+    tx: &Transaction,
+    input_index: usize,
+    spent_outputs: &[TxOut],
+    sighash_type: u32,
+    annex: Option<&[u8]>,
+    leaf: Option<&TapLeafSpend>,
+) -> Result<[u8; 32], &'static str> {
+    if !matches!(sighash_type, 0x00..=0x03 | 0x81..=0x83) {
+        return Err("Invalid taproot sighash type");
+    }
+    let input = tx.inputs.get(input_index).ok_or("Input index out of range")?;
+    if spent_outputs.len() != tx.inputs.len() {
+        return Err("Spent outputs do not match inputs");
+    }
+    let base_type = sighash_type & 0x03;
+    let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+    if base_type == SIGHASH_SINGLE && input_index >= tx.outputs.len() {
+        return Err("SIGHASH_SINGLE without matching output");
+    }
+    if annex.is_some_and(|annex| annex.first() != Some(&0x50)) {
+        return Err("Annex must start with 0x50");
+    }
+
+    let mut hasher = Sha256::tagged(b"TapSighash");
+    hasher.update(&[0x00]); // epoch
+    hasher.update(&[sighash_type as u8]);
+    hasher.update(&tx.version.to_le_bytes());
+    hasher.update(&tx.lock_time.to_le_bytes());
+
+    if !anyone_can_pay {
+        let mut amounts = Vec::with_capacity(spent_outputs.len() * 8);
+        let mut scripts = Vec::new();
+        for output in spent_outputs {
+            amounts.extend_from_slice(&output.value.to_le_bytes());
+            write_var_bytes(&mut scripts, &output.script_pubkey);
+        }
+        hasher.update(&Sha256::digest(&prevouts(tx)));
+        hasher.update(&Sha256::digest(&amounts));
+        hasher.update(&Sha256::digest(&scripts));
+        hasher.update(&Sha256::digest(&sequences(tx)));
+    }
+    if base_type != SIGHASH_NONE && base_type != SIGHASH_SINGLE {
+        hasher.update(&Sha256::digest(&outputs(tx)));
+    }
+
+    // spend_type = ext_flag * 2 + annex_present
+    let ext_flag = if leaf.is_some() { 1 } else { 0 };
+    hasher.update(&[ext_flag * 2 + annex.is_some() as u8]);
+    if anyone_can_pay {
+        let spent = &spent_outputs[input_index];
+        let mut prevout = Vec::new();
+        input.previous_output.consensus_encode(&mut prevout);
+        spent.consensus_encode(&mut prevout);
+        hasher.update(&prevout);
+        hasher.update(&input.sequence.to_le_bytes());
+    } else {
+        hasher.update(&(input_index as u32).to_le_bytes());
+    }
+    if let Some(annex) = annex {
+        let mut serialized = Vec::new();
+        write_var_bytes(&mut serialized, annex);
+        hasher.update(&Sha256::digest(&serialized));
+    }
+
+    if base_type == SIGHASH_SINGLE {
+        let mut output = Vec::new();
+        tx.outputs[input_index].consensus_encode(&mut output);
+        hasher.update(&Sha256::digest(&output));
+    }
+
+    if let Some(leaf) = leaf {
+        hasher.update(&leaf.leaf_hash);
+        hasher.update(&[0x00]); // key_version
+        hasher.update(&leaf.codesep_pos.to_le_bytes());
+    }
+
+    Ok(hasher.finalize())
+}
+
+/// 所有输入的 outpoint 串联
+fn prevouts(tx: &Transaction) -> Vec<u8> {
+    let mut out = Vec::with_capacity(tx.inputs.len() * 36);
+    for input in &tx.inputs {
+        input.previous_output.consensus_encode(&mut out);
+    }
+    out
+}
+
+/// 所有输入的 sequence 串联
+fn sequences(tx: &Transaction) -> Vec<u8> {
+    let mut out = Vec::with_capacity(tx.inputs.len() * 4);
+    for input in &tx.inputs {
+        out.extend_from_slice(&input.sequence.to_le_bytes());
+    }
+    out
+}
+
+/// 所有输出的序列化串联
+fn outputs(tx: &Transaction) -> Vec<u8> {
+    let mut out = Vec::new();
+    for output in &tx.outputs {
+        output.consensus_encode(&mut out);
+    }
+    out
+}
+
+/// 删除脚本中的 OP_CODESEPARATOR（跳过数据推入，不误删数据中的 0xAB 字节）
+///
+/// 脚本在推入数据中途截断时，剩余字节原样保留。
+fn remove_codeseparators(script: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(script.len());
+    let mut kept_from = 0;
+    let mut iter = instructions(script);
+    while let Some(Ok(instruction)) = iter.next() {
+        if instruction == Instruction::Op(OP_CODESEPARATOR) {
+            out.extend_from_slice(&script[kept_from..iter.position() - 1]);
+            kept_from = iter.position();
+        }
+    }
+    out.extend_from_slice(&script[kept_from..]);
+    out
+}
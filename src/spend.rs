@@ -0,0 +1,410 @@
+extern crate alloc;
+
+use crate::address::{hash160, parse_address, AddressType, Cryptocurrency};
+use crate::bip32::{DerivationPath, MasterKey, HARDENED};
+use crate::coin_selection::{self, Candidate, Strategy, Target};
+use crate::flipper_wallet_core::{Wallet, WalletKind};
+use crate::qrcodegen::{QrCode, QrCodeEcc, QrSegment};
+use crate::script::{self, Builder, ScriptKind};
+use crate::secp256k1::{PublicKey, SecretKey};
+use crate::sighash::{self, SIGHASH_ALL, SIGHASH_DEFAULT};
+use crate::storage;
+use crate::transaction::{Transaction, TxIn, TxOut, Witness, SEQUENCE_RBF};
+use crate::utxo::{self, Utxo};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// 允许的最高费率（聪/vB），防止误输入导致手续费过高
+pub const MAX_FEE_RATE: u64 = 1_000;
+
+/// 文件没有给出派生路径时，在账户 0 的每条链上查找的地址数
+pub const ADDRESS_GAP_LIMIT: u32 = 20;
+
+/// 一笔付款的参数
+///
+/// UTXO 文件没有给出派生路径（path 或 desc）时，只在账户 0 的收款链与找零链的
+/// 前 ADDRESS_GAP_LIMIT 个地址中查找密钥；其他账户或更靠后的地址必须在文件中写明路径。
+#[derive(Debug, Clone, Copy)]
+pub struct SpendRequest<'a> {
+    /// 收款地址
+    pub destination: &'a str,
+    /// 付款金额（聪）
+    pub amount: u64,
+    /// 费率（聪/vB）
+    pub fee_rate: u64,
+    pub strategy: Strategy,
+    /// 找零地址索引（m/.../1/index）
+    ///
+    /// None 时使用 UTXO 中出现过的最大找零索引加一；UTXO 中没有找零链上的输出时无法
+    /// 判断哪些找零地址已被使用，助记词钱包必须显式给出。
+    pub change_index: Option<u32>,
+}
+
+/// 构建并签名完成的交易
+#[derive(Debug, Clone)]
+pub struct Spend {
+    pub tx: Transaction,
+    pub fee: u64,
+    /// 找零输出在交易中的位置
+    pub change_output: Option<usize>,
+    /// 找零密钥的派生路径（单私钥钱包找零回到同一地址，没有路径）
+    pub change_path: Option<DerivationPath>,
+}
+
+impl Spend {
+    /// 广播用的原始交易（小写十六进制）
+    pub fn raw_hex(&self) -> String {
+        to_hex(&self.tx.serialize(), b"0123456789abcdef")
+    }
+
+    /// 原始交易的二维码（大写十六进制使用字母数字模式，容量更大）
+    pub fn qr_code(&self) -> Result<QrCode, &'static str> {
+        let text = to_hex(&self.tx.serialize(), b"0123456789ABCDEF");
+        let segment = QrSegment::make_alphanumeric(&text);
+        QrCode::encode_segments(&[segment], QrCodeEcc::Low).map_err(|_| "Transaction too large for QR code")
+    }
+}
+
+/// 花费一个 UTXO 所用的密钥
+struct InputKey {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+    compressed: bool,
+    path: Option<DerivationPath>,
+}
+
+/// 读取 UTXO 文件，构建并签名交易，把原始交易十六进制写到同一目录下的 "-tx.txt" 文件
+///
+/// 返回交易文件路径与交易。
+pub fn spend_file(
+    wallet: &Wallet,
+    utxo_path: &str,
+    request: &SpendRequest,
+) -> Result<(String, Spend), &'static str> {
+    let utxos = utxo::load(utxo_path)?;
+    let spend = build(wallet, &utxos, request)?;
+    Ok((save(utxo_path, &spend)?, spend))
+}
+
+/// 把原始交易十六进制写到 UTXO 文件旁的 "-tx.txt" 文件，返回写入的路径
+pub fn save(utxo_path: &str, spend: &Spend) -> Result<String, &'static str> {
+    let out_path = tx_path(utxo_path);
+    storage::write_file(&alloc::format!("{}\0", out_path), spend.raw_hex().as_bytes())?;
+    Ok(out_path)
+}
+
+/// 交易文件路径：utxos.json → utxos-tx.txt
+pub fn tx_path(path: &str) -> String {
+    let stem = path
+        .strip_suffix(".json")
+        .or_else(|| path.strip_suffix(".csv"))
+        .unwrap_or(path);
+    alloc::format!("{}-tx.txt", stem)
+}
+
+/// 用钱包中的 UTXO 构建一笔付款并签名
+///
+/// 所有 UTXO 都必须属于本钱包（单签名 P2PKH、P2SH-P2WPKH、P2WPKH 或 BIP86 P2TR）。
+/// P2PKH 与 SegWit v0 的签名不覆盖其他输入的金额，这些 UTXO 必须带有前序交易
+/// （见 utxo::parse 的 prev_tx 字段），否则篡改过的文件可以让实际手续费远高于审核界面的数值。
+/// 找零使用第一个 UTXO 的地址类型与账户，派生在找零链（change=1）上。
+/// 交易版本为 2，所有输入启用 RBF，输出顺序为收款在前、找零在后。
+pub fn build(wallet: &Wallet, utxos: &[Utxo], request: &SpendRequest) -> Result<Spend, &'static str> {
+    if request.fee_rate == 0 {
+        return Err("Fee rate must be at least 1 sat/vB");
+    }
+    if request.fee_rate > MAX_FEE_RATE {
+        return Err("Fee rate too high");
+    }
+    let first = utxos.first().ok_or("No UTXOs to spend")?;
+    let unverified = utxos.iter().any(|utxo| {
+        !utxo.verified && !matches!(script::classify(&utxo.script_pubkey), ScriptKind::P2tr(_))
+    });
+    if unverified {
+        return Err("Non-Taproot UTXOs need prev_tx");
+    }
+
+    let destination = destination_script(wallet, request.destination)?;
+    if request.amount < coin_selection::dust_threshold(&destination) {
+        return Err("Amount below dust limit");
+    }
+
+    let keys = utxos
+        .iter()
+        .map(|utxo| input_key(wallet, utxo))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (change_script, change_path) = change_script(wallet, first, &keys, request.change_index)?;
+
+    // 有任何见证输入时交易带 marker/flag，按最坏情况估计
+    let segwit = utxos.iter().any(|utxo| coin_selection::is_segwit_input(&utxo.script_pubkey));
+    let mut candidates = Vec::with_capacity(utxos.len());
+    for (utxo, key) in utxos.iter().zip(&keys) {
+        candidates.push(Candidate {
+            value: utxo.value,
+            weight: coin_selection::input_weight(&utxo.script_pubkey, key.compressed, segwit)
+                .ok_or("Unsupported UTXO script")?,
+        });
+    }
+    let target = Target {
+        amount: request.amount,
+        fee_rate: request.fee_rate,
+        base_weight: coin_selection::base_weight(&[&destination], segwit),
+        change_weight: coin_selection::output_weight(&change_script),
+        change_spend_weight: coin_selection::input_weight(&change_script, true, true).unwrap_or(0),
+        change_dust: coin_selection::dust_threshold(&change_script),
+    };
+    let selection = coin_selection::select(&candidates, &target, request.strategy)?;
+
+    let mut tx = Transaction {
+        version: 2,
+        inputs: Vec::with_capacity(selection.indices.len()),
+        outputs: Vec::with_capacity(2),
+        lock_time: 0,
+    };
+    for &i in &selection.indices {
+        tx.inputs.push(TxIn::new(utxos[i].outpoint, SEQUENCE_RBF));
+    }
+    tx.outputs.push(TxOut {
+        value: request.amount,
+        script_pubkey: destination,
+    });
+    let change_output = selection.change.map(|value| {
+        tx.outputs.push(TxOut {
+            value,
+            script_pubkey: change_script,
+        });
+        tx.outputs.len() - 1
+    });
+
+    let spent: Vec<TxOut> = selection.indices.iter().map(|&i| utxos[i].txout()).collect();
+    let signing_keys: Vec<&InputKey> = selection.indices.iter().map(|&i| &keys[i]).collect();
+    sign_inputs(&mut tx, &spent, &signing_keys)?;
+
+    Ok(Spend {
+        tx,
+        fee: selection.fee,
+        change_output,
+        change_path: change_output.and(change_path),
+    })
+}
+
+/// 收款地址的输出脚本，必须是当前网络的比特币地址
+fn destination_script(wallet: &Wallet, destination: &str) -> Result<Vec<u8>, &'static str> {
+    let parsed = parse_address(destination)?;
+    if parsed.cryptocurrency != Cryptocurrency::Bitcoin {
+        return Err("Destination is not a Bitcoin address");
+    }
+    let script_pubkey = script::from_parsed_address(&parsed)?;
+
+    // 按钱包网络重新编码后应解析为同一地址（测试网络之间共用地址格式）
+    let encoded = script::to_address(&script_pubkey, Cryptocurrency::Bitcoin, wallet.network())?;
+    let same_network = core::str::from_utf8(&encoded)
+        .ok()
+        .and_then(|encoded| parse_address(encoded).ok())
+        .is_some_and(|reparsed| reparsed == parsed);
+    if !same_network {
+        return Err("Destination is for a different network");
+    }
+    Ok(script_pubkey)
+}
+
+/// 找到花费 UTXO 的密钥，并确认 UTXO 的脚本确实支付给它
+fn input_key(wallet: &Wallet, utxo: &Utxo) -> Result<InputKey, &'static str> {
+    match &wallet.kind {
+        WalletKind::SingleKey {
+            secret_key,
+            compressed,
+        } => {
+            let public_key = PublicKey::from_secret_key(secret_key)?;
+            let owned = if *compressed {
+                script::pays_to_public_key(&utxo.script_pubkey, &public_key)
+            } else {
+                utxo.script_pubkey == script::p2pkh(&hash160(&public_key.serialize_uncompressed()))
+            };
+            if !owned {
+                return Err("UTXO not owned by wallet");
+            }
+            Ok(InputKey {
+                secret_key: *secret_key,
+                public_key,
+                compressed: *compressed,
+                path: None,
+            })
+        }
+        WalletKind::Mnemonic(master_key) => match utxo.path {
+            Some(path) => {
+                let key = master_key.derive_path(&path)?;
+                let public_key = key.public_key()?;
+                if !script::pays_to_public_key(&utxo.script_pubkey, &public_key) {
+                    return Err("UTXO does not match its derivation path");
+                }
+                Ok(InputKey {
+                    secret_key: *key.secret_key(),
+                    public_key,
+                    compressed: true,
+                    path: Some(path),
+                })
+            }
+            None => find_key(master_key, wallet, &utxo.script_pubkey),
+        },
+        WalletKind::WatchOnly { .. } => Err("Wallet cannot sign"),
+    }
+}
+
+/// 在账户 0 的收款链与找零链上按地址查找密钥
+fn find_key(master_key: &MasterKey, wallet: &Wallet, script_pubkey: &[u8]) -> Result<InputKey, &'static str> {
+    let address_type = match script::classify(script_pubkey) {
+        ScriptKind::P2pkh(_) => AddressType::P2pkh,
+        ScriptKind::P2sh(_) => AddressType::P2shP2wpkh,
+        ScriptKind::P2wpkh(_) => AddressType::P2wpkh,
+        ScriptKind::P2tr(_) => AddressType::P2tr,
+        _ => return Err("Unsupported UTXO script"),
+    };
+    let coin_type = Cryptocurrency::Bitcoin.coin_type_for(wallet.network());
+    let account = master_key.derive_account(address_type.purpose(), coin_type, 0)?;
+
+    for change in 0..2 {
+        let chain = account.derive_child(change, false)?;
+        for index in 0..ADDRESS_GAP_LIMIT {
+            let key = chain.derive_child(index, false)?;
+            let public_key = key.public_key()?;
+            if script::pays_to_public_key(script_pubkey, &public_key) {
+                return Ok(InputKey {
+                    secret_key: *key.secret_key(),
+                    public_key,
+                    compressed: true,
+                    path: Some(DerivationPath::from_purpose(
+                        address_type.purpose(),
+                        coin_type,
+                        0,
+                        change,
+                        index,
+                    )),
+                });
+            }
+        }
+    }
+    Err("UTXO not owned by wallet")
+}
+
+/// 找零脚本与派生路径
+///
+/// 助记词钱包使用第一个 UTXO 的地址类型与账户，在找零链上取下一个未出现过的索引；
+/// 单私钥钱包只有一个密钥，找零回到第一个 UTXO 的脚本。
+/// 不会复用 UTXO 中已出现的找零地址。
+fn change_script(
+    wallet: &Wallet,
+    first: &Utxo,
+    keys: &[InputKey],
+    change_index: Option<u32>,
+) -> Result<(Vec<u8>, Option<DerivationPath>), &'static str> {
+    let master_key = match &wallet.kind {
+        WalletKind::Mnemonic(master_key) => master_key,
+        _ => return Ok((first.script_pubkey.clone(), None)),
+    };
+
+    let address_type = match script::classify(&first.script_pubkey) {
+        ScriptKind::P2pkh(_) => AddressType::P2pkh,
+        ScriptKind::P2sh(_) => AddressType::P2shP2wpkh,
+        ScriptKind::P2wpkh(_) => AddressType::P2wpkh,
+        ScriptKind::P2tr(_) => AddressType::P2tr,
+        _ => return Err("Unsupported UTXO script"),
+    };
+    let coin_type = Cryptocurrency::Bitcoin.coin_type_for(wallet.network());
+    let standard_account = [address_type.purpose() | HARDENED, coin_type | HARDENED];
+
+    // 第一个 UTXO 的路径为标准五级路径时沿用它的账户
+    let account = keys[0]
+        .path
+        .filter(|path| path.len() == 5 && path.indices()[..2] == standard_account)
+        .map_or(0, |path| path.indices()[2] & !HARDENED);
+    let used: Vec<u32> = keys
+        .iter()
+        .filter_map(|key| key.path)
+        .filter(|path| {
+            path.len() == 5
+                && path.indices()[..2] == standard_account
+                && path.indices()[2] == account | HARDENED
+                && path.indices()[3] == 1
+        })
+        .map(|path| path.indices()[4])
+        .collect();
+    let index = match change_index {
+        Some(index) if used.contains(&index) => return Err("Change address already used"),
+        Some(index) => index,
+        None => match used.iter().max() {
+            Some(&last) => last.checked_add(1).ok_or("Change index out of range")?,
+            None => return Err("Change index required"),
+        },
+    };
+    if index >= HARDENED {
+        return Err("Change index out of range");
+    }
+
+    let path = DerivationPath::from_purpose(address_type.purpose(), coin_type, account, 1, index);
+    let public_key = master_key.derive_path(&path)?.public_key()?;
+    Ok((script::for_address_type(address_type, &public_key)?, Some(path)))
+}
+
+/// 为每个输入填入 scriptSig 与见证
+fn sign_inputs(tx: &mut Transaction, spent: &[TxOut], keys: &[&InputKey]) -> Result<(), &'static str> {
+    for (index, key) in keys.iter().enumerate() {
+        let script_pubkey = &spent[index].script_pubkey;
+        let pubkey = if key.compressed {
+            key.public_key.serialize_compressed().to_vec()
+        } else {
+            key.public_key.serialize_uncompressed().to_vec()
+        };
+        let pubkey_hash = hash160(&pubkey);
+
+        let mut witness = Witness::new();
+        let script_sig = match script::classify(script_pubkey) {
+            ScriptKind::P2pkh(_) => {
+                let hash = sighash::legacy(tx, index, script_pubkey, SIGHASH_ALL)?;
+                let sig = sign_ecdsa(key, &hash)?;
+                Builder::new().push_slice(&sig).push_slice(&pubkey).into_script()
+            }
+            kind @ (ScriptKind::P2wpkh(_) | ScriptKind::P2sh(_)) => {
+                let script_code = script::p2pkh(&pubkey_hash);
+                let hash = sighash::segwit_v0(tx, index, &script_code, spent[index].value, SIGHASH_ALL)?;
+                witness.push(&sign_ecdsa(key, &hash)?);
+                witness.push(&pubkey);
+                match kind {
+                    ScriptKind::P2sh(_) => Builder::new().push_slice(&script::p2wpkh(&pubkey_hash)).into_script(),
+                    _ => Vec::new(),
+                }
+            }
+            ScriptKind::P2tr(_) => {
+                let hash = sighash::taproot(tx, index, spent, SIGHASH_DEFAULT, None, None)?;
+                let tweaked = key.secret_key.tap_tweak(None)?;
+                witness.push(&tweaked.sign_schnorr(&hash, None)?.serialize());
+                Vec::new()
+            }
+            _ => return Err("Unsupported UTXO script"),
+        };
+
+        tx.inputs[index].script_sig = script_sig;
+        tx.inputs[index].witness = witness;
+    }
+    Ok(())
+}
+
+/// SIGHASH_ALL 的 DER 签名，发布前用公钥自检
+fn sign_ecdsa(key: &InputKey, hash: &[u8; 32]) -> Result<Vec<u8>, &'static str> {
+    let signature = key.secret_key.sign_ecdsa(hash);
+    if !key.public_key.verify_ecdsa(hash, &signature) {
+        return Err("ECDSA signature self-check failed");
+    }
+    let mut sig = signature.serialize_der();
+    sig.push(SIGHASH_ALL as u8);
+    Ok(sig)
+}
+
+fn to_hex(bytes: &[u8], digits: &[u8; 16]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        out.push(digits[(byte >> 4) as usize] as char);
+        out.push(digits[(byte & 0x0F) as usize] as char);
+    }
+    out
+}
@@ -3,14 +3,24 @@ extern crate alloc;
 use alloc::vec::Vec;
 use alloc::string::String;
 use crate::aes::{aes256_cbc_encrypt, aes256_cbc_decrypt};
+use crate::network::Network;
 use crate::pbkdf2::pbkdf2_hmac_sha256;
 use core::convert::TryInto;
-// storage currently uses no platform symbols while debugging crashes.
+#[cfg(feature = "flipper")]
+use core::ffi::CStr;
+#[cfg(feature = "flipper")]
+use flipperzero::io::{Read, Seek, Write};
+#[cfg(feature = "flipper")]
+use flipperzero::storage::{OpenOptions, Storage};
+#[cfg(feature = "flipper")]
+use flipperzero_sys as sys;
 
 // Lower iteration count to avoid long blocking PBKDF2 on-device (can be increased for offline backups)
 const PBKDF2_ITERATIONS: u32 = 10_000;
 const SALT_LEN: usize = 16;
 const IV_LEN: usize = 16;
+// Largest file load_file will read into memory (PSBTs and UTXO lists stay well below this)
+pub const MAX_FILE_SIZE: usize = 64 * 1024;
 
 /// Wallet data structure for serialization
 #[derive(Clone, Debug)]
@@ -19,10 +29,12 @@ pub struct WalletData {
     pub mnemonic: String,
     pub passphrase: String,
     pub word_count: u16, // 12, 15, 18, 21, or 24
+    pub network: Network,
+    pub wif: String, // WIF private key for single-key wallets, empty for mnemonic wallets
 }
 
 impl WalletData {
-    /// Serialize wallet to bytes (simple format: name_len, name, mnemonic_len, mnemonic, passphrase_len, passphrase, word_count)
+    /// Serialize wallet to bytes (simple format: name_len, name, mnemonic_len, mnemonic, passphrase_len, passphrase, word_count, network, wif_len, wif)
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut out = Vec::new();
         
@@ -46,6 +58,14 @@ impl WalletData {
         // Serialize word_count
         out.push(((self.word_count >> 8) & 0xFF) as u8);
         out.push((self.word_count & 0xFF) as u8);
+
+        // Serialize network
+        out.push(self.network.to_u8());
+
+        // Serialize WIF private key
+        let wif_bytes = self.wif.as_bytes();
+        out.push((wif_bytes.len() & 0xFF) as u8);
+        out.extend_from_slice(wif_bytes);
         
         out
     }
@@ -84,8 +104,27 @@ impl WalletData {
         // Deserialize word_count
         if pos + 2 > data.len() { return Err("Truncated word_count"); }
         let word_count = ((data[pos] as u16) << 8) | (data[pos+1] as u16);
+        pos += 2;
+
+        // Deserialize network (files saved before networks existed are mainnet)
+        let network = match data.get(pos) {
+            Some(&id) => Network::from_u8(id).ok_or("Unknown network")?,
+            None => Network::Mainnet,
+        };
+        pos += 1;
 
-        Ok(WalletData { name, mnemonic, passphrase, word_count })
+        // Deserialize WIF private key (absent in mnemonic-only files)
+        let wif = match data.get(pos) {
+            Some(&wif_len) => {
+                pos += 1;
+                if pos + wif_len as usize > data.len() { return Err("Truncated WIF"); }
+                String::from_utf8(data[pos..pos + wif_len as usize].to_vec())
+                    .map_err(|_| "Invalid UTF-8 in WIF")?
+            }
+            None => String::new(),
+        };
+
+        Ok(WalletData { name, mnemonic, passphrase, word_count, network, wif })
     }
 }
 
@@ -130,11 +169,10 @@ pub fn generate_salt_and_iv() -> ([u8;16], [u8;16]) {
 }
 
 /// Persist raw bytes to given path.
-/// NOTE: low-level libc-style file I/O is not available on all firmware builds.
-/// This implementation is a stub that returns an error. Replace this with a
-/// call into the platform's official file/SD API (e.g. using flipperzero-sys)
-/// to write to `/ext/apps_data/flipperwallet/...`. Leaving this as a stub
-/// avoids introducing missing-imports at load time.
+///
+/// NOTE: wallet saves deliberately stay in memory: the encrypted wallet must
+/// not land on the SD card while its passphrase is this short, and nothing
+/// loads wallets back yet. PSBT and transaction exports use `write_file`.
 pub fn persist_file(_path: &str, _data: &[u8]) -> Result<(), &'static str> {
     // Temporary no-op persist implementation to avoid MPU/stack faults while we
     // continue debugging device crashes. This makes saves succeed in-memory,
@@ -143,14 +181,79 @@ pub fn persist_file(_path: &str, _data: &[u8]) -> Result<(), &'static str> {
     Ok(())
 }
 
+/// Write an export (signed PSBT, raw transaction) to given path.
+///
+/// The path is NUL-terminated, e.g. "/ext/apps_data/flipperwallet/x\0".
+/// The parent directory is created if needed and an existing file is replaced.
+/// Never use this for wallet files.
+#[cfg(feature = "flipper")]
+pub fn write_file(path: &str, data: &[u8]) -> Result<(), &'static str> {
+    let c_path = CStr::from_bytes_with_nul(path.as_bytes()).map_err(|_| "Invalid file path")?;
+
+    // Create the app data directory on first use; mkdir succeeds if it already exists
+    if let Some(end) = path.rfind('/').filter(|&end| end > 0) {
+        let dir = alloc::format!("{}\0", &path[..end]);
+        let storage = Storage::open();
+        if !unsafe { sys::storage_simply_mkdir(storage.as_ptr(), dir.as_ptr().cast()) } {
+            return Err("Failed to create directory");
+        }
+    }
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_always(true)
+        .open(c_path)
+        .map_err(|_| "Failed to open file for writing")?;
+    file.write_all(data).map_err(|_| "Failed to write file")
+}
+
+/// Read raw bytes from given path (NUL-terminated). Files larger than
+/// MAX_FILE_SIZE are rejected instead of exhausting the heap.
+#[cfg(feature = "flipper")]
+pub fn load_file(path: &str) -> Result<Vec<u8>, &'static str> {
+    let c_path = CStr::from_bytes_with_nul(path.as_bytes()).map_err(|_| "Invalid file path")?;
+    let mut file = OpenOptions::new()
+        .read(true)
+        .open_existing(true)
+        .open(c_path)
+        .map_err(|_| "File not found")?;
+
+    let len = file.stream_len().map_err(|_| "Failed to read file")?;
+    if len > MAX_FILE_SIZE {
+        return Err("File too large");
+    }
+    let mut data = alloc::vec![0u8; len];
+    let mut pos = 0;
+    while pos < len {
+        match file.read(&mut data[pos..]) {
+            Ok(0) => return Err("Unexpected end of file"),
+            Ok(n) => pos += n,
+            Err(_) => return Err("Failed to read file"),
+        }
+    }
+    Ok(data)
+}
+
+/// Without the Flipper SDK (host builds) there is no SD card to write to.
+#[cfg(not(feature = "flipper"))]
+pub fn write_file(_path: &str, _data: &[u8]) -> Result<(), &'static str> {
+    Err("File storage not available")
+}
+
+/// Without the Flipper SDK (host builds) there is no SD card to read from.
+#[cfg(not(feature = "flipper"))]
+pub fn load_file(_path: &str) -> Result<Vec<u8>, &'static str> {
+    Err("File storage not available")
+}
+
 /// Generate a printable random passphrase of given length using TRNG.
 pub fn generate_random_passphrase(len: usize) -> String {
     // base62 charset
     const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
     let mut out = String::new();
     let bytes = crate::trng::get_random_bytes(len);
-    for i in 0..len {
-        let idx = (bytes[i] as usize) % CHARS.len();
+    for byte in bytes {
+        let idx = (byte as usize) % CHARS.len();
         out.push(CHARS[idx] as char);
     }
     out
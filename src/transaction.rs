@@ -0,0 +1,442 @@
+extern crate alloc;
+
+use crate::fixed_string::FixedString;
+use crate::sha256::Sha256;
+use alloc::vec::Vec;
+
+/// 最终 sequence（不启用 RBF 与相对锁定时间）
+pub const SEQUENCE_FINAL: u32 = 0xFFFF_FFFF;
+
+/// 启用 BIP125 替换（RBF）的 sequence
+pub const SEQUENCE_RBF: u32 = 0xFFFF_FFFD;
+
+/// 隔离见证权重系数（非见证字节按 4 计算）
+pub const WITNESS_SCALE_FACTOR: usize = 4;
+
+/// 写入 CompactSize 变长整数
+pub fn write_compact_size(out: &mut Vec<u8>, value: u64) {
+    match value {
+        0..=0xFC => out.push(value as u8),
+        0xFD..=0xFFFF => {
+            out.push(0xFD);
+            out.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+        0x1_0000..=0xFFFF_FFFF => {
+            out.push(0xFE);
+            out.extend_from_slice(&(value as u32).to_le_bytes());
+        }
+        _ => {
+            out.push(0xFF);
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+/// 写入带 CompactSize 长度前缀的字节串（脚本、见证项等）
+pub fn write_var_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_compact_size(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+/// 双重 SHA-256
+pub fn double_sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(&Sha256::digest(data))
+}
+
+/// 交易 ID 的显示形式（字节反转后的十六进制，与区块浏览器一致）
+pub fn txid_to_hex(txid: &[u8; 32]) -> FixedString<64> {
+    const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = FixedString::new();
+    for &byte in txid.iter().rev() {
+        let _ = out.push(HEX_CHARS[(byte >> 4) as usize] as char);
+        let _ = out.push(HEX_CHARS[(byte & 0x0F) as usize] as char);
+    }
+    out
+}
+
+/// 解析显示形式的交易 ID（64 位十六进制，字节反转为内部顺序）
+pub fn txid_from_hex(s: &str) -> Result<[u8; 32], &'static str> {
+    if s.len() != 64 {
+        return Err("Invalid txid length");
+    }
+    let bytes = crate::hex::decode(s).map_err(|_| "Invalid txid")?;
+    let mut txid = [0u8; 32];
+    for (dst, src) in txid.iter_mut().zip(bytes.iter().rev()) {
+        *dst = *src;
+    }
+    Ok(txid)
+}
+
+/// 共识格式的顺序解码器
+pub struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Decoder { data, pos: 0 }
+    }
+
+    /// 剩余字节数
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// 确认数据已全部读完
+    pub fn finish(&self) -> Result<(), &'static str> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err("Trailing data")
+        }
+    }
+
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+        if len > self.remaining() {
+            return Err("Unexpected end of data");
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], &'static str> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.bytes(N)?);
+        Ok(out)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, &'static str> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    pub fn u32(&mut self) -> Result<u32, &'static str> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, &'static str> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    /// 读取 CompactSize（拒绝非最短编码）
+    pub fn compact_size(&mut self) -> Result<u64, &'static str> {
+        let (value, min) = match self.u8()? {
+            0xFD => (u16::from_le_bytes(self.array()?) as u64, 0xFD),
+            0xFE => (u32::from_le_bytes(self.array()?) as u64, 0x1_0000),
+            0xFF => (self.u64()?, 0x1_0000_0000),
+            byte => return Ok(byte as u64),
+        };
+        if value < min {
+            return Err("Non-canonical compact size");
+        }
+        Ok(value)
+    }
+
+    /// 读取长度或元素个数
+    ///
+    /// 每个元素至少占 1 字节，超过剩余数据量的值必然无效，
+    /// 提前拒绝可避免按伪造的计数分配内存。
+    pub fn length(&mut self) -> Result<usize, &'static str> {
+        let value = self.compact_size()?;
+        if value > self.remaining() as u64 {
+            return Err("Length exceeds remaining data");
+        }
+        Ok(value as usize)
+    }
+
+    /// 读取带 CompactSize 长度前缀的字节串
+    pub fn var_bytes(&mut self) -> Result<&'a [u8], &'static str> {
+        let len = self.length()?;
+        self.bytes(len)
+    }
+}
+
+/// 被花费的输出位置：前序交易 ID（内部字节序）与输出索引
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutPoint {
+    pub txid: [u8; 32],
+    pub vout: u32,
+}
+
+impl OutPoint {
+    /// 币基交易输入使用的空 outpoint
+    pub const NULL: OutPoint = OutPoint {
+        txid: [0u8; 32],
+        vout: 0xFFFF_FFFF,
+    };
+
+    pub fn new(txid: [u8; 32], vout: u32) -> Self {
+        OutPoint { txid, vout }
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == Self::NULL
+    }
+
+    pub fn consensus_encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.txid);
+        out.extend_from_slice(&self.vout.to_le_bytes());
+    }
+
+    pub fn consensus_decode(decoder: &mut Decoder) -> Result<Self, &'static str> {
+        Ok(OutPoint {
+            txid: decoder.array()?,
+            vout: decoder.u32()?,
+        })
+    }
+}
+
+/// 输入的见证数据（字节串栈）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Witness {
+    items: Vec<Vec<u8>>,
+}
+
+impl Witness {
+    pub fn new() -> Self {
+        Witness { items: Vec::new() }
+    }
+
+    /// 压入一项
+    pub fn push(&mut self, item: &[u8]) {
+        self.items.push(item.to_vec());
+    }
+
+    /// 项数
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        self.items.get(index).map(|item| item.as_slice())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[u8]> {
+        self.items.iter().map(|item| item.as_slice())
+    }
+
+    pub fn consensus_encode(&self, out: &mut Vec<u8>) {
+        write_compact_size(out, self.items.len() as u64);
+        for item in &self.items {
+            write_var_bytes(out, item);
+        }
+    }
+
+    pub fn consensus_decode(decoder: &mut Decoder) -> Result<Self, &'static str> {
+        let count = decoder.length()?;
+        let mut items = Vec::new();
+        for _ in 0..count {
+            items.push(decoder.var_bytes()?.to_vec());
+        }
+        Ok(Witness { items })
+    }
+}
+
+/// 交易输入
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn {
+    pub previous_output: OutPoint,
+    pub script_sig: Vec<u8>,
+    pub sequence: u32,
+    pub witness: Witness,
+}
+
+impl TxIn {
+    /// 未签名的输入（空 scriptSig 与见证）
+    pub fn new(previous_output: OutPoint, sequence: u32) -> Self {
+        TxIn {
+            previous_output,
+            script_sig: Vec::new(),
+            sequence,
+            witness: Witness::new(),
+        }
+    }
+
+    /// 编码 outpoint、scriptSig 与 sequence（见证数据单独编码）
+    pub fn consensus_encode(&self, out: &mut Vec<u8>) {
+        self.previous_output.consensus_encode(out);
+        write_var_bytes(out, &self.script_sig);
+        out.extend_from_slice(&self.sequence.to_le_bytes());
+    }
+
+    pub fn consensus_decode(decoder: &mut Decoder) -> Result<Self, &'static str> {
+        Ok(TxIn {
+            previous_output: OutPoint::consensus_decode(decoder)?,
+            script_sig: decoder.var_bytes()?.to_vec(),
+            sequence: decoder.u32()?,
+            witness: Witness::new(),
+        })
+    }
+}
+
+/// 交易输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    /// 金额（聪）
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+impl TxOut {
+    pub fn consensus_encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.value.to_le_bytes());
+        write_var_bytes(out, &self.script_pubkey);
+    }
+
+    pub fn consensus_decode(decoder: &mut Decoder) -> Result<Self, &'static str> {
+        Ok(TxOut {
+            value: decoder.u64()?,
+            script_pubkey: decoder.var_bytes()?.to_vec(),
+        })
+    }
+}
+
+/// 比特币交易
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub version: u32,
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    pub lock_time: u32,
+}
+
+impl Transaction {
+    /// 是否带有见证数据（序列化时使用 BIP144 的 marker 与 flag）
+    pub fn has_witness(&self) -> bool {
+        self.inputs.iter().any(|input| !input.witness.is_empty())
+    }
+
+    /// 是否为币基交易
+    pub fn is_coinbase(&self) -> bool {
+        self.inputs.len() == 1 && self.inputs[0].previous_output.is_null()
+    }
+
+    /// 按共识格式编码；有见证数据时写入 marker、flag 与各输入的见证
+    pub fn consensus_encode(&self, out: &mut Vec<u8>) {
+        self.encode(out, self.has_witness());
+    }
+
+    fn encode(&self, out: &mut Vec<u8>, include_witness: bool) {
+        out.extend_from_slice(&self.version.to_le_bytes());
+        if include_witness {
+            out.extend_from_slice(&[0x00, 0x01]);
+        }
+        write_compact_size(out, self.inputs.len() as u64);
+        for input in &self.inputs {
+            input.consensus_encode(out);
+        }
+        write_compact_size(out, self.outputs.len() as u64);
+        for output in &self.outputs {
+            output.consensus_encode(out);
+        }
+        if include_witness {
+            for input in &self.inputs {
+                input.witness.consensus_encode(out);
+            }
+        }
+        out.extend_from_slice(&self.lock_time.to_le_bytes());
+    }
+
+    /// 按共识格式解码，同时接受传统格式与隔离见证格式
+    pub fn consensus_decode(decoder: &mut Decoder) -> Result<Self, &'static str> {
+        let version = decoder.u32()?;
+
+        // 输入数为 0 的位置出现 0x00 即为 marker，其后必须是 flag 0x01
+        let mut input_count = decoder.length()?;
+        let segwit = input_count == 0;
+        if segwit {
+            if decoder.u8()? != 0x01 {
+                return Err("Invalid segwit flag");
+            }
+            input_count = decoder.length()?;
+        }
+
+        let mut inputs = Vec::new();
+        for _ in 0..input_count {
+            inputs.push(TxIn::consensus_decode(decoder)?);
+        }
+
+        let output_count = decoder.length()?;
+        let mut outputs = Vec::new();
+        for _ in 0..output_count {
+            outputs.push(TxOut::consensus_decode(decoder)?);
+        }
+
+        if segwit {
+            for input in inputs.iter_mut() {
+                input.witness = Witness::consensus_decode(decoder)?;
+            }
+            // 与 Bitcoin Core 一致：带 flag 却没有任何见证数据的编码无效
+            if inputs.iter().all(|input| input.witness.is_empty()) {
+                return Err("Superfluous witness flag");
+            }
+        }
+
+        let lock_time = decoder.u32()?;
+        Ok(Transaction { version, inputs, outputs, lock_time })
+    }
+
+    /// 解码完整的交易字节（不允许多余数据）
+    pub fn deserialize(data: &[u8]) -> Result<Self, &'static str> {
+        let mut decoder = Decoder::new(data);
+        let tx = Self::consensus_decode(&mut decoder)?;
+        decoder.finish()?;
+        Ok(tx)
+    }
+
+    /// 完整序列化（含见证）
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.consensus_encode(&mut out);
+        out
+    }
+
+    /// 不含见证数据的序列化（用于计算 txid）
+    pub fn serialize_no_witness(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out, false);
+        out
+    }
+
+    /// 交易 ID：不含见证序列化的双重 SHA-256（内部字节序）
+    pub fn txid(&self) -> [u8; 32] {
+        double_sha256(&self.serialize_no_witness())
+    }
+
+    /// 见证交易 ID：完整序列化的双重 SHA-256；没有见证数据时与 txid 相同
+    pub fn wtxid(&self) -> [u8; 32] {
+        double_sha256(&self.serialize())
+    }
+
+    /// 交易权重（BIP141）：非见证字节 × 3 + 全部字节
+    pub fn weight(&self) -> usize {
+        let base_size = self.serialize_no_witness().len();
+        let total_size = if self.has_witness() {
+            self.serialize().len()
+        } else {
+            base_size
+        };
+        base_size * (WITNESS_SCALE_FACTOR - 1) + total_size
+    }
+
+    /// 虚拟大小（vbytes），用于计算手续费率
+    pub fn vsize(&self) -> usize {
+        self.weight().div_ceil(WITNESS_SCALE_FACTOR)
+    }
+
+    /// 所有输出金额之和（溢出返回 None）
+    pub fn total_output_value(&self) -> Option<u64> {
+        self.outputs
+            .iter()
+            .try_fold(0u64, |total, output| total.checked_add(output.value))
+    }
+}
@@ -2,8 +2,7 @@ extern crate alloc;
 use alloc::vec::Vec;
 
 pub fn get_random_bytes(len: usize) -> Vec<u8> {
-    let mut buf = Vec::with_capacity(len);
-    buf.resize(len, 0u8);
+    let mut buf = alloc::vec![0u8; len];
     
     unsafe {
         furi_hal_random_fill_buf(buf.as_mut_ptr(), len as u32);
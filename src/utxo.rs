@@ -0,0 +1,452 @@
+extern crate alloc;
+
+use crate::bip32::DerivationPath;
+use crate::hex;
+use crate::script;
+use crate::storage;
+use crate::transaction::{txid_from_hex, OutPoint, Transaction, TxOut};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// UTXO 文件所在目录（与 PSBT 文件相同）
+pub const UTXO_DIR: &str = crate::psbt::PSBT_DIR;
+
+/// 单个文件最多读取的 UTXO 数
+pub const MAX_UTXOS: usize = 500;
+
+/// 1 BTC 对应的聪数
+const SATS_PER_BTC: u64 = 100_000_000;
+
+/// 观察钱包导出的一个未花费输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Utxo {
+    pub outpoint: OutPoint,
+    /// 金额（聪）
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+    /// 密钥的派生路径（文件没有给出时为 None，由钱包按地址查找）
+    pub path: Option<DerivationPath>,
+    /// 金额与脚本是否取自文件中给出的完整前序交易（已按 txid 核对）
+    pub verified: bool,
+}
+
+impl Utxo {
+    /// 对应的输出（签名哈希需要金额与脚本）
+    pub fn txout(&self) -> TxOut {
+        TxOut {
+            value: self.value,
+            script_pubkey: self.script_pubkey.clone(),
+        }
+    }
+}
+
+/// 读取并解析 SD 卡上的 UTXO 文件
+pub fn load(path: &str) -> Result<Vec<Utxo>, &'static str> {
+    let data = storage::load_file(&alloc::format!("{}\0", path))?;
+    parse(&data)
+}
+
+/// 解析 UTXO 列表，按首个非空白字符识别 JSON（'['）或 CSV
+///
+/// JSON 为对象数组（兼容 Bitcoin Core 的 listunspent 输出）；CSV 第一行为列名。
+/// 两种格式使用相同的字段：
+/// - txid、vout：必填
+/// - value（聪，整数）或 amount（BTC，最多 8 位小数）：二选一
+/// - scriptPubKey / script_pubkey（十六进制）或 address：二选一
+/// - path（例如 m/84'/0'/0'/0/3）或 desc（从描述符的密钥来源中提取路径）：可选
+/// - prev_tx（前序交易的原始十六进制）：可选；给出时金额与脚本取自它，
+///   上面的金额与脚本字段可以省略，写了则必须与它一致
+///
+/// 其他字段会被忽略。
+pub fn parse(data: &[u8]) -> Result<Vec<Utxo>, &'static str> {
+    let text = core::str::from_utf8(data).map_err(|_| "UTXO file is not UTF-8")?;
+    let text = text.trim_start_matches('\u{FEFF}').trim();
+    let utxos = if text.starts_with('[') {
+        parse_json(text)?
+    } else {
+        parse_csv(text)?
+    };
+
+    if utxos.is_empty() {
+        return Err("UTXO file is empty");
+    }
+    for (i, utxo) in utxos.iter().enumerate() {
+        if utxos[..i].iter().any(|other| other.outpoint == utxo.outpoint) {
+            return Err("Duplicate UTXO");
+        }
+    }
+    Ok(utxos)
+}
+
+/// 一条记录中认得的字段
+#[derive(Default)]
+struct Fields {
+    txid: Option<String>,
+    vout: Option<String>,
+    value: Option<String>,
+    amount: Option<String>,
+    script_pubkey: Option<String>,
+    address: Option<String>,
+    path: Option<String>,
+    desc: Option<String>,
+    prev_tx: Option<String>,
+}
+
+impl Fields {
+    fn set(&mut self, key: &str, value: String) {
+        let slot = match key.trim() {
+            "txid" => &mut self.txid,
+            "vout" => &mut self.vout,
+            "value" => &mut self.value,
+            "amount" => &mut self.amount,
+            "scriptPubKey" | "script_pubkey" => &mut self.script_pubkey,
+            "address" => &mut self.address,
+            "path" => &mut self.path,
+            "desc" => &mut self.desc,
+            "prev_tx" => &mut self.prev_tx,
+            _ => return,
+        };
+        *slot = Some(value);
+    }
+
+    fn into_utxo(self) -> Result<Utxo, &'static str> {
+        let txid = txid_from_hex(self.txid.as_deref().ok_or("UTXO txid missing")?.trim())?;
+        let vout = self
+            .vout
+            .as_deref()
+            .ok_or("UTXO vout missing")?
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| "Invalid UTXO vout")?;
+
+        let value = match (self.value.as_deref(), self.amount.as_deref()) {
+            (Some(sats), _) => Some(sats.trim().parse::<u64>().map_err(|_| "Invalid UTXO value")?),
+            (None, Some(btc)) => Some(parse_btc_amount(btc.trim())?),
+            (None, None) => None,
+        };
+        let script_pubkey = match (self.script_pubkey.as_deref(), self.address.as_deref()) {
+            (Some(script), _) => Some(hex::decode(script.trim()).map_err(|_| "Invalid UTXO script")?),
+            (None, Some(address)) => Some(script::from_address(address)?),
+            (None, None) => None,
+        };
+
+        // 前序交易受 txid 保护，金额与脚本以它为准
+        let (value, script_pubkey) = match self.prev_tx.as_deref() {
+            Some(prev_tx) => {
+                let output = prev_output(prev_tx.trim(), &txid, vout)?;
+                if value.is_some_and(|value| value != output.value)
+                    || script_pubkey.is_some_and(|script| script != output.script_pubkey)
+                {
+                    return Err("UTXO does not match previous transaction");
+                }
+                (output.value, output.script_pubkey)
+            }
+            None => (
+                value.ok_or("UTXO amount missing")?,
+                script_pubkey.ok_or("UTXO script missing")?,
+            ),
+        };
+        if value == 0 {
+            return Err("Invalid UTXO value");
+        }
+        if script_pubkey.is_empty() {
+            return Err("Invalid UTXO script");
+        }
+
+        let path = match (self.path.as_deref(), self.desc.as_deref()) {
+            (Some(path), _) if !path.trim().is_empty() => Some(DerivationPath::parse(path)?),
+            (_, Some(desc)) => descriptor_path(desc)?,
+            _ => None,
+        };
+
+        Ok(Utxo {
+            outpoint: OutPoint::new(txid, vout),
+            value,
+            script_pubkey,
+            path,
+            verified: self.prev_tx.is_some(),
+        })
+    }
+}
+
+/// 前序交易中 UTXO 指向的输出，交易必须与 UTXO 的 txid 相符
+fn prev_output(prev_tx: &str, txid: &[u8; 32], vout: u32) -> Result<TxOut, &'static str> {
+    let raw = hex::decode(prev_tx).map_err(|_| "Invalid previous transaction")?;
+    let prev_tx = Transaction::deserialize(&raw)?;
+    if prev_tx.txid() != *txid {
+        return Err("Previous transaction does not match UTXO");
+    }
+    prev_tx
+        .outputs
+        .get(vout as usize)
+        .cloned()
+        .ok_or("UTXO vout out of range")
+}
+
+/// 描述符中第一个密钥来源 [指纹/路径] 的路径部分
+///
+/// 例如 wpkh([73c5da0a/84h/0h/0h/0/3]02...)#checksum 得到 m/84h/0h/0h/0/3。
+fn descriptor_path(desc: &str) -> Result<Option<DerivationPath>, &'static str> {
+    let start = match desc.find('[') {
+        Some(start) => start + 1,
+        None => return Ok(None),
+    };
+    let end = desc[start..].find(']').ok_or("Invalid descriptor key origin")? + start;
+    let origin = &desc[start..end];
+    let path = match origin.find('/') {
+        Some(slash) => &origin[slash..],
+        None => "",
+    };
+    DerivationPath::parse(&alloc::format!("m{}", path)).map(Some)
+}
+
+/// 把 BTC 十进制金额转换为聪（不接受超过 8 位的小数和指数形式）
+fn parse_btc_amount(s: &str) -> Result<u64, &'static str> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let fraction = fraction.trim_end_matches('0');
+    if (whole.is_empty() && fraction.is_empty())
+        || fraction.len() > 8
+        || !whole.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err("Invalid UTXO amount");
+    }
+
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| "Invalid UTXO amount")?
+    };
+    let mut sats: u64 = 0;
+    for i in 0..8 {
+        let digit = fraction.as_bytes().get(i).map_or(0, |b| (b - b'0') as u64);
+        sats = sats * 10 + digit;
+    }
+    whole
+        .checked_mul(SATS_PER_BTC)
+        .and_then(|whole| whole.checked_add(sats))
+        .ok_or("Invalid UTXO amount")
+}
+
+/// CSV：第一行为列名，空行和以 # 开头的行被跳过，字段可以用双引号包围
+fn parse_csv(text: &str) -> Result<Vec<Utxo>, &'static str> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    let header: Vec<&str> = lines.next().ok_or("UTXO file is empty")?.split(',').map(unquote).collect();
+
+    let mut utxos = Vec::new();
+    for line in lines {
+        if utxos.len() == MAX_UTXOS {
+            return Err("Too many UTXOs");
+        }
+        let columns: Vec<&str> = line.split(',').map(unquote).collect();
+        if columns.len() != header.len() {
+            return Err("CSV column count mismatch");
+        }
+        let mut fields = Fields::default();
+        for (key, value) in header.iter().zip(columns) {
+            fields.set(key, String::from(value));
+        }
+        utxos.push(fields.into_utxo()?);
+    }
+    Ok(utxos)
+}
+
+fn unquote(field: &str) -> &str {
+    let field = field.trim();
+    field
+        .strip_prefix('"')
+        .and_then(|field| field.strip_suffix('"'))
+        .unwrap_or(field)
+}
+
+/// JSON：对象数组，字段值为字符串或数字，嵌套的数组与对象会被跳过
+fn parse_json(text: &str) -> Result<Vec<Utxo>, &'static str> {
+    let mut reader = JsonReader {
+        data: text.as_bytes(),
+        pos: 0,
+    };
+    let mut utxos = Vec::new();
+
+    reader.expect(b'[')?;
+    if !reader.consume(b']') {
+        loop {
+            if utxos.len() == MAX_UTXOS {
+                return Err("Too many UTXOs");
+            }
+            utxos.push(reader.object()?.into_utxo()?);
+            if reader.consume(b']') {
+                break;
+            }
+            reader.expect(b',')?;
+        }
+    }
+
+    reader.skip_whitespace();
+    if reader.pos != reader.data.len() {
+        return Err("Trailing data after JSON");
+    }
+    Ok(utxos)
+}
+
+/// 只支持 UTXO 列表所需子集的 JSON 读取器
+struct JsonReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl JsonReader<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.data.get(self.pos).is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.data.get(self.pos).copied()
+    }
+
+    fn consume(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), &'static str> {
+        if self.consume(byte) {
+            Ok(())
+        } else {
+            Err("Invalid JSON")
+        }
+    }
+
+    /// 读取一个对象，保留认得的字符串或数字字段
+    fn object(&mut self) -> Result<Fields, &'static str> {
+        let mut fields = Fields::default();
+        self.expect(b'{')?;
+        if self.consume(b'}') {
+            return Ok(fields);
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(b':')?;
+            match self.peek() {
+                Some(b'"') => {
+                    let value = self.string()?;
+                    fields.set(&key, value);
+                }
+                Some(b'-' | b'0'..=b'9') => {
+                    let value = self.number()?;
+                    fields.set(&key, String::from(value));
+                }
+                _ => self.skip_value(0)?,
+            }
+            if self.consume(b'}') {
+                return Ok(fields);
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    /// 字符串（只接受 ASCII 转义，UTXO 字段不会包含其他字符）
+    fn string(&mut self) -> Result<String, &'static str> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        loop {
+            let byte = *self.data.get(self.pos).ok_or("Invalid JSON")?;
+            self.pos += 1;
+            match byte {
+                b'"' => return Ok(out),
+                b'\\' => {
+                    let escaped = *self.data.get(self.pos).ok_or("Invalid JSON")?;
+                    self.pos += 1;
+                    out.push(match escaped {
+                        b'"' | b'\\' | b'/' => escaped as char,
+                        b'n' => '\n',
+                        b't' => '\t',
+                        b'r' => '\r',
+                        _ => return Err("Unsupported JSON escape"),
+                    });
+                }
+                0x00..=0x1F => return Err("Invalid JSON"),
+                _ => {
+                    // 多字节 UTF-8 序列按原样复制
+                    let start = self.pos - 1;
+                    while self.data.get(self.pos).is_some_and(|b| b & 0xC0 == 0x80) {
+                        self.pos += 1;
+                    }
+                    let chunk = core::str::from_utf8(&self.data[start..self.pos]).map_err(|_| "Invalid JSON")?;
+                    out.push_str(chunk);
+                }
+            }
+        }
+    }
+
+    /// 数字的原始文本
+    fn number(&mut self) -> Result<&str, &'static str> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .data
+            .get(self.pos)
+            .is_some_and(|b| matches!(b, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9'))
+        {
+            self.pos += 1;
+        }
+        core::str::from_utf8(&self.data[start..self.pos]).map_err(|_| "Invalid JSON")
+    }
+
+    /// 跳过任意值（嵌套深度有限，避免递归耗尽栈）
+    fn skip_value(&mut self, depth: usize) -> Result<(), &'static str> {
+        if depth > 8 {
+            return Err("JSON nested too deeply");
+        }
+        match self.peek().ok_or("Invalid JSON")? {
+            b'"' => self.string().map(|_| ()),
+            b'-' | b'0'..=b'9' => self.number().map(|_| ()),
+            b'[' => {
+                self.pos += 1;
+                if self.consume(b']') {
+                    return Ok(());
+                }
+                loop {
+                    self.skip_value(depth + 1)?;
+                    if self.consume(b']') {
+                        return Ok(());
+                    }
+                    self.expect(b',')?;
+                }
+            }
+            b'{' => {
+                self.pos += 1;
+                if self.consume(b'}') {
+                    return Ok(());
+                }
+                loop {
+                    self.string()?;
+                    self.expect(b':')?;
+                    self.skip_value(depth + 1)?;
+                    if self.consume(b'}') {
+                        return Ok(());
+                    }
+                    self.expect(b',')?;
+                }
+            }
+            _ => {
+                for literal in [&b"true"[..], b"false", b"null"] {
+                    if self.data[self.pos..].starts_with(literal) {
+                        self.pos += literal.len();
+                        return Ok(());
+                    }
+                }
+                Err("Invalid JSON")
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::address::Cryptocurrency;
+use crate::base58;
+use crate::fixed_string::FixedString;
+use crate::network::Network;
+use crate::secp256k1::SecretKey;
+
+/// 压缩公钥标志（WIF payload 末尾的 0x01）
+const COMPRESSED_FLAG: u8 = 0x01;
+
+/// 解码后的 WIF 私钥
+#[derive(Clone, Copy)]
+pub struct WifKey {
+    pub secret_key: SecretKey,
+    /// 对应地址是否使用压缩公钥
+    pub compressed: bool,
+    pub cryptocurrency: Cryptocurrency,
+    pub network: Network,
+}
+
+/// 将私钥编码为 WIF（Wallet Import Format）
+///
+/// payload = 版本字节 || 32 字节私钥 || 0x01（仅压缩），再做 Base58Check。
+pub fn encode(
+    secret_key: &SecretKey,
+    cryptocurrency: Cryptocurrency,
+    network: Network,
+    compressed: bool,
+) -> Result<FixedString<64>, &'static str> {
+    let version = cryptocurrency
+        .wif_prefix_for(network)
+        .ok_or("WIF not supported for this cryptocurrency")?;
+
+    let mut payload = [0u8; 34];
+    payload[0] = version;
+    payload[1..33].copy_from_slice(secret_key.as_bytes());
+    let payload_len = if compressed {
+        payload[33] = COMPRESSED_FLAG;
+        34
+    } else {
+        33
+    };
+
+    let mut encoded = [0u8; 64];
+    let len = base58::check_encode_into(&payload[..payload_len], &base58::BITCOIN, &mut encoded)?;
+    FixedString::from_bytes(&encoded[..len])
+}
+
+/// 解码 WIF 私钥，并根据版本字节识别币种与网络
+///
+/// 版本字节在多个币种间重复时（例如 BTC 与 RVN 的 0x80）优先识别为比特币；
+/// 测试网络之间无法区分，统一报告为 Testnet3。
+pub fn decode(wif: &str) -> Result<WifKey, &'static str> {
+    let mut payload = [0u8; 64];
+    let len = base58::check_decode_into(wif.trim().as_bytes(), &base58::BITCOIN, &mut payload)?;
+
+    let compressed = match len {
+        33 => false,
+        34 if payload[33] == COMPRESSED_FLAG => true,
+        _ => return Err("Invalid WIF length"),
+    };
+
+    let mut key_bytes = [0u8; 32];
+    key_bytes.copy_from_slice(&payload[1..33]);
+    let secret_key = SecretKey::from_bytes(&key_bytes)?;
+
+    let version = payload[0];
+    for cryptocurrency in Cryptocurrency::ALL {
+        for network in [Network::Mainnet, Network::Testnet3] {
+            if cryptocurrency.wif_prefix_for(network) == Some(version) {
+                return Ok(WifKey {
+                    secret_key,
+                    compressed,
+                    cryptocurrency,
+                    network,
+                });
+            }
+        }
+    }
+
+    Err("Unknown WIF version")
+}